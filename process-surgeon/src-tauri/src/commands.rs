@@ -1,324 +1,4380 @@
 // Commands module - Tauri IPC command handlers
-use crate::discovery::{ProcessEnricher, scan_listening_ports, scan_ports, find_port_users};
-use crate::docker::DockerResolver;
+use crate::config::{ConfigStore, PidRange};
+use crate::discovery::{
+    AddressClass, CpuSampler, GeoIpDatabase, PortQuery, PortScan, ProcessEnricher,
+    ReverseDnsResolver, check_port_available, check_reachability, describe_tunnel,
+    enrich_socket_traffic, ephemeral_port_range, find_port_users, format_age,
+    get_container_id_for_pid, interface_addresses, is_docker_process_name,
+    is_loopback_connection, is_orphaned, is_public_address, is_ssh_process_name,
+    is_sshd_process_name, live_pids, normalize_listen_address, primary_interface_ip,
+    ps_processes_info, reachable_addresses, read_process_cwd, read_process_env, read_systemd_unit,
+    read_windows_services, redact_secrets,
+    scan_listening_ports, scan_namespace_ports, scan_ports, scan_ports_cancellable,
+    scan_tcp_socket_states,
+    scan_unix_sockets, search_listening_ports, service_name, sshd_daemon_ports,
+    summarize_port_pressure, uptime_secs,
+    CPU_SAMPLE_INTERVAL, DEFAULT_PRESSURE_WARN_PERCENT,
+};
+use crate::docker::{
+    host_port_conflicts, is_version_mismatch, runtime_conflicts, ActionStatus, DockerResolver,
+    DEFAULT_STOP_TIMEOUT_SECS, RECREATE_WINDOW,
+};
+use crate::logging::LogBuffer;
+use crate::metrics::{render_prometheus, DEFAULT_TOP_PROCESSES};
 use crate::models::*;
-use crate::surgery::{ProcessTerminator, request_elevated_termination};
-use chrono::Utc;
-use std::collections::{HashMap, HashSet};
+use crate::proxy::{proxy_endpoints, render_proxy_config};
+use crate::report::render_process_report;
+use crate::schema::model_schema;
+use crate::snapshot::{
+    is_valid_snapshot_name, listeners_of, Snapshot, SnapshotStore, SNAPSHOT_DIR_NAME,
+};
+use crate::surgery::{
+    NamePattern, PidReused, ProcessTerminator, RiskFactors, assess_risk, check_process_safety,
+    check_process_safety_with_ports,
+    is_self_process, protected_ports, request_elevated_termination,
+    request_elevated_termination_many, supported_termination_signals, wait_for_pid_exit,
+    DEFAULT_ESCALATION_LADDER, ELEVATION_METHOD,
+};
+use chrono::{DateTime, Utc};
+use futures_util::StreamExt;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::net::IpAddr;
+use std::path::PathBuf;
 use std::sync::Arc;
-use tauri::State;
-use tokio::sync::RwLock;
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter, Manager, State, Window};
+use tauri_plugin_opener::OpenerExt;
+use tokio::sync::{Notify, RwLock};
+use tokio::time::MissedTickBehavior;
+use tokio_util::sync::CancellationToken;
 
 /// Application state managed by Tauri
 pub struct AppStateManager {
     pub docker: Arc<RwLock<DockerResolver>>,
     pub process_enricher: Arc<RwLock<ProcessEnricher>>,
+    /// Per-process CPU usage refreshed in the background, read by scans
+    pub cpu_sampler: Arc<CpuSampler>,
     pub terminator: Arc<RwLock<ProcessTerminator>>,
+    pub dns_resolver: Arc<RwLock<ReverseDnsResolver>>,
+    pub geoip: Arc<RwLock<Option<GeoIpDatabase>>>,
+    /// Background task started by `start_monitoring`, shared by every subscriber
+    pub monitor: Arc<RwLock<MonitorState>>,
+    /// Docker event subscription started alongside the monitor
+    pub container_events: Arc<RwLock<Option<JoinHandle<()>>>>,
+    /// Persisted user settings
+    pub config: Arc<RwLock<ConfigStore>>,
+    /// Cancels a caller's in-flight port scan when it starts a newer one
+    pub scan_tokens: Arc<RwLock<HashMap<ScanCaller, CancellationToken>>>,
+    /// PIDs paused with `suspend_process`, dropped when resumed or when they exit
+    pub suspended: Arc<RwLock<HashSet<u32>>>,
+    /// Background tasks started by `watch_port`, one per port
+    pub port_watches: Arc<RwLock<HashMap<u16, JoinHandle<()>>>>,
+    /// Set by `set_safe_mode`: no force kills, elevation, or container kill/remove
+    pub safe_mode: Arc<RwLock<bool>>,
+    /// Named listener baselines saved next to the settings file
+    pub snapshots: Arc<SnapshotStore>,
+    /// Latest process list from `get_processes` or the monitor, for `get_processes_if_newer`
+    pub last_scan: Arc<RwLock<ScanCache>>,
+    /// Lets `cancel_drain` end a `drain_and_stop` wait, keyed by PID
+    pub drains: Arc<RwLock<HashMap<u32, CancellationToken>>>,
+}
+
+/// The single monitoring loop and the windows that share it
+#[derive(Debug, Default)]
+pub struct MonitorState {
+    pub task: Option<JoinHandle<()>>,
+    /// Settings of each window that has not yet called `stop_monitoring`, by label
+    pub subscribers: HashMap<String, MonitorSettings>,
+    /// Wakes the loop to pick up changed subscribers without waiting for its next tick
+    pub settings_changed: Arc<Notify>,
+}
+
+/// What one window asked `start_monitoring` for
+#[derive(Debug, Clone)]
+pub struct MonitorSettings {
+    pub interval: Duration,
+    pub show_all_connections: bool,
+    pub thresholds: Option<ResourceThresholds>,
+}
+
+/// Callers whose scans supersede their own earlier scan while it is still running
+///
+/// A scan only cancels an older one from the same caller, so the monitor loop and a UI
+/// refresh never cancel each other. Scans from other callers are never cancelled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScanCaller {
+    /// `get_processes`, the UI's refresh
+    Ui,
+    /// The `start_monitoring` loop
+    Monitor,
+}
+
+/// The most recent scan handed to the UI, numbered so pollers can skip unchanged ones
+///
+/// Only scans made with default options are kept, untruncated, so every poller sees
+/// the same list whatever filters the scanning caller asked for.
+#[derive(Debug, Default)]
+pub struct ScanCache {
+    /// Incremented on every recorded scan; 0 until the first one
+    pub generation: u64,
+    pub state: Option<AppState>,
+}
+
+impl ScanCache {
+    /// Keep a scan under the next generation, unless it was made with non-default options
+    fn record(&mut self, options: ScanOptions, app_state: &AppState) {
+        if options != ScanOptions::default() {
+            return;
+        }
+        self.generation += 1;
+        self.state = Some(app_state.clone());
+    }
+
+    /// The cached scan cut to `max_results` if it is newer than `client_generation`
+    fn update_since(&self, client_generation: u64, max_results: usize) -> ScanUpdate {
+        match &self.state {
+            Some(app_state) if self.generation > client_generation => {
+                let mut app_state = app_state.clone();
+                truncate_processes(&mut app_state, max_results);
+                ScanUpdate::Updated {
+                    generation: self.generation,
+                    state: app_state,
+                }
+            }
+            _ => ScanUpdate::NotModified {
+                generation: self.generation,
+            },
+        }
+    }
 }
 
 impl AppStateManager {
-    pub async fn new() -> Self {
+    /// Create the state, loading user settings from `config_path` when given
+    ///
+    /// Protected ports and PID ranges from the settings replace the built-in ones.
+    /// Snapshots are kept in a directory beside the settings file.
+    pub async fn new(config_path: Option<PathBuf>) -> Self {
+        let config = match &config_path {
+            Some(path) => {
+                let config = ConfigStore::load(path.clone());
+                apply_protection_settings(&config);
+                config
+            }
+            None => ConfigStore::default(),
+        };
+        let snapshot_dir = config_path
+            .as_ref()
+            .and_then(|path| path.parent())
+            .map(|dir| dir.join(SNAPSHOT_DIR_NAME));
+
         Self {
             docker: Arc::new(RwLock::new(DockerResolver::new().await)),
             process_enricher: Arc::new(RwLock::new(ProcessEnricher::new())),
+            cpu_sampler: Arc::new(CpuSampler::start(CPU_SAMPLE_INTERVAL)),
             terminator: Arc::new(RwLock::new(ProcessTerminator::new())),
+            dns_resolver: Arc::new(RwLock::new(ReverseDnsResolver::new())),
+            geoip: Arc::new(RwLock::new(None)),
+            monitor: Arc::new(RwLock::new(MonitorState::default())),
+            container_events: Arc::new(RwLock::new(None)),
+            config: Arc::new(RwLock::new(config)),
+            scan_tokens: Arc::new(RwLock::new(HashMap::new())),
+            suspended: Arc::new(RwLock::new(HashSet::new())),
+            port_watches: Arc::new(RwLock::new(HashMap::new())),
+            safe_mode: Arc::new(RwLock::new(false)),
+            snapshots: Arc::new(SnapshotStore::new(snapshot_dir)),
+            last_scan: Arc::new(RwLock::new(ScanCache::default())),
+            drains: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+}
+
+/// Build a port entry from a scanned socket, resolving its well-known service name
+///
+/// `listeners` holds the namespaced ports something listens on; an established socket
+/// on one of them is the accepting side and is named after its local port.
+fn to_port_entry(port_info: &PortInfo, listeners: &HashSet<NamespacedPort>) -> PortEntry {
+    let accepted = port_info.state != SocketState::Listening
+        && listeners.contains(&namespaced_port(
+            port_info.protocol,
+            port_info.local_port,
+            &port_info.netns,
+        ));
+    // Outbound connections are described by their remote end, everything else by its local port
+    let service = match port_info.remote_port {
+        Some(remote_port) if remote_port != 0 && !accepted => {
+            service_name(remote_port, port_info.protocol)
+        }
+        _ => service_name(port_info.local_port, port_info.protocol),
+    };
+
+    PortEntry {
+        protocol: port_info.protocol,
+        local_address: port_info.local_address.clone(),
+        local_port: port_info.local_port,
+        remote_address: port_info.remote_address.clone(),
+        remote_port: port_info.remote_port,
+        state: port_info.state,
+        is_loopback: port_info.is_loopback,
+        is_wildcard: port_info.is_wildcard,
+        is_ipv6: port_info.is_ipv6,
+        remote_hostname: None,
+        service_name: service.map(|s| s.to_string()),
+        rx_bytes: port_info.rx_bytes,
+        tx_bytes: port_info.tx_bytes,
+        remote_asn: None,
+        connection_count: 0,
+        shared_port: false,
+        inherited_socket: false,
+        port_key: port_key(port_info),
+        netns: port_info.netns.clone(),
+    }
+}
+
+/// Stable per-node key for a socket, e.g. `tcp:53` or `udp:53@loopback`
+///
+/// Wildcard binds get the bare `protocol:port` form; other binds are qualified with
+/// their normalized local address so keys stay unique within a node.
+fn port_key(port_info: &PortInfo) -> String {
+    let protocol = match port_info.protocol {
+        Protocol::TCP => "tcp",
+        Protocol::UDP => "udp",
+    };
+    let key = format!("{}:{}", protocol, port_info.local_port);
+
+    match normalize_listen_address(&port_info.local_address) {
+        AddressClass::Wildcard => key,
+        class => format!("{}@{}", key, class),
+    }
+}
+
+/// Id of a node holding one socket, stable across scans
+///
+/// One node per socket, so the id tells apart a PID's TCP and UDP or IPv4 and IPv6
+/// sockets on the same port by `port_key` plus the exact local address. Connections
+/// also carry their remote end, since accepted ones share the listener's local port.
+fn socket_node_id(pid: u32, port_info: &PortInfo) -> String {
+    let id = format!("{}-{}/{}", pid, port_key(port_info), port_info.local_address);
+    match (&port_info.remote_address, port_info.remote_port) {
+        (Some(address), Some(port)) => format!("{}->{}:{}", id, address, port),
+        _ => id,
+    }
+}
+
+/// Fill in `remote_hostname` for every port entry with a resolvable remote address
+async fn resolve_remote_hostnames(resolver: &ReverseDnsResolver, processes: &mut [ProcessNode]) {
+    let remote_ips: Vec<IpAddr> = processes
+        .iter()
+        .flat_map(|p| p.ports.iter())
+        .filter_map(|port| port.remote_address.as_deref())
+        .filter_map(|addr| addr.parse().ok())
+        .collect();
+
+    if remote_ips.is_empty() {
+        return;
+    }
+
+    let hostnames = resolver.lookup_many(&remote_ips).await;
+
+    for port in processes.iter_mut().flat_map(|p| p.ports.iter_mut()) {
+        port.remote_hostname = port
+            .remote_address
+            .as_deref()
+            .and_then(|addr| addr.parse::<IpAddr>().ok())
+            .and_then(|ip| hostnames.get(&ip).cloned());
+    }
+}
+
+/// Attach ASN details to established connections with a public remote address
+fn annotate_remote_asn(database: &GeoIpDatabase, processes: &mut [ProcessNode]) {
+    for port in processes.iter_mut().flat_map(|p| p.ports.iter_mut()) {
+        if port.state != SocketState::Established {
+            continue;
         }
+
+        port.remote_asn = port
+            .remote_address
+            .as_deref()
+            .and_then(|addr| addr.parse::<IpAddr>().ok())
+            .filter(|ip| is_public_address(*ip))
+            .and_then(|ip| database.lookup(ip));
     }
 }
 
+/// Options controlling how `get_processes` scans and enriches sockets
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct ScanOptions {
+    show_all_connections: bool,
+    resolve_hostnames: bool,
+    include_traffic: bool,
+    include_unix_sockets: bool,
+    /// Drop established connections whose both ends are loopback
+    exclude_loopback: bool,
+    /// Also scan network namespaces other than ours (Linux only)
+    include_netns: bool,
+}
+
 /// Fetch all processes with their port bindings
+///
+/// Reverse DNS for remote addresses (`resolve_hostnames`), per-socket byte counters
+/// (`include_traffic`) and Unix domain sockets (`include_unix_sockets`) are opt-in
+/// since each adds latency. `exclude_loopback` hides loopback-to-loopback connections
+/// while keeping loopback listeners. `include_netns` adds sockets from other network
+/// namespaces (containers, `ip netns`), which means walking every process on Linux.
 #[tauri::command]
 pub async fn get_processes(
     state: State<'_, AppStateManager>,
     show_all_connections: bool,
+    resolve_hostnames: Option<bool>,
+    include_traffic: Option<bool>,
+    include_unix_sockets: Option<bool>,
+    exclude_loopback: Option<bool>,
+    include_netns: Option<bool>,
 ) -> Result<AppState, AppError> {
-    log::debug!("Fetching processes, show_all: {}", show_all_connections);
+    let options = ScanOptions {
+        show_all_connections,
+        resolve_hostnames: resolve_hostnames.unwrap_or(false),
+        include_traffic: include_traffic.unwrap_or(false),
+        include_unix_sockets: include_unix_sockets.unwrap_or(false),
+        exclude_loopback: exclude_loopback.unwrap_or(false),
+        include_netns: include_netns.unwrap_or(false),
+    };
 
-    // Scan ports
-    let ports = if show_all_connections {
-        scan_ports().map_err(|e| AppError::new("SCAN_ERROR", &e.to_string()))?
-    } else {
-        scan_listening_ports().map_err(|e| AppError::new("SCAN_ERROR", &e.to_string()))?
+    let mut app_state = build_app_state_for(&state, options, ScanCaller::Ui).await?;
+    record_scan(&state, options, &app_state).await;
+    let max_results = state.config.read().await.max_results();
+    truncate_processes(&mut app_state, max_results);
+    Ok(app_state)
+}
+
+/// Return the latest scan only if it is newer than `client_generation`
+///
+/// Scans are recorded by `get_processes` and the monitor, so this never scans itself.
+/// Only scans with default options are recorded, e.g. not ones showing every
+/// connection, and the reply is cut to the configured scan limit like `get_processes`.
+/// Pass 0 to get whatever scan is cached, then the generation from each reply.
+#[tauri::command]
+pub async fn get_processes_if_newer(
+    state: State<'_, AppStateManager>,
+    client_generation: u64,
+) -> Result<ScanUpdate, AppError> {
+    let max_results = state.config.read().await.max_results();
+    let cache = state.last_scan.read().await;
+    Ok(cache.update_since(client_generation, max_results))
+}
+
+/// Every socket straight from the OS, without process, Docker or safety enrichment
+///
+/// Much faster than `get_processes`, e.g. for a first paint while the full scan runs.
+/// Processes are only known by PID here.
+#[tauri::command]
+pub async fn get_ports_only() -> Result<Vec<PortInfo>, AppError> {
+    scan_owned_sockets().await
+}
+
+/// Cache a default-option scan for `get_processes_if_newer` under the next generation
+async fn record_scan(state: &AppStateManager, options: ScanOptions, app_state: &AppState) {
+    state.last_scan.write().await.record(options, app_state);
+}
+
+/// Cut `processes` to `max_results`, flagging the state so the UI can ask for a filter
+///
+/// Totals like `total_connections` keep describing the full scan.
+fn truncate_processes(app_state: &mut AppState, max_results: usize) {
+    if app_state.processes.len() > max_results {
+        log::warn!(
+            "Returning {} of {} processes; raise the limit with set_scan_limit",
+            max_results,
+            app_state.processes.len()
+        );
+        app_state.processes.truncate(max_results);
+        app_state.truncated = true;
+    }
+}
+
+/// Set how many process nodes `get_processes` returns before truncating
+#[tauri::command]
+pub async fn set_scan_limit(
+    state: State<'_, AppStateManager>,
+    n: usize,
+) -> Result<(), AppError> {
+    if n == 0 {
+        return Err(AppError::new(ErrorCode::InvalidInput, "The scan limit must be at least 1"));
+    }
+
+    let mut config = state.config.write().await;
+    config.settings.max_results = Some(n);
+    save_config(&config)
+}
+
+/// Default number of consecutive samples over a threshold before alerting
+const DEFAULT_ALERT_SAMPLES: u32 = 3;
+
+/// Shortest allowed monitoring interval
+const MIN_MONITOR_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Start periodic scanning, emitting `processes-updated` with each new `AppState`
+///
+/// A `scan-diff` event follows whenever processes or their ports changed since the
+/// previous sample. With `thresholds`, processes that stay over a CPU or memory limit
+/// for several consecutive samples also trigger a `resource-alert` event. When Docker
+/// is available, container starts and stops are pushed as `container-event` events as
+/// they happen rather than at the next sample.
+///
+/// Process updates go to every window, so all callers share one loop. Each call
+/// subscribes the calling window with its own settings, and the loop keeps running
+/// until each subscribed window has called `stop_monitoring` or been closed. The loop
+/// samples at the shortest interval any window asked for and lists all connections
+/// when any window wants them; resource alerts only go to the window whose thresholds
+/// were crossed. Calling again from an already subscribed window only changes its
+/// settings. Intervals below 500ms are raised to it, and ticks that come due while a
+/// scan is still running are skipped rather than queued up behind it.
+#[tauri::command]
+pub async fn start_monitoring(
+    app: AppHandle,
+    window: Window,
+    state: State<'_, AppStateManager>,
+    interval_ms: u64,
+    show_all_connections: bool,
+    thresholds: Option<ResourceThresholds>,
+) -> Result<(), AppError> {
+    let settings = MonitorSettings {
+        interval: Duration::from_millis(interval_ms).max(MIN_MONITOR_INTERVAL),
+        show_all_connections,
+        thresholds,
     };
 
-    // Collect unique PIDs
-    let all_pids: Vec<u32> = ports.iter().flat_map(|p| p.pids.clone()).collect();
-    let unique_pids: Vec<u32> = {
-        let mut pids = all_pids.clone();
-        pids.sort();
-        pids.dedup();
-        pids
+    let subscribers = {
+        let mut monitor = state.monitor.write().await;
+        monitor.subscribers.insert(window.label().to_string(), settings);
+        monitor.settings_changed.notify_one();
+        if monitor.task.as_ref().is_some_and(|task| !task.inner().is_finished()) {
+            log::debug!("Monitoring settings changed for {}", window.label());
+            return Ok(());
+        }
+        let task = spawn_monitor(app.clone(), monitor.settings_changed.clone());
+        monitor.task = Some(task);
+        monitor.subscribers.len()
     };
+    subscribe_container_events(app, &state).await;
+    log::info!("Monitoring started ({} subscribers)", subscribers);
+    Ok(())
+}
 
-    // Refresh and get process info
-    let enricher = state.process_enricher.read().await;
-    let process_map = enricher.get_processes_info(&unique_pids);
+/// The sampling interval and scan options that satisfy every subscriber
+fn combined_monitor_settings(
+    subscribers: &HashMap<String, MonitorSettings>,
+) -> (Duration, ScanOptions) {
+    let interval = subscribers
+        .values()
+        .map(|settings| settings.interval)
+        .min()
+        .unwrap_or(MIN_MONITOR_INTERVAL);
+    let options = ScanOptions {
+        show_all_connections: subscribers.values().any(|s| s.show_all_connections),
+        ..Default::default()
+    };
+    (interval, options)
+}
 
-    // Refresh Docker port map
-    let docker = state.docker.read().await;
-    if docker.is_available() {
-        let _ = docker.refresh().await;
-    }
+/// Run the monitoring loop for the subscribers in `AppStateManager::monitor`
+///
+/// Subscribers are re-read before every tick; `settings_changed` cuts a wait short so
+/// a shorter interval takes effect at once.
+fn spawn_monitor(app: AppHandle, settings_changed: Arc<Notify>) -> JoinHandle<()> {
+    tauri::async_runtime::spawn(async move {
+        let mut trackers: HashMap<String, ThresholdTracker> = HashMap::new();
+        let mut interval = Duration::ZERO;
+        let mut ticker = tokio::time::interval(MIN_MONITOR_INTERVAL);
+        let mut previous: Option<AppState> = None;
 
-    // Build process nodes grouped by PID
-    // Use a set to track unique ports per process (protocol + port + address)
-    let mut pid_to_ports: HashMap<u32, Vec<PortEntry>> = HashMap::new();
-    let mut pid_seen_ports: HashMap<u32, HashSet<(Protocol, u16, String)>> = HashMap::new();
-    
-    for port_info in &ports {
-        let port_entry = PortEntry {
-            protocol: port_info.protocol,
-            local_address: port_info.local_address.clone(),
-            local_port: port_info.local_port,
-            remote_address: port_info.remote_address.clone(),
-            remote_port: port_info.remote_port,
-            state: port_info.state,
-        };
-        
-        // Create a key for deduplication (protocol + port + normalized address)
-        // Normalize address: treat 0.0.0.0, ::, and specific IPs as potentially the same listening port
-        let normalized_addr = if port_info.local_address == "0.0.0.0" || 
-                                 port_info.local_address == "::" || 
-                                 port_info.local_address == "::1" ||
-                                 port_info.local_address == "127.0.0.1" {
-            "any".to_string()
-        } else {
-            port_info.local_address.clone()
-        };
-        let port_key = (port_info.protocol, port_info.local_port, normalized_addr);
-        
-        for &pid in &port_info.pids {
-            let seen = pid_seen_ports.entry(pid).or_insert_with(HashSet::new);
-            
-            // Only add if we haven't seen this port combination for this PID
-            if seen.insert(port_key.clone()) {
-                pid_to_ports
-                    .entry(pid)
-                    .or_insert_with(Vec::new)
-                    .push(port_entry.clone());
+        loop {
+            let state = app.state::<AppStateManager>();
+            let subscribers = state.monitor.read().await.subscribers.clone();
+            let (wanted, options) = combined_monitor_settings(&subscribers);
+            if wanted != interval {
+                interval = wanted;
+                ticker = tokio::time::interval(interval);
+                ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+            }
+            tokio::select! {
+                _ = ticker.tick() => {}
+                _ = settings_changed.notified() => continue,
+            }
+
+            let app_state = match build_app_state_for(&state, options, ScanCaller::Monitor).await {
+                Ok(app_state) => app_state,
+                Err(e) if e.code == ErrorCode::ScanCancelled => {
+                    log::debug!("Monitoring scan superseded");
+                    continue;
+                }
+                Err(e) => {
+                    log::warn!("Monitoring scan failed: {}", e.message);
+                    continue;
+                }
+            };
+
+            // Each window keeps its own streaks, dropped once it unsubscribes
+            trackers.retain(|label, _| subscribers.contains_key(label));
+            for (label, settings) in &subscribers {
+                let Some(thresholds) = &settings.thresholds else {
+                    continue;
+                };
+                let tracker = trackers.entry(label.clone()).or_default();
+                for alert in tracker.observe(&app_state.processes, thresholds) {
+                    if let Err(e) = app.emit_to(label.as_str(), "resource-alert", alert) {
+                        log::debug!("Failed to emit resource alert: {}", e);
+                    }
+                }
+            }
+
+            if let Some(previous) = &previous {
+                let diff = scan_diff(previous, &app_state);
+                if !diff.is_empty() {
+                    if let Err(e) = app.emit("scan-diff", diff) {
+                        log::debug!("Failed to emit scan diff: {}", e);
+                    }
+                }
+            }
+
+            record_scan(&state, options, &app_state).await;
+            if let Err(e) = app.emit("processes-updated", &app_state) {
+                log::debug!("Failed to emit process update: {}", e);
+            }
+            previous = Some(app_state);
+        }
+    })
+}
+
+/// Forward container lifecycle events as `container-event`, replacing any subscription
+///
+/// Each event also drops the container's cached port mappings. The task ends on its
+/// own when the engine goes away.
+async fn subscribe_container_events(app: AppHandle, state: &AppStateManager) {
+    let events = state.docker.read().await.container_events();
+    let task = match events {
+        Ok(mut events) => tauri::async_runtime::spawn(async move {
+            while let Some(event) = events.next().await {
+                let event = match event {
+                    Ok(event) => event,
+                    Err(e) => {
+                        log::info!("Container event stream ended: {}", e);
+                        break;
+                    }
+                };
+
+                let state = app.state::<AppStateManager>();
+                state.docker.read().await.invalidate_container(&event.id).await;
+                if let Err(e) = app.emit("container-event", &event) {
+                    log::debug!("Failed to emit container event: {}", e);
+                }
+            }
+        }),
+        Err(e) => {
+            log::debug!("Not subscribing to container events: {}", e);
+            if let Some(previous) = state.container_events.write().await.take() {
+                previous.abort();
             }
+            return;
         }
+    };
+
+    if let Some(previous) = state.container_events.write().await.replace(task) {
+        previous.abort();
     }
+}
 
-    // Create ProcessNodes
-    let mut processes: Vec<ProcessNode> = Vec::new();
-    
-    for (pid, ports) in pid_to_ports {
-        let is_docker = enricher.is_docker_proxy(pid);
-        
-        // Try to get container info for first port
-        let container = if is_docker && docker.is_available() {
-            if let Some(first_port) = ports.first() {
-                docker.get_container_for_port(first_port.local_port).await
-            } else {
-                None
+/// Unsubscribe the calling window, stopping the loop when it was the last subscriber
+#[tauri::command]
+pub async fn stop_monitoring(
+    window: Window,
+    state: State<'_, AppStateManager>,
+) -> Result<(), AppError> {
+    unsubscribe_monitoring(&state, window.label()).await;
+    Ok(())
+}
+
+/// Drop a window's monitoring subscription, e.g. when it is closed without calling
+/// `stop_monitoring`, and stop the loop once nobody is subscribed
+pub async fn unsubscribe_monitoring(state: &AppStateManager, label: &str) {
+    let task = {
+        let mut monitor = state.monitor.write().await;
+        monitor.subscribers.remove(label);
+        if !monitor.subscribers.is_empty() {
+            monitor.settings_changed.notify_one();
+            log::debug!("Monitoring kept for {} subscribers", monitor.subscribers.len());
+            return;
+        }
+        monitor.task.take()
+    };
+
+    if let Some(task) = state.container_events.write().await.take() {
+        task.abort();
+    }
+    if let Some(task) = task {
+        task.abort();
+        if let Some(token) = state.scan_tokens.read().await.get(&ScanCaller::Monitor) {
+            token.cancel();
+        }
+        log::info!("Monitoring stopped");
+    }
+}
+
+/// How often `watch_port` polls when the caller gives no interval
+const DEFAULT_PORT_WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Watch one port, emitting `port-state-changed` whenever its owning PIDs change
+///
+/// Returns the current owners as the baseline; events only follow changes from it.
+/// Watching a port that is already watched keeps the existing watcher.
+#[tauri::command]
+pub async fn watch_port(
+    app: AppHandle,
+    state: State<'_, AppStateManager>,
+    port: u16,
+    interval_ms: Option<u64>,
+) -> Result<Vec<u32>, AppError> {
+    let interval = interval_ms
+        .map(Duration::from_millis)
+        .unwrap_or(DEFAULT_PORT_WATCH_INTERVAL)
+        .max(MIN_MONITOR_INTERVAL);
+
+    let baseline = tokio::task::spawn_blocking(move || find_port_users(port))
+        .await
+        .map_err(anyhow::Error::from)
+        .and_then(|result| result)
+        .map(|ports| port_owner_pids(&ports))
+        .map_err(|e| AppError::new(ErrorCode::ScanError, &e.to_string()))?;
+
+    let mut watches = state.port_watches.write().await;
+    if watches.contains_key(&port) {
+        log::debug!("Port {} is already watched", port);
+        return Ok(baseline);
+    }
+
+    let mut previous = baseline.clone();
+    let task = tauri::async_runtime::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        // The first tick fires immediately and the baseline is already known
+        ticker.tick().await;
+
+        loop {
+            ticker.tick().await;
+
+            let scanned = tokio::task::spawn_blocking(move || find_port_users(port))
+                .await
+                .map_err(anyhow::Error::from)
+                .and_then(|result| result);
+            let pids = match scanned {
+                Ok(ports) => port_owner_pids(&ports),
+                Err(e) => {
+                    log::debug!("Watch of port {} failed to scan: {}", port, e);
+                    continue;
+                }
+            };
+            if pids == previous {
+                continue;
             }
-        } else {
-            None
-        };
 
-        let (name, exe_path, command_line, user, memory_usage, cpu_usage, start_time) =
-            if let Some(info) = process_map.get(&pid) {
+            let change = PortStateChange {
+                port,
+                previous_pids: std::mem::replace(&mut previous, pids.clone()),
+                pids,
+                at: Utc::now(),
+            };
+            if let Err(e) = app.emit("port-state-changed", change) {
+                log::debug!("Failed to emit port state change: {}", e);
+            }
+        }
+    });
+    watches.insert(port, task);
+
+    log::info!("Watching port {} (every {:?})", port, interval);
+    Ok(baseline)
+}
+
+/// Stop the watcher started by `watch_port`, if any
+#[tauri::command]
+pub async fn stop_watch_port(
+    state: State<'_, AppStateManager>,
+    port: u16,
+) -> Result<(), AppError> {
+    if let Some(task) = state.port_watches.write().await.remove(&port) {
+        task.abort();
+        log::info!("Stopped watching port {}", port);
+    }
+    Ok(())
+}
+
+/// Distinct PIDs holding any socket on a port, sorted
+fn port_owner_pids(ports: &[PortInfo]) -> Vec<u32> {
+    let mut pids: Vec<u32> = ports.iter().flat_map(|p| p.pids.iter().copied()).collect();
+    pids.sort_unstable();
+    pids.dedup();
+    pids
+}
+
+/// Consecutive-sample counters per (PID, threshold), so a single spike doesn't alert
+#[derive(Debug, Default)]
+struct ThresholdTracker {
+    streaks: HashMap<(u32, ThresholdKind), u32>,
+}
+
+impl ThresholdTracker {
+    /// Record one sample and return alerts for streaks that just reached the limit
+    fn observe(
+        &mut self,
+        processes: &[ProcessNode],
+        thresholds: &ResourceThresholds,
+    ) -> Vec<ResourceAlert> {
+        let required = thresholds
+            .consecutive_samples
+            .unwrap_or(DEFAULT_ALERT_SAMPLES)
+            .max(1);
+        let mut streaks = HashMap::new();
+        let mut alerts = Vec::new();
+
+        for process in processes {
+            let over = [
                 (
-                    info.name.clone(),
-                    info.exe_path.clone(),
-                    info.command_line.clone(),
-                    info.user.clone(),
-                    info.memory_usage,
-                    info.cpu_usage,
-                    info.start_time,
-                )
-            } else {
+                    ThresholdKind::Cpu,
+                    thresholds.cpu_percent.is_some_and(|max| process.cpu_usage > max),
+                ),
                 (
-                    "Unknown".to_string(),
-                    None,
-                    None,
-                    "Unknown".to_string(),
-                    0,
-                    0.0,
-                    None,
-                )
-            };
+                    ThresholdKind::Memory,
+                    thresholds.memory_bytes.is_some_and(|max| process.memory_usage > max),
+                ),
+            ];
 
-        // Check if process is protected
-        let safety = crate::surgery::check_process_safety(pid, &name);
-        let is_protected = !safety.is_safe();
+            for (kind, exceeded) in over {
+                // One node per PID and port may repeat a process; count it once per sample
+                if !exceeded || streaks.contains_key(&(process.pid, kind)) {
+                    continue;
+                }
 
-        let node = ProcessNode {
-            id: format!("{}-{}", pid, ports.first().map(|p| p.local_port).unwrap_or(0)),
-            pid,
-            name,
-            exe_path,
-            command_line,
-            user,
-            memory_usage,
-            cpu_usage,
-            start_time,
-            ports,
-            is_docker_proxy: is_docker,
-            container,
-            is_protected,
-        };
-        
-        processes.push(node);
+                let samples = self.streaks.get(&(process.pid, kind)).copied().unwrap_or(0) + 1;
+                streaks.insert((process.pid, kind), samples);
+
+                // Alert once when the streak reaches the limit, not on every sample after
+                if samples == required {
+                    alerts.push(ResourceAlert {
+                        process: process.clone(),
+                        threshold: kind,
+                        samples,
+                    });
+                }
+            }
+        }
+
+        // Streaks that were not extended this sample, including exited PIDs, reset
+        self.streaks = streaks;
+        alerts
     }
+}
 
-    // Sort by PID for consistency
-    processes.sort_by_key(|p| p.pid);
+/// Fetch processes, containers and Docker availability in a single call
+///
+/// Meant for startup; the individual commands remain for incremental refreshes.
+#[tauri::command]
+pub async fn get_dashboard(
+    state: State<'_, AppStateManager>,
+    show_all_connections: bool,
+) -> Result<Dashboard, AppError> {
+    let options = ScanOptions {
+        show_all_connections,
+        ..Default::default()
+    };
+    let app_state = build_app_state(&state, options).await?;
 
-    let listening_count = processes
-        .iter()
-        .filter(|p| p.ports.iter().any(|port| matches!(port.state, SocketState::Listening)))
-        .count();
+    // The scan already refreshed the Docker port map; only the full listing remains
+    let docker = state.docker.read().await;
+    let containers = if app_state.docker_available {
+        docker.get_all_containers().await.unwrap_or_else(|e| {
+            log::warn!("Failed to list containers: {}", e);
+            Vec::new()
+        })
+    } else {
+        Vec::new()
+    };
 
-    Ok(AppState {
-        processes,
-        total_connections: ports.len(),
-        listening_ports: listening_count,
-        docker_available: docker.is_available(),
-        last_updated: Utc::now(),
+    Ok(Dashboard {
+        docker_available: app_state.docker_available,
+        app_state,
+        containers,
     })
 }
 
-/// Find processes using a specific port
+/// Export the current scan as Prometheus text-format metrics
+///
+/// Per-process gauges are limited to the `top_n` heaviest processes by CPU and by
+/// memory (10 by default).
 #[tauri::command]
-pub async fn find_port(
+pub async fn metrics_prometheus(
+    state: State<'_, AppStateManager>,
+    top_n: Option<usize>,
+) -> Result<String, AppError> {
+    let app_state = build_app_state(&state, ScanOptions::default()).await?;
+    Ok(render_prometheus(&app_state, top_n.unwrap_or(DEFAULT_TOP_PROCESSES)))
+}
+
+/// JSON Schema (draft 7) of `AppState`, `ProcessNode`, `ContainerInfo`, `KillResult`
+/// and `AppError`, for checking the frontend's type definitions against the backend
+#[tauri::command]
+pub fn export_type_schema() -> serde_json::Value {
+    model_schema()
+}
+
+/// Fetch only listeners owned by Docker's port proxy or published by a container
+///
+/// Every port is checked against the container port map, not just the first one.
+/// Returns an empty list when Docker is unavailable.
+#[tauri::command]
+pub async fn get_docker_ports(
     state: State<'_, AppStateManager>,
-    port: u16,
 ) -> Result<Vec<ProcessNode>, AppError> {
-    let ports = find_port_users(port).map_err(|e| AppError::new("SCAN_ERROR", &e.to_string()))?;
-    
-    if ports.is_empty() {
+    if !state.docker.read().await.is_available() {
         return Ok(vec![]);
     }
 
-    let enricher = state.process_enricher.read().await;
+    let app_state = build_app_state(&state, ScanOptions::default()).await?;
     let docker = state.docker.read().await;
-    
-    let mut nodes = Vec::new();
-    
-    for port_info in ports {
-        for &pid in &port_info.pids {
-            let is_docker = enricher.is_docker_proxy(pid);
-            let container = if is_docker && docker.is_available() {
-                docker.get_container_for_port(port_info.local_port).await
-            } else {
-                None
-            };
+    let can_correlate = docker.can_correlate_local_ports();
 
-            let info = enricher.get_process_info(pid);
-            let (name, exe_path, command_line, user, memory_usage, cpu_usage, start_time) =
-                if let Some(info) = info {
-                    (
-                        info.name,
-                        info.exe_path,
-                        info.command_line,
-                        info.user,
-                        info.memory_usage,
-                        info.cpu_usage,
-                        info.start_time,
-                    )
-                } else {
-                    (
-                        "Unknown".to_string(),
-                        None,
-                        None,
-                        "Unknown".to_string(),
-                        0,
-                        0.0,
-                        None,
+    let mut nodes = Vec::new();
+    for mut node in app_state.processes {
+        if node.container.is_none() && can_correlate {
+            for port in &node.ports {
+                let container = docker
+                    .get_container_for_port(
+                        port.local_port,
+                        Some(port.protocol),
+                        Some(&port.local_address),
                     )
-                };
+                    .await;
+                if let Some(container) = container {
+                    node.container = Some(container);
+                    break;
+                }
+            }
+        }
 
-            let safety = crate::surgery::check_process_safety(pid, &name);
-            
-            nodes.push(ProcessNode {
-                id: format!("{}-{}", pid, port_info.local_port),
-                pid,
-                name,
-                exe_path,
-                command_line,
-                user,
-                memory_usage,
-                cpu_usage,
-                start_time,
-                ports: vec![PortEntry {
-                    protocol: port_info.protocol,
-                    local_address: port_info.local_address.clone(),
-                    local_port: port_info.local_port,
-                    remote_address: port_info.remote_address.clone(),
-                    remote_port: port_info.remote_port,
-                    state: port_info.state,
-                }],
-                is_docker_proxy: is_docker,
-                container,
-                is_protected: !safety.is_safe(),
-            });
+        if node.is_docker_proxy || node.container.is_some() {
+            nodes.push(node);
         }
     }
-    
+
     Ok(nodes)
 }
 
-/// Kill a process by PID
+/// Fetch processes merged by executable path, so worker pools show as a single row
+#[tauri::command]
+pub async fn get_processes_grouped(
+    state: State<'_, AppStateManager>,
+    show_all_connections: bool,
+    resolve_hostnames: Option<bool>,
+    include_traffic: Option<bool>,
+) -> Result<GroupedAppState, AppError> {
+    let options = ScanOptions {
+        show_all_connections,
+        resolve_hostnames: resolve_hostnames.unwrap_or(false),
+        include_traffic: include_traffic.unwrap_or(false),
+        ..Default::default()
+    };
+    let app_state = build_app_state(&state, options).await?;
+
+    Ok(GroupedAppState {
+        groups: group_by_executable(app_state.processes),
+        total_connections: app_state.total_connections,
+        listening_ports: app_state.listening_ports,
+        docker_available: app_state.docker_available,
+        last_updated: app_state.last_updated,
+        warnings: app_state.warnings,
+    })
+}
+
+/// Fetch processes whose name or command line contains `name_filter` (case-insensitive)
+///
+/// An empty or missing filter returns everything. Counts reflect the filtered set.
+#[tauri::command]
+pub async fn get_processes_by_name(
+    state: State<'_, AppStateManager>,
+    show_all_connections: bool,
+    name_filter: Option<String>,
+) -> Result<AppState, AppError> {
+    let options = ScanOptions {
+        show_all_connections,
+        ..Default::default()
+    };
+    let mut app_state = build_app_state(&state, options).await?;
+
+    if let Some(filter) = name_filter.as_deref().map(str::trim).filter(|f| !f.is_empty()) {
+        let filter = filter.to_lowercase();
+        app_state
+            .processes
+            .retain(|node| matches_name_filter(node, &filter));
+
+        app_state.total_connections = app_state.processes.iter().map(|p| p.ports.len()).sum();
+        app_state.listening_ports = app_state
+            .processes
+            .iter()
+            .filter(|p| p.ports.iter().any(|port| matches!(port.state, SocketState::Listening)))
+            .count();
+        app_state.port_counts = tally_sockets(
+            app_state
+                .processes
+                .iter()
+                .flat_map(|p| &p.ports)
+                .map(|port| (port.protocol, port.state, port.is_ipv6)),
+        );
+    }
+
+    Ok(app_state)
+}
+
+/// Case-insensitive substring match on name or command line; `filter` must be lowercase
+fn matches_name_filter(node: &ProcessNode, filter: &str) -> bool {
+    node.name.to_lowercase().contains(filter)
+        || node
+            .command_line
+            .as_deref()
+            .map(|cmd| cmd.to_lowercase().contains(filter))
+            .unwrap_or(false)
+}
+
+/// Fetch one page of processes, sorted server-side so pages are stable
+///
+/// Hostnames are only resolved for the returned page.
+#[tauri::command]
+pub async fn get_processes_paginated(
+    state: State<'_, AppStateManager>,
+    show_all_connections: bool,
+    offset: usize,
+    limit: usize,
+    sort_by: SortKey,
+    resolve_hostnames: Option<bool>,
+    include_traffic: Option<bool>,
+) -> Result<ProcessPage, AppError> {
+    let options = ScanOptions {
+        show_all_connections,
+        resolve_hostnames: false,
+        include_traffic: include_traffic.unwrap_or(false),
+        ..Default::default()
+    };
+    let app_state = build_app_state(&state, options).await?;
+
+    let mut processes = app_state.processes;
+    let total = processes.len();
+    sort_processes(&mut processes, sort_by);
+
+    let mut page: Vec<ProcessNode> = processes.into_iter().skip(offset).take(limit).collect();
+
+    if resolve_hostnames.unwrap_or(false) {
+        let resolver = state.dns_resolver.read().await;
+        resolve_remote_hostnames(&resolver, &mut page).await;
+    }
+
+    Ok(ProcessPage {
+        processes: page,
+        total,
+        offset,
+        limit,
+        total_connections: app_state.total_connections,
+        listening_ports: app_state.listening_ports,
+        docker_available: app_state.docker_available,
+        last_updated: app_state.last_updated,
+        warnings: app_state.warnings,
+    })
+}
+
+/// Sort process nodes by the requested key, breaking ties by PID
+fn sort_processes(processes: &mut [ProcessNode], sort_by: SortKey) {
+    match sort_by {
+        SortKey::Pid => processes.sort_by_key(|p| p.pid),
+        SortKey::Name => processes.sort_by_cached_key(|p| (p.name.to_lowercase(), p.pid)),
+        SortKey::Memory => processes.sort_by(|a, b| {
+            b.memory_usage.cmp(&a.memory_usage).then(a.pid.cmp(&b.pid))
+        }),
+        SortKey::Cpu => processes.sort_by(|a, b| {
+            b.cpu_usage.total_cmp(&a.cpu_usage).then(a.pid.cmp(&b.pid))
+        }),
+        SortKey::Port => processes.sort_by_key(|p| {
+            // Nodes without ports sort last
+            let lowest = p.ports.iter().map(|port| port.local_port).min();
+            (lowest.is_none(), lowest, p.pid)
+        }),
+    }
+}
+
+/// Processes returned by `get_all_processes` when the caller gives no limit
+const DEFAULT_PROCESS_LIST_LIMIT: usize = 500;
+
+/// List running processes whether or not they own sockets, like a task manager
+///
+/// With `without_ports`, processes owning any TCP or UDP socket are left out, leaving
+/// the ones the port-centric views never show. `SortKey::Port` orders by PID since
+/// plain process entries carry no ports. At most `limit` entries (default 500) are
+/// returned.
+#[tauri::command]
+pub async fn get_all_processes(
+    state: State<'_, AppStateManager>,
+    sort_by: Option<SortKey>,
+    limit: Option<usize>,
+    without_ports: Option<bool>,
+) -> Result<Vec<ProcessInfo>, AppError> {
+    let socket_owners: HashSet<u32> = if without_ports.unwrap_or(false) {
+        tokio::task::spawn_blocking(scan_ports)
+            .await
+            .map_err(anyhow::Error::from)
+            .and_then(|result| result)
+            .map_err(|e| AppError::new(ErrorCode::ScanError, &e.to_string()))?
+            .ports
+            .into_iter()
+            .flat_map(|p| p.pids)
+            .collect()
+    } else {
+        HashSet::new()
+    };
+
+    let mut processes: Vec<ProcessInfo> = {
+        let mut enricher = state.process_enricher.write().await;
+        enricher.refresh_processes_only();
+        enricher
+            .get_all_processes()
+            .into_iter()
+            .filter(|info| !socket_owners.contains(&info.pid))
+            .collect()
+    };
+    sort_process_infos(&mut processes, sort_by.unwrap_or(SortKey::Pid));
+    processes.truncate(limit.unwrap_or(DEFAULT_PROCESS_LIST_LIMIT));
+    Ok(processes)
+}
+
+/// Sort plain process entries like `sort_processes`, with `Port` falling back to PID
+fn sort_process_infos(processes: &mut [ProcessInfo], sort_by: SortKey) {
+    match sort_by {
+        SortKey::Pid | SortKey::Port => processes.sort_by_key(|p| p.pid),
+        SortKey::Name => processes.sort_by_cached_key(|p| (p.name.to_lowercase(), p.pid)),
+        SortKey::Memory => processes.sort_by(|a, b| {
+            b.memory_usage.cmp(&a.memory_usage).then(a.pid.cmp(&b.pid))
+        }),
+        SortKey::Cpu => processes.sort_by(|a, b| {
+            b.cpu_usage.total_cmp(&a.cpu_usage).then(a.pid.cmp(&b.pid))
+        }),
+    }
+}
+
+/// Id of a node holding all of a process's ports
+///
+/// PIDs are unique within a scan, so the PID alone is used for portless nodes; the
+/// first port is appended otherwise to keep ids readable.
+fn process_node_id(pid: u32, ports: &[PortEntry]) -> String {
+    match ports.first() {
+        Some(port) => format!("{}-{}", pid, port.local_port),
+        None => pid.to_string(),
+    }
+}
+
+/// Whether a process is an SSH tunnel, and where it forwards `listening_ports` to
+///
+/// An sshd not started by another sshd is the listening daemon, never a tunnel, as
+/// its ports may come from sshd_config. A per-connection sshd listening on its own
+/// SSH port isn't one either; any other port it holds is a client's `-R` forward,
+/// whose destination is only known to the client.
+fn tunnel_info(
+    name: &str,
+    args: &[String],
+    listening_ports: &[u16],
+    sshd_daemon: bool,
+) -> (bool, Option<String>) {
+    if !is_ssh_process_name(name) || listening_ports.is_empty() {
+        return (false, None);
+    }
+    if is_sshd_process_name(name) {
+        if sshd_daemon {
+            return (false, None);
+        }
+        let daemon_ports = sshd_daemon_ports(args);
+        let forwards = listening_ports.iter().any(|port| !daemon_ports.contains(port));
+        return (forwards, None);
+    }
+    (true, describe_tunnel(args, listening_ports))
+}
+
+/// Cancel `caller`'s in-flight scan, if any, and return the token for its new one
+async fn supersede_scan(state: &AppStateManager, caller: ScanCaller) -> CancellationToken {
+    let token = CancellationToken::new();
+    let mut tokens = state.scan_tokens.write().await;
+    if let Some(previous) = tokens.insert(caller, token.clone()) {
+        previous.cancel();
+    }
+    token
+}
+
+/// Run blocking work off the async runtime, giving up as soon as `token` is cancelled
+///
+/// The blocking thread itself can't be interrupted and finishes in the background;
+/// its result is discarded.
+async fn run_blocking_cancellable<T, F>(token: &CancellationToken, work: F) -> Result<T, AppError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let task = tokio::task::spawn_blocking(work);
+
+    tokio::select! {
+        _ = token.cancelled() => Err(AppError::new(
+            ErrorCode::ScanCancelled,
+            "Scan was superseded by a newer scan",
+        )),
+        result = task => result.map_err(|e| AppError::new(ErrorCode::ScanError, &e.to_string())),
+    }
+}
+
+/// Stop a scan between phases once `token` is cancelled
+fn check_scan_cancelled(token: &CancellationToken) -> Result<(), AppError> {
+    if token.is_cancelled() {
+        return Err(AppError::new(
+            ErrorCode::ScanCancelled,
+            "Scan was superseded by a newer scan",
+        ));
+    }
+    Ok(())
+}
+
+/// Scan ports and build one process node per PID; nothing cancels this scan
+async fn build_app_state(
+    state: &AppStateManager,
+    options: ScanOptions,
+) -> Result<AppState, AppError> {
+    scan_app_state(state, options, CancellationToken::new()).await
+}
+
+/// `build_app_state` for `caller`, superseding its previous scan if still running
+async fn build_app_state_for(
+    state: &AppStateManager,
+    options: ScanOptions,
+    caller: ScanCaller,
+) -> Result<AppState, AppError> {
+    let token = supersede_scan(state, caller).await;
+    scan_app_state(state, options, token).await
+}
+
+/// Scan ports and build one process node per PID, giving up once `token` is cancelled
+async fn scan_app_state(
+    state: &AppStateManager,
+    options: ScanOptions,
+    token: CancellationToken,
+) -> Result<AppState, AppError> {
+    log::debug!("Fetching processes, show_all: {}", options.show_all_connections);
+
+    // Scan ports
+    // Always scan every socket so listeners can report their established connections
+    // Partial results are kept; failed protocol/address-family queries become warnings
+    // The blocking scans also watch the token, so a cancelled scan stops its work too
+    let cancel = token.clone();
+    let scanned = run_blocking_cancellable(&token, move || scan_ports_cancellable(&cancel)).await?;
+    check_scan_cancelled(&token)?;
+    let PortScan {
+        ports: mut all_ports,
+        mut warnings,
+        limited_privileges,
+        state_histogram,
+    } = scanned.map_err(|e| AppError::new(ErrorCode::ScanError, &e.to_string()))?;
+    if options.include_netns {
+        let cancel = token.clone();
+        let scanned =
+            run_blocking_cancellable(&token, move || scan_namespace_ports(&cancel)).await?;
+        check_scan_cancelled(&token)?;
+        match scanned {
+            Ok(namespaced) => all_ports.extend(namespaced),
+            Err(e) => warnings.push(format!("Failed to scan network namespaces: {}", e)),
+        }
+    }
+    let connection_counts = count_established_connections(&all_ports);
+    let shared_listeners = shared_listener_ports(&all_ports);
+
+    let mut ports = if options.show_all_connections {
+        all_ports
+    } else {
+        all_ports
+            .into_iter()
+            .filter(|p| matches!(p.state, SocketState::Listening))
+            .collect()
+    };
+
+    if options.exclude_loopback {
+        ports.retain(|p| !is_loopback_connection(p));
+    }
+
+    if options.include_traffic {
+        enrich_socket_traffic(&mut ports);
+    }
+
+    // Collect unique PIDs
+    let all_pids: Vec<u32> = ports.iter().flat_map(|p| p.pids.clone()).collect();
+    let unique_pids: Vec<u32> = {
+        let mut pids = all_pids.clone();
+        pids.sort();
+        pids.dedup();
+        pids
+    };
+
+    // Refresh once per scan, then read everything from the loaded snapshot
+    let process_map = refreshed_process_map(state, &unique_pids).await;
+    let orphaned = state
+        .process_enricher
+        .read()
+        .await
+        .orphaned_pids(process_map.values());
+    let suspended = live_suspended_pids(state).await;
+    check_scan_cancelled(&token)?;
+
+    // Refresh Docker port map
+    let docker = state.docker.read().await;
+    if docker.can_correlate_local_ports() {
+        let _ = docker.refresh().await;
+    }
+    check_scan_cancelled(&token)?;
+
+    // Build process nodes grouped by PID
+    let mut pid_to_ports = group_ports_by_pid(&ports);
+    for (pid, entries) in pid_to_ports.iter_mut() {
+        for entry in entries.iter_mut().filter(|e| e.state == SocketState::Listening) {
+            let key = namespaced_port(entry.protocol, entry.local_port, &entry.netns);
+            entry.shared_port = shared_listeners.contains(&key);
+            entry.connection_count = connection_counts.get(&(*pid, key)).copied().unwrap_or(0);
+        }
+    }
+
+    // Create ProcessNodes
+    let mut processes: Vec<ProcessNode> = Vec::new();
+    let context =
+        NodeContext::load(state, &docker, &unique_pids, &ports, orphaned, suspended).await;
+
+    for (pid, ports) in pid_to_ports {
+        let info = process_map.get(&pid).cloned();
+        let id = process_node_id(pid, &ports);
+        processes.push(context.node(&docker, id, pid, info, ports).await);
+    }
+
+    // Sort by PID for consistency
+    processes.sort_by_key(|p| p.pid);
+
+    if let Some(database) = state.geoip.read().await.as_ref() {
+        annotate_remote_asn(database, &mut processes);
+    }
+
+    if options.resolve_hostnames {
+        let resolver = state.dns_resolver.read().await;
+        resolve_remote_hostnames(&resolver, &mut processes).await;
+    }
+
+    let unix_sockets = if options.include_unix_sockets {
+        let scanned = tokio::task::spawn_blocking(scan_unix_sockets)
+            .await
+            .map_err(anyhow::Error::from)
+            .and_then(|result| result);
+        scanned.unwrap_or_else(|e| {
+            warnings.push(format!("Failed to enumerate Unix sockets: {}", e));
+            Vec::new()
+        })
+    } else {
+        Vec::new()
+    };
+
+    let listening_count = processes
+        .iter()
+        .filter(|p| p.ports.iter().any(|port| matches!(port.state, SocketState::Listening)))
+        .count();
+
+    Ok(AppState {
+        total_processes: processes.len(),
+        processes,
+        total_connections: ports.len(),
+        listening_ports: listening_count,
+        port_counts: count_ports(&ports),
+        docker_available: docker.is_available(),
+        last_updated: Utc::now(),
+        warnings,
+        unix_sockets,
+        limited_privileges,
+        truncated: false,
+        state_histogram,
+    })
+}
+
+/// Tally sockets by protocol, state and address family
+fn count_ports(ports: &[PortInfo]) -> PortCounts {
+    tally_sockets(ports.iter().map(|p| (p.protocol, p.state, p.is_ipv6)))
+}
+
+/// Tally (protocol, state, is_ipv6) triples, shared by raw sockets and port entries
+fn tally_sockets(sockets: impl IntoIterator<Item = (Protocol, SocketState, bool)>) -> PortCounts {
+    let mut counts = PortCounts::default();
+
+    for (protocol, state, is_ipv6) in sockets {
+        match protocol {
+            Protocol::TCP => counts.tcp_count += 1,
+            Protocol::UDP => counts.udp_count += 1,
+        }
+        if state == SocketState::Established {
+            counts.established_count += 1;
+        }
+        if is_ipv6 {
+            counts.ipv6_count += 1;
+        }
+    }
+
+    counts
+}
+
+/// Classify the listeners among `ports`; None if there are none
+///
+/// Loopback binds are left out of `SpecificInterfaces` since they aren't reachable
+/// from other machines.
+fn port_exposure(ports: &[PortInfo]) -> Option<Exposure> {
+    let listeners: Vec<&PortInfo> =
+        ports.iter().filter(|p| p.state == SocketState::Listening).collect();
+
+    if listeners.is_empty() {
+        return None;
+    }
+
+    let classes: Vec<AddressClass> =
+        listeners.iter().map(|p| normalize_listen_address(&p.local_address)).collect();
+    if classes.contains(&AddressClass::Wildcard) {
+        return Some(Exposure::External);
+    }
+
+    let mut addresses: Vec<String> = classes
+        .into_iter()
+        .filter_map(|class| match class {
+            AddressClass::Specific(address) | AddressClass::LinkLocal(address) => Some(address),
+            AddressClass::Wildcard | AddressClass::Loopback => None,
+        })
+        .collect();
+    addresses.sort();
+    addresses.dedup();
+
+    if addresses.is_empty() {
+        Some(Exposure::LoopbackOnly)
+    } else {
+        Some(Exposure::SpecificInterfaces(addresses))
+    }
+}
+
+/// A port within one network namespace: protocol, port and namespace (None for the host's)
+///
+/// Containers in different namespaces can each listen on the same port without
+/// sharing anything, so lookups across a multi-namespace scan are keyed by this.
+type NamespacedPort = (Protocol, u16, Option<String>);
+
+/// The namespaced local port of a socket
+fn namespaced_port(protocol: Protocol, local_port: u16, netns: &Option<String>) -> NamespacedPort {
+    (protocol, local_port, netns.clone())
+}
+
+/// Count established sockets per PID and namespaced local port, a rough load indicator
+/// for listeners
+fn count_established_connections(ports: &[PortInfo]) -> HashMap<(u32, NamespacedPort), usize> {
+    let mut counts: HashMap<(u32, NamespacedPort), usize> = HashMap::new();
+
+    for port in ports.iter().filter(|p| p.state == SocketState::Established) {
+        let key = namespaced_port(port.protocol, port.local_port, &port.netns);
+        for &pid in &port.pids {
+            *counts.entry((pid, key.clone())).or_default() += 1;
+        }
+    }
+
+    counts
+}
+
+/// Namespaced ports with at least one listener
+fn listener_ports(ports: &[PortInfo]) -> HashSet<NamespacedPort> {
+    ports
+        .iter()
+        .filter(|p| p.state == SocketState::Listening)
+        .map(|p| namespaced_port(p.protocol, p.local_port, &p.netns))
+        .collect()
+}
+
+/// Namespaced ports with listeners in more than one PID, e.g. via SO_REUSEPORT
+fn shared_listener_ports(ports: &[PortInfo]) -> HashSet<NamespacedPort> {
+    let mut owners: HashMap<NamespacedPort, HashSet<u32>> = HashMap::new();
+
+    for port in ports.iter().filter(|p| p.state == SocketState::Listening) {
+        owners
+            .entry(namespaced_port(port.protocol, port.local_port, &port.netns))
+            .or_default()
+            .extend(port.pids.iter().copied());
+    }
+
+    owners
+        .into_iter()
+        .filter(|(_, pids)| pids.len() > 1)
+        .map(|(key, _)| key)
+        .collect()
+}
+
+/// Listeners a PID holds only because it inherited them, as `(pid, port_key, netns)`
+///
+/// A socket opened before a fork is reported for the parent and every child. Of the
+/// PIDs sharing one listener, those with another sharer among their ancestors are
+/// marked; the topmost holder is the owner whose exit frees the port. The ancestry
+/// walk goes through `parent_of`, which should cover every process rather than just
+/// the holders, since a holder's parent may be a shell that holds nothing.
+fn inherited_sockets(
+    ports: &[PortInfo],
+    parent_of: impl Fn(u32) -> Option<u32>,
+) -> HashSet<(u32, String, Option<String>)> {
+    // Deeper chains than this are most likely a cycle from a reused PID
+    const MAX_ANCESTRY_DEPTH: usize = 64;
+
+    let mut inherited = HashSet::new();
+    for port_info in ports
+        .iter()
+        .filter(|p| p.state == SocketState::Listening && p.pids.len() > 1)
+    {
+        let holders: HashSet<u32> = port_info.pids.iter().copied().collect();
+        for &pid in &holders {
+            let mut ancestor = parent_of(pid);
+            for _ in 0..MAX_ANCESTRY_DEPTH {
+                match ancestor {
+                    Some(parent) if holders.contains(&parent) => {
+                        inherited.insert((pid, port_key(port_info), port_info.netns.clone()));
+                        break;
+                    }
+                    Some(parent) if parent != pid => ancestor = parent_of(parent),
+                    _ => break,
+                }
+            }
+        }
+    }
+    inherited
+}
+
+/// Group scanned sockets into port entries per PID, dropping duplicate listeners
+fn group_ports_by_pid(ports: &[PortInfo]) -> HashMap<u32, Vec<PortEntry>> {
+    // Use a set to track unique ports per process (protocol + port + address)
+    let mut pid_to_ports: HashMap<u32, Vec<PortEntry>> = HashMap::new();
+    let mut pid_seen_ports: HashMap<u32, HashSet<String>> = HashMap::new();
+    let listeners = listener_ports(ports);
+    
+    for port_info in ports {
+        // The port key (protocol + port + normalized address) doubles as the dedup key
+        let port_entry = to_port_entry(port_info, &listeners);
+        
+        for &pid in &port_info.pids {
+            let seen = pid_seen_ports.entry(pid).or_insert_with(HashSet::new);
+            
+            // Only add if we haven't seen this port combination for this PID
+            if seen.insert(port_entry.port_key.clone()) {
+                pid_to_ports
+                    .entry(pid)
+                    .or_insert_with(Vec::new)
+                    .push(port_entry.clone());
+            }
+        }
+    }
+
+    pid_to_ports
+}
+
+/// Compare two scans the frontend already holds
+#[tauri::command]
+pub fn diff_scans(previous: AppState, current: AppState) -> ScanDiff {
+    scan_diff(&previous, &current)
+}
+
+/// Save the current listeners as a named baseline, replacing any snapshot of that name
+///
+/// Returns how many listeners were recorded.
+#[tauri::command]
+pub async fn save_snapshot(
+    state: State<'_, AppStateManager>,
+    name: String,
+) -> Result<usize, AppError> {
+    let name = validated_snapshot_name(&name)?;
+    let app_state = build_app_state(&state, ScanOptions::default()).await?;
+    let snapshot = Snapshot::capture(name, &app_state);
+
+    state
+        .snapshots
+        .save(&snapshot)
+        .map_err(|e| snapshot_error("Failed to save snapshot", e))?;
+    log::info!("Saved snapshot '{}' with {} listeners", name, snapshot.listeners.len());
+    Ok(snapshot.listeners.len())
+}
+
+/// Report listeners added or removed since a saved snapshot
+///
+/// Unlike `diff_scans`, listeners are matched by process name rather than PID, so
+/// services restarted since the baseline don't show up.
+#[tauri::command]
+pub async fn compare_to_snapshot(
+    state: State<'_, AppStateManager>,
+    name: String,
+) -> Result<SnapshotComparison, AppError> {
+    let name = validated_snapshot_name(&name)?;
+    let snapshot = state
+        .snapshots
+        .load(name)
+        .map_err(|e| snapshot_error("Failed to read snapshot", e))?
+        .ok_or_else(|| {
+            AppError::with_details(
+                ErrorCode::NotFound,
+                "Snapshot not found",
+                &format!("No snapshot named '{}'; list_snapshots shows the saved ones", name),
+            )
+        })?;
+
+    let app_state = build_app_state(&state, ScanOptions::default()).await?;
+    Ok(snapshot.compare(&listeners_of(&app_state)))
+}
+
+/// Names of the saved snapshots, sorted
+#[tauri::command]
+pub async fn list_snapshots(state: State<'_, AppStateManager>) -> Result<Vec<String>, AppError> {
+    state
+        .snapshots
+        .list()
+        .map_err(|e| snapshot_error("Failed to list snapshots", e))
+}
+
+fn snapshot_error(message: &str, e: anyhow::Error) -> AppError {
+    AppError::with_details(ErrorCode::ConfigError, message, &format!("{:#}", e))
+}
+
+fn validated_snapshot_name(name: &str) -> Result<&str, AppError> {
+    let name = name.trim();
+    if !is_valid_snapshot_name(name) {
+        return Err(AppError::with_details(
+            ErrorCode::InvalidInput,
+            "Invalid snapshot name",
+            "Use up to 64 letters, digits, spaces, '-', '_' or '.', not starting with '.'",
+        ));
+    }
+    Ok(name)
+}
+
+/// Compare two scans
+///
+/// Processes are matched by PID; a PID now running a differently named program is
+/// treated as reuse and reported as removed plus added. A surviving process whose
+/// sockets changed is reported once in `port_changes`.
+fn scan_diff(previous: &AppState, current: &AppState) -> ScanDiff {
+    let before: HashMap<u32, &ProcessNode> =
+        previous.processes.iter().map(|p| (p.pid, p)).collect();
+    let after: HashMap<u32, &ProcessNode> =
+        current.processes.iter().map(|p| (p.pid, p)).collect();
+
+    let mut diff = ScanDiff::default();
+
+    for node in &current.processes {
+        match before.get(&node.pid) {
+            Some(old) if old.name == node.name => {
+                let added = ports_missing_from(&node.ports, &old.ports);
+                let removed = ports_missing_from(&old.ports, &node.ports);
+                if !added.is_empty() || !removed.is_empty() {
+                    diff.port_changes.push(PortChange {
+                        pid: node.pid,
+                        name: node.name.clone(),
+                        added,
+                        removed,
+                    });
+                }
+            }
+            Some(old) => {
+                diff.removed.push((*old).clone());
+                diff.added.push(node.clone());
+            }
+            None => diff.added.push(node.clone()),
+        }
+    }
+
+    for node in &previous.processes {
+        if !after.contains_key(&node.pid) {
+            diff.removed.push(node.clone());
+        }
+    }
+
+    diff
+}
+
+/// Entries of `ports` with no counterpart in `other`
+fn ports_missing_from(ports: &[PortEntry], other: &[PortEntry]) -> Vec<PortEntry> {
+    let key = |p: &PortEntry| {
+        (
+            p.protocol,
+            p.local_address.clone(),
+            p.local_port,
+            p.remote_address.clone(),
+            p.remote_port,
+        )
+    };
+    let other: HashSet<_> = other.iter().map(key).collect();
+
+    ports.iter().filter(|p| !other.contains(&key(p))).cloned().collect()
+}
+
+/// Merge process nodes that share an executable path into group nodes
+///
+/// The lowest PID in each group represents it. Processes without a known
+/// executable path are never merged with each other.
+fn group_by_executable(processes: Vec<ProcessNode>) -> Vec<ProcessGroupNode> {
+    let mut groups: Vec<ProcessGroupNode> = Vec::new();
+    let mut exe_to_group: HashMap<String, usize> = HashMap::new();
+
+    for node in processes {
+        let existing = node
+            .exe_path
+            .as_ref()
+            .and_then(|exe| exe_to_group.get(exe).copied());
+
+        match existing {
+            Some(index) => {
+                let group = &mut groups[index];
+                group.worker_pids.push(node.pid);
+                group.node.memory_usage += node.memory_usage;
+                group.node.cpu_usage += node.cpu_usage;
+                group.node.disk_read_bytes += node.disk_read_bytes;
+                group.node.disk_written_bytes += node.disk_written_bytes;
+                group.node.is_docker_proxy |= node.is_docker_proxy;
+                group.node.is_tunnel |= node.is_tunnel;
+                if group.node.tunnel_target.is_none() {
+                    group.node.tunnel_target = node.tunnel_target;
+                }
+                group.node.is_protected |= node.is_protected;
+                if group.node.container.is_none() {
+                    group.node.container = node.container;
+                }
+                // Workers usually share their listener, so skip ports already in the group
+                for port in node.ports {
+                    let duplicate = group.node.ports.iter().any(|existing| {
+                        existing.protocol == port.protocol
+                            && existing.local_address == port.local_address
+                            && existing.local_port == port.local_port
+                            && existing.remote_address == port.remote_address
+                            && existing.remote_port == port.remote_port
+                    });
+                    if !duplicate {
+                        group.node.ports.push(port);
+                    }
+                }
+            }
+            None => {
+                if let Some(exe) = &node.exe_path {
+                    exe_to_group.insert(exe.clone(), groups.len());
+                }
+                groups.push(ProcessGroupNode {
+                    worker_pids: vec![node.pid],
+                    node,
+                });
+            }
+        }
+    }
+
+    for group in &mut groups {
+        group.worker_pids.sort();
+        group.node.ports.sort_by_key(|p| p.local_port);
+
+        // Represent the group by its lowest PID (typically the master process)
+        if let Some(&leader) = group.worker_pids.first() {
+            if leader != group.node.pid {
+                group.node.pid = leader;
+                group.node.id = process_node_id(leader, &group.node.ports);
+            }
+        }
+    }
+
+    groups.sort_by_key(|g| g.node.pid);
+    groups
+}
+
+/// Find processes using a specific port
+#[tauri::command]
+pub async fn find_port(
+    state: State<'_, AppStateManager>,
+    port: u16,
+) -> Result<Vec<ProcessNode>, AppError> {
+    let ports = find_port_users(port)
+        .map_err(|e| AppError::new(ErrorCode::ScanError, &e.to_string()))?;
+    
+    Ok(build_port_nodes(&state, ports).await)
+}
+
+/// Find listeners by a loose port query: `80`, a range like `80-90`, or a prefix like `8*`
+#[tauri::command]
+pub async fn search_ports(
+    state: State<'_, AppStateManager>,
+    query: String,
+) -> Result<Vec<ProcessNode>, AppError> {
+    let query = PortQuery::parse(&query).map_err(|e| {
+        AppError::with_details(ErrorCode::InvalidInput, "Invalid port query", &e.to_string())
+    })?;
+
+    let ports = search_listening_ports(&query)
+        .map_err(|e| AppError::new(ErrorCode::ScanError, &e.to_string()))?;
+
+    Ok(build_port_nodes(&state, ports).await)
+}
+
+/// Summarize which addresses a port is listening on across every PID and protocol
+///
+/// Answers "is my service reachable from other machines?". Errors with `NotFound`
+/// when nothing listens on the port.
+#[tauri::command]
+pub async fn get_port_exposure(port: u16) -> Result<Exposure, AppError> {
+    let ports = find_port_users(port)
+        .map_err(|e| AppError::new(ErrorCode::ScanError, &e.to_string()))?;
+
+    port_exposure(&ports).ok_or_else(|| {
+        AppError::new(ErrorCode::NotFound, &format!("Nothing is listening on port {}", port))
+    })
+}
+
+/// Turn a process's local listeners into an nginx or Caddy reverse-proxy snippet
+///
+/// Only listening TCP ports bound to loopback or a wildcard address are included,
+/// since those are the ones a proxy on this host can reach through `127.0.0.1`.
+#[tauri::command]
+pub async fn generate_proxy_config(
+    state: State<'_, AppStateManager>,
+    pid: u32,
+    format: ProxyFormat,
+) -> Result<String, AppError> {
+    let info = refreshed_process_info(&state, pid)
+        .await
+        .ok_or_else(|| AppError::process_not_found(pid))?;
+
+    let ports = scan_listening_ports()
+        .map_err(|e| AppError::new(ErrorCode::ScanError, &e.to_string()))?;
+    let endpoints = proxy_endpoints(&ports, pid);
+    if endpoints.is_empty() {
+        return Err(AppError::new(
+            ErrorCode::NotFound,
+            &format!("Process {} has no local TCP listeners to proxy to", pid),
+        ));
+    }
+
+    Ok(render_proxy_config(&info.name, &endpoints, format))
+}
+
+/// Find processes whose local port falls within `[start, end]`
+#[tauri::command]
+pub async fn scan_port_range(
+    state: State<'_, AppStateManager>,
+    start: u16,
+    end: u16,
+) -> Result<Vec<ProcessNode>, AppError> {
+    if start > end {
+        return Err(AppError::with_details(
+            ErrorCode::InvalidRange,
+            "Invalid port range",
+            &format!("Start port {} is greater than end port {}", start, end),
+        ));
+    }
+
+    let ports = crate::discovery::scan_port_range(start, end)
+        .map_err(|e| AppError::new(ErrorCode::ScanError, &e.to_string()))?;
+
+    Ok(build_port_nodes(&state, ports).await)
+}
+
+/// Refresh the process snapshot once and look up every requested PID from it
+///
+/// Refreshes the whole process table, which orphan and suspended-PID checks rely on.
+async fn refreshed_process_map(
+    state: &AppStateManager,
+    pids: &[u32],
+) -> HashMap<u32, ProcessInfo> {
+    let started = std::time::Instant::now();
+    let mut enricher = state.process_enricher.write().await;
+    enricher.refresh_processes_only();
+    log::debug!("Process table refresh took {:?}", started.elapsed());
+    let mut infos = enricher.downgrade().get_processes_info(pids);
+    fill_from_ps(&mut infos, pids).await;
+    infos
+}
+
+/// Add the PIDs among `pids` that the process table lacks, using one `ps` call
+///
+/// Only macOS hides other users' processes from sysinfo; elsewhere nothing is run.
+async fn fill_from_ps(infos: &mut HashMap<u32, ProcessInfo>, pids: &[u32]) {
+    let missing: Vec<u32> = pids.iter().copied().filter(|pid| !infos.contains_key(pid)).collect();
+    if !cfg!(target_os = "macos") || missing.is_empty() {
+        return;
+    }
+    match tokio::task::spawn_blocking(move || ps_processes_info(&missing)).await {
+        Ok(found) => infos.extend(found),
+        Err(e) => log::debug!("ps lookup failed: {}", e),
+    }
+}
+
+/// Refresh and look up a single process without reloading the whole process table
+async fn refreshed_process_info(state: &AppStateManager, pid: u32) -> Option<ProcessInfo> {
+    let mut enricher = state.process_enricher.write().await;
+    enricher.refresh_specific(&[pid]);
+    enricher.downgrade().get_process_info(pid)
+}
+
+/// Forget suspended PIDs that have exited and return the ones still paused
+///
+/// Uses the enricher snapshot, so call it after `refreshed_process_map`.
+async fn live_suspended_pids(state: &AppStateManager) -> HashSet<u32> {
+    let enricher = state.process_enricher.read().await;
+    let mut suspended = state.suspended.write().await;
+    suspended.retain(|&pid| enricher.process_exists(pid));
+    suspended.clone()
+}
+
+/// The container each of `pids` runs in, keyed by PID, from their cgroups (Linux only)
+///
+/// Every containerized PID is present; its value is the container's details when the
+/// local engine knows the id, e.g. None for Kubernetes pods run by another runtime.
+async fn containerized_pids(
+    docker: &DockerResolver,
+    pids: &[u32],
+) -> HashMap<u32, Option<ContainerInfo>> {
+    let pids = pids.to_vec();
+    let container_ids: HashMap<u32, String> = tokio::task::spawn_blocking(move || {
+        pids.into_iter()
+            .filter_map(|pid| Some((pid, get_container_id_for_pid(pid).ok()??)))
+            .collect()
+    })
+    .await
+    .unwrap_or_default();
+    if container_ids.is_empty() {
+        return HashMap::new();
+    }
+
+    let containers = if docker.can_correlate_local_ports() {
+        docker
+            .containers_by_id(&container_ids.values().cloned().collect())
+            .await
+    } else {
+        HashMap::new()
+    };
+    container_ids
+        .into_iter()
+        .map(|(pid, id)| (pid, containers.get(&id).cloned()))
+        .collect()
+}
+
+/// Scan-wide lookups that every `ProcessNode` of one scan is built from
+struct NodeContext {
+    scanned_at: DateTime<Utc>,
+    ignored: HashSet<String>,
+    notes: BTreeMap<String, String>,
+    orphaned: HashSet<u32>,
+    suspended: HashSet<u32>,
+    containerized: HashMap<u32, Option<ContainerInfo>>,
+    inherited: HashSet<(u32, String, Option<String>)>,
+    /// The background sampler's CPU readings, preferred over the enricher's, which
+    /// only cover the time since the previous refresh
+    sampled_cpu: HashMap<u32, f32>,
+    /// sshd processes whose parent isn't an sshd, i.e. the listening daemons
+    sshd_daemons: HashSet<u32>,
+}
+
+impl NodeContext {
+    /// Gather the lookups for nodes of `pids` holding `ports`, given which of them are
+    /// orphaned or paused
+    ///
+    /// Reads parents from the process enricher's table, so refresh it first.
+    async fn load(
+        state: &AppStateManager,
+        docker: &DockerResolver,
+        pids: &[u32],
+        ports: &[PortInfo],
+        orphaned: HashSet<u32>,
+        suspended: HashSet<u32>,
+    ) -> Self {
+        let (ignored, notes) = {
+            let config = state.config.read().await;
+            (config.ignored_set(), config.settings.process_notes.clone())
+        };
+        let (inherited, sshd_daemons) = {
+            let enricher = state.process_enricher.read().await;
+            let is_sshd = |pid: u32| {
+                enricher
+                    .process_name(pid)
+                    .is_some_and(|name| is_sshd_process_name(&name))
+            };
+            let sshd_daemons = pids
+                .iter()
+                .copied()
+                .filter(|&pid| is_sshd(pid) && !enricher.parent_pid(pid).is_some_and(is_sshd))
+                .collect();
+            (inherited_sockets(ports, |pid| enricher.parent_pid(pid)), sshd_daemons)
+        };
+        Self {
+            scanned_at: Utc::now(),
+            ignored,
+            notes,
+            orphaned,
+            suspended,
+            containerized: containerized_pids(docker, pids).await,
+            inherited,
+            sampled_cpu: state.cpu_sampler.snapshot().await,
+            sshd_daemons,
+        }
+    }
+
+    /// Build the node for `pid` holding `ports`; `info` is None when the process is gone
+    ///
+    /// Docker proxies are matched to a container by their first port, other processes
+    /// by their cgroup.
+    async fn node(
+        &self,
+        docker: &DockerResolver,
+        id: String,
+        pid: u32,
+        info: Option<ProcessInfo>,
+        mut ports: Vec<PortEntry>,
+    ) -> ProcessNode {
+        for entry in ports.iter_mut() {
+            entry.inherited_socket =
+                self.inherited.contains(&(pid, entry.port_key.clone(), entry.netns.clone()));
+        }
+
+        let info = info.unwrap_or_else(|| ProcessInfo {
+            pid,
+            name: "Unknown".to_string(),
+            exe_path: None,
+            command_line: None,
+            args: Vec::new(),
+            user: "Unknown".to_string(),
+            memory_usage: 0,
+            cpu_usage: 0.0,
+            disk_read_bytes: 0,
+            disk_written_bytes: 0,
+            start_time: None,
+            parent_pid: None,
+        });
+
+        let is_docker = is_docker_process_name(&info.name);
+        let container = match ports.first() {
+            Some(port) if is_docker && docker.can_correlate_local_ports() => {
+                docker
+                    .get_container_for_port(
+                        port.local_port,
+                        Some(port.protocol),
+                        Some(&port.local_address),
+                    )
+                    .await
+            }
+            _ => None,
+        }
+        .or_else(|| self.containerized.get(&pid).cloned().flatten());
+
+        let listening_ports: Vec<u16> = ports
+            .iter()
+            .filter(|port| port.state == SocketState::Listening)
+            .map(|port| port.local_port)
+            .collect();
+        let (is_tunnel, tunnel_target) = tunnel_info(
+            &info.name,
+            &info.args,
+            &listening_ports,
+            self.sshd_daemons.contains(&pid),
+        );
+        let safety = check_process_safety(pid, &info.name);
+        let uptime = uptime_secs(info.start_time, self.scanned_at);
+
+        ProcessNode {
+            id,
+            pid,
+            is_protected: !safety.is_safe(),
+            ignored: self.ignored.contains(&info.name.to_lowercase()),
+            note: info.exe_path.as_ref().and_then(|path| self.notes.get(path)).cloned(),
+            name: info.name,
+            exe_path: info.exe_path,
+            command_line: info.command_line,
+            args: info.args,
+            user: info.user,
+            memory_usage: info.memory_usage,
+            cpu_usage: self.sampled_cpu.get(&pid).copied().unwrap_or(info.cpu_usage),
+            disk_read_bytes: info.disk_read_bytes,
+            disk_written_bytes: info.disk_written_bytes,
+            start_time: info.start_time,
+            uptime_secs: uptime,
+            age: uptime.map(format_age),
+            ports,
+            is_docker_proxy: is_docker,
+            containerized: self.containerized.contains_key(&pid),
+            is_tunnel,
+            tunnel_target,
+            container,
+            is_orphaned: self.orphaned.contains(&pid),
+            is_suspended: self.suspended.contains(&pid),
+        }
+    }
+}
+
+/// Build one process node per (PID, port) pair, enriched with process and container info
+async fn build_port_nodes(state: &AppStateManager, ports: Vec<PortInfo>) -> Vec<ProcessNode> {
+    if ports.is_empty() {
+        return vec![];
+    }
+
+    let mut unique_pids: Vec<u32> = ports.iter().flat_map(|p| p.pids.iter().copied()).collect();
+    unique_pids.sort();
+    unique_pids.dedup();
+
+    let process_map = refreshed_process_map(state, &unique_pids).await;
+    let orphaned = state
+        .process_enricher
+        .read()
+        .await
+        .orphaned_pids(process_map.values());
+    let suspended = live_suspended_pids(state).await;
+    let docker = state.docker.read().await;
+    let shared_listeners = shared_listener_ports(&ports);
+    let listeners = listener_ports(&ports);
+    let context =
+        NodeContext::load(state, &docker, &unique_pids, &ports, orphaned, suspended).await;
+
+    let mut nodes = Vec::new();
+    for port_info in ports {
+        let entry = PortEntry {
+            shared_port: port_info.state == SocketState::Listening
+                && shared_listeners.contains(&namespaced_port(
+                    port_info.protocol,
+                    port_info.local_port,
+                    &port_info.netns,
+                )),
+            ..to_port_entry(&port_info, &listeners)
+        };
+        for &pid in &port_info.pids {
+            let info = process_map.get(&pid).cloned();
+            let id = socket_node_id(pid, &port_info);
+            nodes.push(context.node(&docker, id, pid, info, vec![entry.clone()]).await);
+        }
+    }
+
+    nodes
+}
+
+/// Current state of a few PIDs, for inspector views that follow specific processes
+///
+/// Cheaper than `get_processes`: only the given PIDs are refreshed, though sockets are
+/// still enumerated to find their ports. Results are in the order of `pids`, with
+/// duplicates dropped; exited processes come back with `alive: false`.
+#[tauri::command]
+pub async fn watch_pids(
+    state: State<'_, AppStateManager>,
+    pids: Vec<u32>,
+) -> Result<Vec<WatchedProcess>, AppError> {
+    let mut seen = HashSet::new();
+    let pids: Vec<u32> = pids.into_iter().filter(|pid| seen.insert(*pid)).collect();
+    if pids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let all_ports = tokio::task::spawn_blocking(scan_ports)
+        .await
+        .map_err(anyhow::Error::from)
+        .and_then(|result| result)
+        .map_err(|e| AppError::new(ErrorCode::ScanError, &e.to_string()))?
+        .ports;
+    let connection_counts = count_established_connections(&all_ports);
+    let shared_listeners = shared_listener_ports(&all_ports);
+    let ports: Vec<PortInfo> = all_ports
+        .into_iter()
+        .filter(|port| port.pids.iter().any(|pid| seen.contains(pid)))
+        .collect();
+    let mut pid_to_ports = group_ports_by_pid(&ports);
+
+    // Only live PIDs are refreshed, since a dead one makes the targeted refresh fall
+    // back to a full one; orphan and suspended state come from live checks too, as the
+    // targeted refresh keeps exited parents in the table
+    let alive = live_pids(&pids);
+    let live: Vec<u32> = pids.iter().copied().filter(|pid| alive.contains(pid)).collect();
+    let mut process_map = {
+        let mut enricher = state.process_enricher.write().await;
+        enricher.refresh_specific(&live);
+        enricher.downgrade().get_processes_info(&live)
+    };
+    fill_from_ps(&mut process_map, &live).await;
+    let parents: Vec<u32> = process_map.values().filter_map(|info| info.parent_pid).collect();
+    let live_parents = live_pids(&parents);
+    let orphaned: HashSet<u32> = process_map
+        .values()
+        .filter(|info| is_orphaned(info.parent_pid, |ppid| live_parents.contains(&ppid)))
+        .map(|info| info.pid)
+        .collect();
+    let suspended: HashSet<u32> = state
+        .suspended
+        .read()
+        .await
+        .intersection(&alive)
+        .copied()
+        .collect();
+    let docker = state.docker.read().await;
+    let context = NodeContext::load(&state, &docker, &live, &ports, orphaned, suspended).await;
+
+    let mut watched = Vec::with_capacity(pids.len());
+    for pid in pids {
+        let Some(info) = process_map.get(&pid).cloned() else {
+            watched.push(WatchedProcess {
+                pid,
+                alive: false,
+                process: None,
+            });
+            continue;
+        };
+
+        let mut ports = pid_to_ports.remove(&pid).unwrap_or_default();
+        for entry in ports.iter_mut().filter(|e| e.state == SocketState::Listening) {
+            let key = namespaced_port(entry.protocol, entry.local_port, &entry.netns);
+            entry.shared_port = shared_listeners.contains(&key);
+            entry.connection_count = connection_counts.get(&(pid, key)).copied().unwrap_or(0);
+        }
+
+        let id = process_node_id(pid, &ports);
+        watched.push(WatchedProcess {
+            pid,
+            alive: true,
+            process: Some(context.node(&docker, id, pid, Some(info), ports).await),
+        });
+    }
+
+    Ok(watched)
+}
+
+/// Check whether a port is free to bind by actually trying to bind it
+#[tauri::command]
+pub async fn is_port_available(
+    port: u16,
+    protocol: Protocol,
+) -> Result<PortAvailability, AppError> {
+    if port == 0 {
+        return Err(AppError::new(ErrorCode::InvalidRange, "Port 0 is not a valid port"));
+    }
+
+    let status = tokio::task::spawn_blocking(move || check_port_available(port, protocol))
+        .await
+        .map_err(|e| AppError::new(ErrorCode::ScanError, &e.to_string()))?;
+
+    Ok(PortAvailability {
+        port,
+        protocol,
+        status,
+    })
+}
+
+/// Report how much of the ephemeral port range is taken by TCP connections
+///
+/// Counts distinct ports in the range held by established, TIME_WAIT or other
+/// non-listening sockets, including ones with no visible owner. `warning` is set at
+/// `warn_percent` utilization (default 80).
+#[tauri::command]
+pub async fn port_pressure(warn_percent: Option<f64>) -> Result<PortPressure, AppError> {
+    let warn_percent = warn_percent.unwrap_or(DEFAULT_PRESSURE_WARN_PERCENT);
+    if !(0.0..=100.0).contains(&warn_percent) {
+        return Err(AppError::new(
+            ErrorCode::InvalidInput,
+            "The warning threshold must be between 0 and 100 percent",
+        ));
+    }
+
+    let pressure = tokio::task::spawn_blocking(move || {
+        scan_tcp_socket_states().map(|sockets| {
+            summarize_port_pressure(&sockets, ephemeral_port_range(), warn_percent)
+        })
+    })
+    .await
+    .map_err(anyhow::Error::from)
+    .and_then(|result| result)
+    .map_err(|e| AppError::new(ErrorCode::ScanError, &e.to_string()))?;
+
+    if pressure.warning {
+        log::warn!(
+            "{:.0}% of ephemeral ports {}-{} are in use ({} in TIME_WAIT)",
+            pressure.utilization_percent,
+            pressure.range_start,
+            pressure.range_end,
+            pressure.time_wait
+        );
+    }
+    Ok(pressure)
+}
+
+/// How long `test_external_reachability` waits for a connection to be accepted
+const REACHABILITY_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Check whether a port accepts connections on the machine's LAN address
+///
+/// Connects to the primary non-loopback interface address rather than localhost, so a
+/// server bound only to loopback shows up as refused. A host firewall may still let
+/// this local connection through while blocking other machines.
+#[tauri::command]
+pub async fn test_external_reachability(port: u16) -> Result<ReachabilityCheck, AppError> {
+    if port == 0 {
+        return Err(AppError::new(ErrorCode::InvalidRange, "Port 0 is not a valid port"));
+    }
+
+    tokio::task::spawn_blocking(move || {
+        let ip = primary_interface_ip().map_err(|e| {
+            AppError::with_details(
+                ErrorCode::NotFound,
+                "No network interface to test from",
+                &format!("{:#}", e),
+            )
+        })?;
+        let status = check_reachability((ip, port).into(), REACHABILITY_TIMEOUT).map_err(|e| {
+            AppError::with_details(
+                ErrorCode::ScanError,
+                &format!("Failed to connect to {}:{}", ip, port),
+                &e.to_string(),
+            )
+        })?;
+        Ok(ReachabilityCheck {
+            port,
+            address: ip.to_string(),
+            status,
+        })
+    })
+    .await
+    .map_err(|e| AppError::new(ErrorCode::ScanError, &e.to_string()))?
+}
+
+/// Concrete addresses the listeners on `port` can be reached on
+///
+/// Wildcard binds are expanded to the addresses of every interface that is up; a
+/// listener bound to one address yields just that address. Empty when nothing listens
+/// on the port.
+#[tauri::command]
+pub async fn resolve_listener_interfaces(port: u16) -> Result<Vec<ListenerInterface>, AppError> {
+    tokio::task::spawn_blocking(move || {
+        let listeners = find_port_users(port)
+            .map_err(|e| AppError::new(ErrorCode::ScanError, &e.to_string()))?;
+        let interfaces = interface_addresses().map_err(|e| {
+            AppError::with_details(
+                ErrorCode::ScanError,
+                "Failed to list network interfaces",
+                &e.to_string(),
+            )
+        })?;
+
+        let mut resolved: Vec<ListenerInterface> = Vec::new();
+        for listener in listeners.iter().filter(|p| p.state == SocketState::Listening) {
+            for (interface, address) in reachable_addresses(&listener.local_address, &interfaces) {
+                let entry = ListenerInterface {
+                    protocol: listener.protocol,
+                    interface,
+                    address,
+                    port,
+                };
+                if !resolved.contains(&entry) {
+                    resolved.push(entry);
+                }
+            }
+        }
+        Ok(resolved)
+    })
+    .await
+    .map_err(|e| AppError::new(ErrorCode::ScanError, &e.to_string()))?
+}
+
+/// Forget cached reverse DNS results, including remembered failures
+///
+/// Returns how many addresses were dropped.
+#[tauri::command]
+pub async fn clear_dns_cache(state: State<'_, AppStateManager>) -> Result<usize, AppError> {
+    let dropped = state.dns_resolver.read().await.clear().await;
+    log::info!("Cleared {} cached reverse DNS results", dropped);
+    Ok(dropped)
+}
+
+/// Configure the GeoIP/ASN database used to annotate remote connections
+///
+/// Passing `None` disables the annotation.
+#[tauri::command]
+pub async fn set_geoip_database(
+    state: State<'_, AppStateManager>,
+    path: Option<String>,
+) -> Result<(), AppError> {
+    let database = match path {
+        Some(path) => Some(GeoIpDatabase::open(&path).map_err(|e| {
+            AppError::with_details(
+                ErrorCode::GeoipError,
+                "Failed to load GeoIP database",
+                &format!("{:#}", e),
+            )
+        })?),
+        None => None,
+    };
+
+    *state.geoip.write().await = database;
+    Ok(())
+}
+
+/// Find established connections whose remote address belongs to a matching network
+///
+/// `org_substring` is matched case-insensitively against the ASN organization, or as
+/// an AS number when it looks like one (`16509` or `AS16509`). Returns the owning
+/// processes with their ports narrowed to the matching connections. Needs a database
+/// from `set_geoip_database`.
+#[tauri::command]
+pub async fn get_connections_to_org(
+    state: State<'_, AppStateManager>,
+    org_substring: String,
+) -> Result<Vec<ProcessNode>, AppError> {
+    if state.geoip.read().await.is_none() {
+        return Err(AppError::with_details(
+            ErrorCode::GeoipError,
+            "No GeoIP database loaded",
+            "Load an ASN database with set_geoip_database to look up remote organizations",
+        ));
+    }
+    let query = org_substring.trim().to_lowercase();
+    if query.is_empty() {
+        return Err(AppError::new(ErrorCode::InvalidInput, "Organization must not be empty"));
+    }
+
+    let options = ScanOptions {
+        show_all_connections: true,
+        ..Default::default()
+    };
+    let app_state = build_app_state(&state, options).await?;
+
+    Ok(app_state
+        .processes
+        .into_iter()
+        .filter_map(|mut node| {
+            node.ports.retain(|port| {
+                port.state == SocketState::Established
+                    && port.remote_asn.as_ref().is_some_and(|asn| asn_matches(asn, &query))
+            });
+            (!node.ports.is_empty()).then_some(node)
+        })
+        .collect())
+}
+
+/// Whether `asn` matches a lowercase query: its number (optionally `as`-prefixed) or
+/// a substring of its organization
+fn asn_matches(asn: &AsnInfo, query: &str) -> bool {
+    let number = query.strip_prefix("as").unwrap_or(query);
+    if let Ok(number) = number.parse::<u32>() {
+        return asn.asn == number;
+    }
+    asn.org.to_lowercase().contains(query)
+}
+
+/// Fetch details for any PID, including ones without sockets
+#[tauri::command]
+pub async fn get_process_detail(
+    state: State<'_, AppStateManager>,
+    pid: u32,
+) -> Result<ProcessDetail, AppError> {
+    let info = refreshed_process_info(&state, pid)
+        .await
+        .ok_or_else(|| AppError::process_not_found(pid))?;
+
+    let safety = check_process_safety(pid, &info.name);
+    let is_docker_proxy = is_docker_process_name(&info.name);
+
+    Ok(ProcessDetail {
+        info,
+        is_protected: !safety.is_safe(),
+        protection_reason: safety.reason(),
+        is_docker_proxy,
+    })
+}
+
+/// Summarize a process as plain text for pasting into chats and tickets
+///
+/// Covers identity, resource usage, listening ports and, for Docker proxies, the
+/// container behind them. Fields the OS doesn't expose read "unknown".
+#[tauri::command]
+pub async fn format_process_report(
+    state: State<'_, AppStateManager>,
+    pid: u32,
+) -> Result<String, AppError> {
+    let info = refreshed_process_info(&state, pid)
+        .await
+        .ok_or_else(|| AppError::process_not_found(pid))?;
+
+    let ports = scan_listening_ports()
+        .map_err(|e| AppError::new(ErrorCode::ScanError, &e.to_string()))?;
+
+    let mut container = None;
+    let docker = state.docker.read().await;
+    if is_docker_process_name(&info.name) && docker.can_correlate_local_ports() {
+        let _ = docker.refresh().await;
+        for port in ports.iter().filter(|p| p.pids.contains(&pid)) {
+            container = docker
+                .get_container_for_port(
+                    port.local_port,
+                    Some(port.protocol),
+                    Some(&port.local_address),
+                )
+                .await;
+            if container.is_some() {
+                break;
+            }
+        }
+    }
+
+    Ok(render_process_report(&info, &ports, container.as_ref(), Utc::now()))
+}
+
+/// Walk up from a process to its top-level ancestor
+///
+/// The chain starts with `pid` itself and ends at PID 1 or the first parent that is no
+/// longer running.
+#[tauri::command]
+pub async fn get_root_ancestor(
+    state: State<'_, AppStateManager>,
+    pid: u32,
+) -> Result<Vec<ProcessInfo>, AppError> {
+    let mut enricher = state.process_enricher.write().await;
+    enricher.refresh_processes_only();
+
+    let chain = enricher.downgrade().ancestor_chain(pid);
+    if chain.is_empty() {
+        return Err(AppError::process_not_found(pid));
+    }
+    Ok(chain)
+}
+
+/// Read a process's environment variables (Linux only)
+///
+/// Values of secret-looking variables (TOKEN, SECRET, PASSWORD, KEY) are redacted
+/// unless `include_secrets` is set.
+#[tauri::command]
+pub async fn get_process_env(
+    pid: u32,
+    include_secrets: Option<bool>,
+) -> Result<HashMap<String, String>, AppError> {
+    use std::io::ErrorKind;
+
+    let mut env = read_process_env(pid).map_err(|e| match e.kind() {
+        ErrorKind::NotFound => AppError::process_not_found(pid),
+        ErrorKind::PermissionDenied => AppError::with_details(
+            ErrorCode::AccessDenied,
+            "Insufficient privileges",
+            &format!("Cannot read the environment of PID {}", pid),
+        ),
+        ErrorKind::Unsupported => AppError::new(ErrorCode::NotSupported, &e.to_string()),
+        _ => AppError::new(ErrorCode::ScanError, &e.to_string()),
+    })?;
+
+    if !include_secrets.unwrap_or(false) {
+        redact_secrets(&mut env);
+    }
+    Ok(env)
+}
+
+/// Find the systemd unit (e.g. `nginx.service`) a process belongs to (Linux only)
+///
+/// Stopping the unit avoids fighting its restart policy. Returns None when the process
+/// is not part of a service or scope, or the host doesn't run systemd.
+#[tauri::command]
+pub async fn get_systemd_unit(pid: u32) -> Result<Option<String>, AppError> {
+    use std::io::ErrorKind;
+
+    read_systemd_unit(pid).map_err(|e| match e.kind() {
+        ErrorKind::NotFound => AppError::process_not_found(pid),
+        ErrorKind::PermissionDenied => AppError::with_details(
+            ErrorCode::AccessDenied,
+            "Insufficient privileges",
+            &format!("Cannot read the cgroup of PID {}", pid),
+        ),
+        _ => AppError::new(ErrorCode::ScanError, &e.to_string()),
+    })
+}
+
+/// Name the Windows services a process hosts, e.g. `SSDPSRV` for an svchost listener
+///
+/// Empty when the PID hosts no service, and always on other platforms.
+#[tauri::command]
+pub async fn get_windows_service(pid: u32) -> Result<Vec<String>, AppError> {
+    tokio::task::spawn_blocking(move || read_windows_services(pid))
+        .await
+        .map_err(|e| AppError::new(ErrorCode::ScanError, &e.to_string()))?
+        .map_err(|e| AppError::new(ErrorCode::ScanError, &e.to_string()))
+}
+
+/// Reveal a process's executable in the platform file manager
+#[tauri::command]
+pub async fn reveal_process_exe(
+    app: AppHandle,
+    state: State<'_, AppStateManager>,
+    pid: u32,
+) -> Result<(), AppError> {
+    let info = refreshed_process_info(&state, pid)
+        .await
+        .ok_or_else(|| AppError::process_not_found(pid))?;
+
+    let exe_path = info.exe_path.ok_or_else(|| {
+        AppError::new(
+            ErrorCode::NotFound,
+            &format!("Executable path of PID {} is unavailable", pid),
+        )
+    })?;
+
+    app.opener()
+        .reveal_item_in_dir(&exe_path)
+        .map_err(|e| {
+            AppError::with_details(
+                ErrorCode::OpenError,
+                "Failed to reveal executable",
+                &e.to_string(),
+            )
+        })
+}
+
+/// Open a process's working directory in the platform file manager (Linux only)
+#[tauri::command]
+pub async fn reveal_process_cwd(app: AppHandle, pid: u32) -> Result<(), AppError> {
+    use std::io::ErrorKind;
+
+    let cwd = read_process_cwd(pid).map_err(|e| match e.kind() {
+        ErrorKind::NotFound => AppError::process_not_found(pid),
+        ErrorKind::PermissionDenied => AppError::with_details(
+            ErrorCode::AccessDenied,
+            "Insufficient privileges",
+            &format!("Cannot read the working directory of PID {}", pid),
+        ),
+        ErrorKind::Unsupported => AppError::new(ErrorCode::NotSupported, &e.to_string()),
+        _ => AppError::new(ErrorCode::NotFound, &e.to_string()),
+    })?;
+
+    app.opener()
+        .open_path(cwd.to_string_lossy(), None::<&str>)
+        .map_err(|e| {
+            AppError::with_details(ErrorCode::OpenError, "Failed to open directory", &e.to_string())
+        })
+}
+
+/// Add a process name to the ignore list (case-insensitive)
+///
+/// Matching processes are still returned by scans, flagged `ignored`. A blank name is
+/// rejected with `InvalidInput`.
+#[tauri::command]
+pub async fn add_ignored_process(
+    state: State<'_, AppStateManager>,
+    name: String,
+) -> Result<Vec<String>, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::new(ErrorCode::InvalidInput, "Process name cannot be empty"));
+    }
+
+    let mut config = state.config.write().await;
+    if config.add_ignored_process(name) {
+        save_config(&config)?;
+    }
+    Ok(config.settings.ignored_processes.clone())
+}
+
+/// Remove a process name from the ignore list
+#[tauri::command]
+pub async fn remove_ignored_process(
+    state: State<'_, AppStateManager>,
+    name: String,
+) -> Result<Vec<String>, AppError> {
+    let mut config = state.config.write().await;
+    if config.remove_ignored_process(name.trim()) {
+        save_config(&config)?;
+    }
+    Ok(config.settings.ignored_processes.clone())
+}
+
+/// Attach a note to an executable path, or clear it with a blank note
+///
+/// Notes are keyed by executable path rather than PID so they survive restarts; scans
+/// attach them to every matching process. Returns all notes.
+#[tauri::command]
+pub async fn set_process_note(
+    state: State<'_, AppStateManager>,
+    exe_path: String,
+    note: String,
+) -> Result<BTreeMap<String, String>, AppError> {
+    let exe_path = exe_path.trim();
+    if exe_path.is_empty() {
+        return Err(AppError::new(ErrorCode::InvalidInput, "Executable path must not be empty"));
+    }
+
+    let mut config = state.config.write().await;
+    if config.set_process_note(exe_path, &note) {
+        save_config(&config)?;
+    }
+    Ok(config.settings.process_notes.clone())
+}
+
+/// Replace the ports whose holders are refused a graceful kill, or restore the defaults
+///
+/// Passing None restores SSH, DNS and CUPS (22, 53, 631). The setting is saved and
+/// applies to every later kill. Returns the ports now protected.
+#[tauri::command]
+pub async fn set_protected_ports(
+    state: State<'_, AppStateManager>,
+    ports: Option<Vec<u16>>,
+) -> Result<Vec<u16>, AppError> {
+    let mut config = state.config.write().await;
+    config.settings.protected_ports = ports.map(|mut ports| {
+        ports.sort_unstable();
+        ports.dedup();
+        ports
+    });
+    save_config(&config)?;
+    apply_protection_settings(&config);
+    Ok(protected_ports())
+}
+
+/// Ports whose holders are currently refused a graceful kill
+#[tauri::command]
+pub async fn get_protected_ports() -> Result<Vec<u16>, AppError> {
+    Ok(protected_ports())
+}
+
+/// Replace the PID ranges refused on top of PIDs 0 and 1, or restore the defaults
+///
+/// Passing None restores the platform's low system PIDs. Ranges are inclusive and
+/// must not be reversed. The setting is saved and applies to every later kill.
+/// Returns the ranges now protected.
+#[tauri::command]
+pub async fn set_protected_pid_ranges(
+    state: State<'_, AppStateManager>,
+    ranges: Option<Vec<PidRange>>,
+) -> Result<Vec<PidRange>, AppError> {
+    if let Some(range) = ranges.iter().flatten().find(|range| range.start > range.end) {
+        return Err(AppError::new(
+            ErrorCode::InvalidRange,
+            &format!("PID range {}-{} is reversed", range.start, range.end),
+        ));
+    }
+
+    let mut config = state.config.write().await;
+    config.settings.protected_pid_ranges = ranges;
+    save_config(&config)?;
+    apply_protection_settings(&config);
+    Ok(config
+        .protected_pid_ranges()
+        .into_iter()
+        .map(|range| PidRange {
+            start: *range.start(),
+            end: *range.end(),
+        })
+        .collect())
+}
+
+/// Hand the persisted protection settings to the safety registry
+fn apply_protection_settings(config: &ConfigStore) {
+    crate::surgery::set_protected_ports(config.protected_ports());
+    crate::surgery::set_protected_pid_ranges(config.protected_pid_ranges());
+}
+
+/// Persist settings, reporting failures to the frontend
+fn save_config(config: &ConfigStore) -> Result<(), AppError> {
+    config.save().map_err(|e| {
+        AppError::with_details(
+            ErrorCode::ConfigError,
+            "Failed to save settings",
+            &format!("{:#}", e),
+        )
+    })
+}
+
+/// Report which termination features work on this platform
+///
+/// Lets the frontend hide actions that would only fail, e.g. suspend on Windows.
+/// Elevation is reported unavailable while safe mode is on.
+#[tauri::command]
+pub async fn capabilities(state: State<'_, AppStateManager>) -> Result<Capabilities, AppError> {
+    let safe_mode = *state.safe_mode.read().await;
+    Ok(Capabilities {
+        supports_signals: supported_termination_signals(),
+        supports_suspend: ensure_stop_signals_supported().is_ok(),
+        supports_renice: true,
+        supports_killpg: true,
+        elevation_method: (!safe_mode).then(|| ELEVATION_METHOD.to_string()),
+        docker_available: state.docker.read().await.is_available(),
+    })
+}
+
+/// Kill a process by PID
+///
+/// Pass the name the UI showed as `expected_name` to guard against PID reuse: if the
+/// PID now belongs to a differently named process, nothing is signalled and the call
+/// fails with `PID_REUSED`.
 #[tauri::command]
 pub async fn kill_process(
     state: State<'_, AppStateManager>,
     pid: u32,
     force: bool,
+    expected_name: Option<String>,
+) -> Result<KillResult, AppError> {
+    log::info!("Kill request for PID {} (force: {})", pid, force);
+    let allow_elevation = check_force_allowed(&state, force).await?;
+
+    let mut terminator = state.terminator.write().await;
+    kill_with_elevation(&mut terminator, pid, force, expected_name.as_deref(), allow_elevation)
+}
+
+/// Kill several processes, returning one result per input PID in the same order
+///
+/// Each PID is handled independently, so a protected or failing entry doesn't stop the
+/// rest. Duplicate PIDs are only signalled once and repeat the first result.
+/// `expected_names`, parallel to `pids`, guards each one against PID reuse like
+/// `kill_process` does; a reused PID fails its own result.
+#[tauri::command]
+pub async fn kill_processes(
+    state: State<'_, AppStateManager>,
+    pids: Vec<u32>,
+    force: bool,
+    expected_names: Option<Vec<String>>,
+) -> Result<Vec<KillResult>, AppError> {
+    log::info!("Bulk kill request for {} PIDs (force: {})", pids.len(), force);
+    let expected: HashMap<u32, String> = match expected_names {
+        Some(names) if names.len() != pids.len() => {
+            return Err(AppError::new(
+                ErrorCode::InvalidInput,
+                "expected_names must have one entry per PID",
+            ));
+        }
+        Some(names) => pids.iter().copied().zip(names).rev().collect(),
+        None => HashMap::new(),
+    };
+    let allow_elevation = check_force_allowed(&state, force).await?;
+
+    let mut unique = HashSet::new();
+    let targets: Vec<u32> = pids.iter().copied().filter(|pid| unique.insert(*pid)).collect();
+
+    let mut terminator = state.terminator.write().await;
+    let listeners = prepare_batch(&mut terminator, force)?;
+    let results: HashMap<u32, KillResult> = targets
+        .iter()
+        .copied()
+        .zip(kill_many_with_elevation(
+            &mut terminator,
+            &targets,
+            &expected,
+            force,
+            allow_elevation,
+            &listeners,
+        ))
+        .collect();
+
+    Ok(pids.iter().map(|pid| results[pid].clone()).collect())
+}
+
+/// Kill every process whose name matches `pattern`, returning one result per match
+///
+/// A pattern with `*` or `?` is an anchored glob, anything else a case-insensitive
+/// substring. Names shorter than three characters only match exactly, and globs with
+/// fewer than three literal characters are rejected outright.
+/// Process Surgeon itself is never matched, and protected processes come back as
+/// refused results from the usual safety check. Matches that need privileges are
+/// elevated together, so there is at most one prompt.
+#[tauri::command]
+pub async fn kill_by_name(
+    state: State<'_, AppStateManager>,
+    pattern: String,
+    force: bool,
+) -> Result<Vec<KillResult>, AppError> {
+    let pattern = NamePattern::parse(&pattern)
+        .map_err(|e| AppError::new(ErrorCode::InvalidInput, &e.to_string()))?;
+    let allow_elevation = check_force_allowed(&state, force).await?;
+
+    // Names as matched, so a PID reused before the kill isn't signalled
+    let matched: HashMap<u32, String> = {
+        let mut enricher = state.process_enricher.write().await;
+        enricher.refresh_processes_only();
+        enricher
+            .get_all_processes()
+            .into_iter()
+            .filter(|info| pattern.matches(&info.name) && !is_self_process(info.pid))
+            .map(|info| (info.pid, info.name))
+            .collect()
+    };
+    let mut pids: Vec<u32> = matched.keys().copied().collect();
+    pids.sort_unstable();
+    log::info!("Kill by name request matched {} processes (force: {})", pids.len(), force);
+
+    let mut terminator = state.terminator.write().await;
+    let listeners = prepare_batch(&mut terminator, force)?;
+    Ok(kill_many_with_elevation(
+        &mut terminator,
+        &pids,
+        &matched,
+        force,
+        allow_elevation,
+        &listeners,
+    ))
+}
+
+/// Make a port free, whether a container or a plain process holds it
+///
+/// Containers publishing the port are stopped (killed with `force`); otherwise every
+/// process owning a socket on the port is terminated, subject to the usual safety
+/// checks. Reports `AlreadyFree` when nothing holds the port.
+#[tauri::command]
+pub async fn free_port(
+    state: State<'_, AppStateManager>,
+    port: u16,
+    force: bool,
+) -> Result<FreePortResult, AppError> {
+    log::info!("Free port request for {} (force: {})", port, force);
+    let allow_elevation = check_force_allowed(&state, force).await?;
+
+    let containers = {
+        let docker = state.docker.read().await;
+        if docker.is_available() && docker.refresh().await.is_ok() {
+            docker.containers_for_port(port).await
+        } else {
+            Vec::new()
+        }
+    };
+    if !containers.is_empty() {
+        let action = if force { ContainerAction::Kill } else { ContainerAction::Stop };
+        check_container_action_allowed(&state, &action).await?;
+
+        let docker = state.docker.read().await;
+        let mut results = Vec::with_capacity(containers.len());
+        for container in &containers {
+            let outcome = docker
+                .execute_action(&container.id, action.clone(), DEFAULT_STOP_TIMEOUT_SECS)
+                .await;
+            results.push(batch_action_result(&container.name, &action, outcome));
+        }
+        return Ok(FreePortResult {
+            port,
+            method: FreePortMethod::Container,
+            success: results.iter().all(|r| r.success),
+            containers,
+            pids: Vec::new(),
+            results,
+        });
+    }
+
+    let pids = tokio::task::spawn_blocking(move || find_port_users(port))
+        .await
+        .map_err(anyhow::Error::from)
+        .and_then(|result| result)
+        .map(|ports| port_owner_pids(&ports))
+        .map_err(|e| AppError::new(ErrorCode::ScanError, &e.to_string()))?;
+    if pids.is_empty() {
+        return Ok(FreePortResult {
+            port,
+            method: FreePortMethod::AlreadyFree,
+            success: true,
+            containers: Vec::new(),
+            pids,
+            results: Vec::new(),
+        });
+    }
+    let owners: HashMap<u32, String> = refreshed_process_map(&state, &pids)
+        .await
+        .into_iter()
+        .map(|(pid, info)| (pid, info.name))
+        .collect();
+
+    let mut terminator = state.terminator.write().await;
+    let listeners = prepare_batch(&mut terminator, force)?;
+    let results = kill_many_with_elevation(
+        &mut terminator,
+        &pids,
+        &owners,
+        force,
+        allow_elevation,
+        &listeners,
+    );
+
+    Ok(FreePortResult {
+        port,
+        method: FreePortMethod::Process,
+        success: results.iter().all(|r| r.success),
+        containers: Vec::new(),
+        pids,
+        results,
+    })
+}
+
+/// Refuse a force kill in safe mode; returns whether elevation may be requested
+async fn check_force_allowed(state: &AppStateManager, force: bool) -> Result<bool, AppError> {
+    let safe_mode = *state.safe_mode.read().await;
+    if safe_mode && force {
+        return Err(AppError::safe_mode_blocked("Force killing (SIGKILL)"));
+    }
+    Ok(!safe_mode)
+}
+
+/// Turn safe mode on or off for shared and production machines
+///
+/// While on, force kills, elevated termination and container kill/remove are refused
+/// with a `SAFE_MODE_BLOCKED` error instead of being downgraded silently. Graceful
+/// kills and container stops still work.
+#[tauri::command]
+pub async fn set_safe_mode(
+    state: State<'_, AppStateManager>,
+    enabled: bool,
+) -> Result<(), AppError> {
+    *state.safe_mode.write().await = enabled;
+    log::info!("Safe mode {}", if enabled { "enabled" } else { "disabled" });
+    Ok(())
+}
+
+/// Terminate a PID, retrying with elevated privileges when the plain attempt needs them
+///
+/// Without `allow_elevation` (safe mode) the retry is skipped and the result says why.
+fn kill_with_elevation(
+    terminator: &mut ProcessTerminator,
+    pid: u32,
+    force: bool,
+    expected_name: Option<&str>,
+    allow_elevation: bool,
+) -> Result<KillResult, AppError> {
+    match terminator.terminate_expecting(pid, force, expected_name) {
+        Ok(result) => {
+            if !result.success && result.required_elevation && !allow_elevation {
+                Ok(KillResult {
+                    message: format!(
+                        "{}; elevated termination is blocked by safe mode",
+                        result.message
+                    ),
+                    ..result
+                })
+            } else if !result.success && result.required_elevation {
+                log::info!("Requesting elevated termination for PID {}", pid);
+                // Try elevated termination
+                match request_elevated_termination(pid, force) {
+                    Ok(elevated_result) => Ok(elevated_result),
+                    Err(e) => Ok(KillResult {
+                        success: false,
+                        message: format!("Elevated termination failed: {}", e),
+                        required_elevation: true,
+                    }),
+                }
+            } else {
+                Ok(result)
+            }
+        }
+        Err(e) if e.is::<PidReused>() => {
+            log::warn!("{}", e);
+            Err(AppError::new(ErrorCode::PidReused, &e.to_string()))
+        }
+        Err(e) => Err(AppError::new(ErrorCode::KillError, &e.to_string())),
+    }
+}
+
+/// `ProcessTerminator::prepare_batch`, refusing the batch when listeners can't be scanned
+fn prepare_batch(
+    terminator: &mut ProcessTerminator,
+    force: bool,
+) -> Result<Vec<PortInfo>, AppError> {
+    terminator
+        .prepare_batch(force)
+        .map_err(|e| AppError::new(ErrorCode::KillError, &e.to_string()))
+}
+
+/// Terminate several PIDs, retrying every one that needs privileges behind one prompt
+///
+/// `listeners` come from `ProcessTerminator::prepare_batch`, so the process table and
+/// sockets are read once for the whole batch. Returns one result per PID, in order.
+/// A PID listed in `expected_names` that now runs under another name fails without
+/// being signalled. Without `allow_elevation` (safe mode) the retry is skipped and the
+/// results say why.
+fn kill_many_with_elevation(
+    terminator: &mut ProcessTerminator,
+    pids: &[u32],
+    expected_names: &HashMap<u32, String>,
+    force: bool,
+    allow_elevation: bool,
+    listeners: &[PortInfo],
+) -> Vec<KillResult> {
+    let mut results: Vec<KillResult> = pids
+        .iter()
+        .map(|&pid| {
+            terminator
+                .terminate_in_batch(
+                    pid,
+                    force,
+                    expected_names.get(&pid).map(String::as_str),
+                    listeners,
+                )
+                .unwrap_or_else(|e| KillResult {
+                    success: false,
+                    message: e.to_string(),
+                    required_elevation: false,
+                })
+        })
+        .collect();
+
+    let refused: Vec<usize> = (0..results.len())
+        .filter(|&i| !results[i].success && results[i].required_elevation)
+        .collect();
+    if refused.is_empty() {
+        return results;
+    }
+
+    if !allow_elevation {
+        for i in refused {
+            results[i].message = format!(
+                "{}; elevated termination is blocked by safe mode",
+                results[i].message
+            );
+        }
+        return results;
+    }
+
+    let refused_pids: Vec<u32> = refused.iter().map(|&i| pids[i]).collect();
+    log::info!("Requesting elevated termination for PIDs {:?}", refused_pids);
+    match request_elevated_termination_many(&refused_pids, force) {
+        Ok(elevated) => {
+            for (i, result) in refused.into_iter().zip(elevated) {
+                results[i] = result;
+            }
+        }
+        Err(e) => {
+            for i in refused {
+                results[i] = KillResult {
+                    success: false,
+                    message: format!("Elevated termination failed: {}", e),
+                    required_elevation: true,
+                };
+            }
+        }
+    }
+    results
+}
+
+/// Authenticate once for a privileged helper that performs later elevated kills
+///
+/// Until `end_elevated_session` or five minutes without requests, kills that need
+/// elevation go through the helper instead of prompting each time (Linux and macOS).
+#[tauri::command]
+pub async fn start_elevated_session(state: State<'_, AppStateManager>) -> Result<(), AppError> {
+    if *state.safe_mode.read().await {
+        return Err(AppError::safe_mode_blocked("Elevated termination"));
+    }
+    if cfg!(windows) {
+        return Err(AppError::new(
+            ErrorCode::NotSupported,
+            "Elevated sessions are only supported on Linux and macOS",
+        ));
+    }
+
+    tokio::task::spawn_blocking(crate::surgery::start_elevated_session)
+        .await
+        .map_err(anyhow::Error::from)
+        .and_then(|result| result)
+        .map_err(|e| {
+            AppError::with_details(
+                ErrorCode::AccessDenied,
+                "Elevation was not granted",
+                &e.to_string(),
+            )
+        })
+}
+
+/// End the elevated session early, returning whether one was running
+#[tauri::command]
+pub async fn end_elevated_session() -> Result<bool, AppError> {
+    tokio::task::spawn_blocking(crate::surgery::end_elevated_session)
+        .await
+        .map_err(|e| AppError::new(ErrorCode::KillError, &e.to_string()))
+}
+
+/// Longest `kill_and_watch` will keep re-killing respawned processes
+const MAX_WATCH_SECS: u64 = 60;
+
+/// Delay between re-scans in `kill_and_watch`
+const WATCH_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Terminate a process, then keep killing anything that respawns in its place
+///
+/// Best effort against supervisors that restart a tool within milliseconds: for
+/// `watch_secs` (at most 60) any process started after the kill that runs the same
+/// executable or listens on one of the original ports is terminated too. Processes
+/// already running at the kill, e.g. sibling workers, are left alone. Every kill goes
+/// through the usual safety checks and elevation is never requested. Returns a log of
+/// each kill; one that errors is logged as failed and the watch goes on.
+#[tauri::command]
+pub async fn kill_and_watch(
+    state: State<'_, AppStateManager>,
+    pid: u32,
+    watch_secs: u64,
+) -> Result<Vec<WatchAction>, AppError> {
+    log::info!("Kill-and-watch request for PID {} ({}s)", pid, watch_secs);
+
+    let target = refreshed_process_info(&state, pid)
+        .await
+        .ok_or_else(|| AppError::process_not_found(pid))?;
+    let watched_ports: HashSet<u16> = scan_listening_ports()
+        .map_err(|e| AppError::new(ErrorCode::ScanError, &e.to_string()))?
+        .into_iter()
+        .filter(|p| p.pids.contains(&pid))
+        .map(|p| p.local_port)
+        .collect();
+    // A PID reused after the kill has a new start time, so it still counts as a respawn
+    let preexisting: HashSet<(u32, Option<DateTime<Utc>>)> = {
+        let mut enricher = state.process_enricher.write().await;
+        enricher.refresh_processes_only();
+        enricher
+            .get_all_processes()
+            .into_iter()
+            .map(|p| (p.pid, p.start_time))
+            .collect()
+    };
+
+    let mut actions = Vec::new();
+    let mut targeted = HashSet::from([pid]);
+
+    let reason = "original target".to_string();
+    let result = watch_kill(&state, pid, &target.name, reason, &mut actions).await?;
+    if !result.success {
+        return Ok(actions);
+    }
+
+    let watch_for = Duration::from_secs(watch_secs.min(MAX_WATCH_SECS));
+    let deadline = tokio::time::Instant::now() + watch_for;
+    while tokio::time::Instant::now() < deadline {
+        tokio::time::sleep(WATCH_POLL_INTERVAL).await;
+
+        let listeners = match tokio::task::spawn_blocking(scan_listening_ports).await {
+            Ok(Ok(listeners)) => listeners,
+            _ => continue,
+        };
+        let processes = {
+            let mut enricher = state.process_enricher.write().await;
+            enricher.refresh_processes_only();
+            enricher.get_all_processes()
+        };
+
+        let candidates = respawn_candidates(
+            &listeners,
+            &processes,
+            &preexisting,
+            &watched_ports,
+            target.exe_path.as_deref(),
+        );
+        for (respawn_pid, name, reason) in candidates {
+            if !targeted.insert(respawn_pid) {
+                continue;
+            }
+            if let Err(e) = watch_kill(&state, respawn_pid, &name, reason, &mut actions).await {
+                log::warn!("Kill-and-watch: PID {}: {}", respawn_pid, e.message);
+            }
+        }
+    }
+
+    Ok(actions)
+}
+
+/// Terminate one process for `kill_and_watch` and record the outcome
+///
+/// A kill that errors is recorded as failed before the error is returned.
+async fn watch_kill(
+    state: &AppStateManager,
+    pid: u32,
+    name: &str,
+    reason: String,
+    actions: &mut Vec<WatchAction>,
+) -> Result<KillResult, AppError> {
+    let outcome = state.terminator.write().await.terminate(pid, false);
+    let result = match &outcome {
+        Ok(result) => result.clone(),
+        Err(e) => KillResult {
+            success: false,
+            message: e.to_string(),
+            required_elevation: false,
+        },
+    };
+    log::info!("Kill-and-watch: PID {} ({}): {}", pid, reason, result.message);
+
+    actions.push(WatchAction {
+        pid,
+        name: name.to_string(),
+        reason,
+        result: result.clone(),
+        at: Utc::now(),
+    });
+    outcome
+        .map(|_| result)
+        .map_err(|e| AppError::new(ErrorCode::KillError, &e.to_string()))
+}
+
+/// Processes that took over a watched port or run the watched executable
+///
+/// Processes in `preexisting`, the (pid, start time) pairs seen before the kill, are
+/// skipped. Returns (pid, name, reason) once per PID, port matches first.
+fn respawn_candidates(
+    listeners: &[PortInfo],
+    processes: &[ProcessInfo],
+    preexisting: &HashSet<(u32, Option<DateTime<Utc>>)>,
+    watched_ports: &HashSet<u16>,
+    exe_path: Option<&str>,
+) -> Vec<(u32, String, String)> {
+    let by_pid: HashMap<u32, &ProcessInfo> = processes.iter().map(|p| (p.pid, p)).collect();
+    let name_of = |pid: u32| {
+        by_pid
+            .get(&pid)
+            .map_or("Unknown", |p| p.name.as_str())
+            .to_string()
+    };
+    let is_new = |pid: u32| {
+        let start_time = by_pid.get(&pid).and_then(|p| p.start_time);
+        !preexisting.contains(&(pid, start_time))
+    };
+
+    let mut seen = HashSet::new();
+    let mut candidates = Vec::new();
+
+    for listener in listeners.iter().filter(|p| watched_ports.contains(&p.local_port)) {
+        for &pid in &listener.pids {
+            if is_new(pid) && seen.insert(pid) {
+                let reason = format!("listening on watched port {}", listener.local_port);
+                candidates.push((pid, name_of(pid), reason));
+            }
+        }
+    }
+
+    if let Some(exe_path) = exe_path {
+        for process in processes.iter().filter(|p| p.exe_path.as_deref() == Some(exe_path)) {
+            if is_new(process.pid) && seen.insert(process.pid) {
+                let reason = format!("running watched executable {}", exe_path);
+                candidates.push((process.pid, process.name.clone(), reason));
+            }
+        }
+    }
+
+    candidates
+}
+
+/// Terminate a process by escalating through signals, e.g. SIGINT, SIGTERM, SIGKILL
+///
+/// Each step is `(signal, timeout_secs)`; an empty ladder uses the default one. Step
+/// timeouts above `MAX_ESCALATION_STEP_SECS` are capped.
+#[tauri::command]
+pub async fn kill_process_escalating(
+    state: State<'_, AppStateManager>,
+    pid: u32,
+    ladder: Vec<(TerminationSignal, u64)>,
+) -> Result<EscalationResult, AppError> {
+    log::info!("Escalating kill request for PID {} ({} steps)", pid, ladder.len());
+
+    let steps = if ladder.is_empty() { &DEFAULT_ESCALATION_LADDER[..] } else { &ladder[..] };
+    let escalates_to_kill = steps.iter().any(|(signal, _)| *signal == TerminationSignal::Kill);
+    if escalates_to_kill && *state.safe_mode.read().await {
+        return Err(AppError::safe_mode_blocked("Escalating to SIGKILL"));
+    }
+
+    ProcessTerminator::terminate_escalating(&state.terminator, pid, ladder)
+        .await
+        .map_err(|e| AppError::new(ErrorCode::KillError, &e.to_string()))
+}
+
+/// Close a single established TCP connection instead of killing its process (Linux only)
+///
+/// Requires CAP_NET_ADMIN; the result reports `required_elevation` when it is missing.
+#[tauri::command]
+pub async fn close_connection(
+    local: String,
+    local_port: u16,
+    remote: String,
+    remote_port: u16,
+) -> Result<KillResult, AppError> {
+    use std::io::ErrorKind;
+
+    log::info!(
+        "Close connection request for {}:{} -> {}:{}",
+        local, local_port, remote, remote_port
+    );
+
+    tokio::task::spawn_blocking(move || {
+        crate::surgery::close_connection(&local, local_port, &remote, remote_port)
+    })
+    .await
+    .map_err(|e| AppError::new(ErrorCode::KillError, &e.to_string()))?
+    .map_err(|e| match e.kind() {
+        ErrorKind::Unsupported => AppError::new(ErrorCode::NotSupported, &e.to_string()),
+        ErrorKind::InvalidInput => AppError::new(ErrorCode::InvalidInput, &e.to_string()),
+        _ => AppError::new(ErrorCode::KillError, &e.to_string()),
+    })
+}
+
+/// Longest `drain_and_stop` waits for connections to close
+const MAX_DRAIN_SECS: u64 = 120;
+
+/// Delay between connection counts in `drain_and_stop`
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Wait for a listening service's connections to close, then terminate it gracefully
+///
+/// Established connections on the process's listening ports are re-counted until none
+/// are left or `drain_secs` (at most 120) elapse. Only Linux and macOS attribute
+/// sockets reliably enough to wait on; elsewhere the process is stopped right away.
+/// Protected processes and processes without listeners are refused. `cancel_drain`
+/// ends the wait and leaves the process running. The process is checked again after
+/// the wait, so a PID reused in the meantime is not killed.
+#[tauri::command]
+pub async fn drain_and_stop(
+    state: State<'_, AppStateManager>,
+    pid: u32,
+    drain_secs: u64,
+) -> Result<DrainResult, AppError> {
+    log::info!("Drain request for PID {} ({}s)", pid, drain_secs);
+    let allow_elevation = check_force_allowed(&state, false).await?;
+
+    let info = refreshed_process_info(&state, pid)
+        .await
+        .ok_or_else(|| AppError::process_not_found(pid))?;
+    if let Some(reason) = check_process_safety(pid, &info.name).reason() {
+        return Err(AppError::with_details(
+            ErrorCode::SafetyViolation,
+            "Operation Forbidden: Protected Process",
+            &format!("Cannot stop {}: {}", info.name, reason),
+        ));
+    }
+
+    let ports = scan_owned_sockets().await?;
+    let mut listening_ports: Vec<u16> = ports
+        .iter()
+        .filter(|p| p.state == SocketState::Listening && p.pids.contains(&pid))
+        .map(|p| p.local_port)
+        .collect();
+    listening_ports.sort_unstable();
+    listening_ports.dedup();
+    if listening_ports.is_empty() {
+        return Err(AppError::new(
+            ErrorCode::InvalidInput,
+            &format!("{} (PID {}) has no listening ports to drain", info.name, pid),
+        ));
+    }
+
+    let drain_supported = cfg!(any(target_os = "linux", target_os = "macos"));
+    let mut open_connections = open_connection_count(&ports, pid, &listening_ports);
+    let started = tokio::time::Instant::now();
+    let token = CancellationToken::new();
+    if let Some(previous) = state.drains.write().await.insert(pid, token.clone()) {
+        previous.cancel();
+    }
+    if drain_supported {
+        let deadline = started + Duration::from_secs(drain_secs.min(MAX_DRAIN_SECS));
+        while open_connections > 0 && tokio::time::Instant::now() < deadline {
+            tokio::select! {
+                _ = token.cancelled() => break,
+                _ = tokio::time::sleep(DRAIN_POLL_INTERVAL) => {}
+            }
+            match scan_owned_sockets().await {
+                Ok(ports) => {
+                    open_connections = open_connection_count(&ports, pid, &listening_ports)
+                }
+                Err(e) => log::debug!("Drain of PID {} failed to scan: {}", pid, e.message),
+            }
+        }
+    }
+    {
+        let mut drains = state.drains.write().await;
+        if !token.is_cancelled() {
+            drains.remove(&pid);
+        }
+    }
+    let waited_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+    let drained = |result: KillResult| DrainResult {
+        result,
+        listening_ports: listening_ports.clone(),
+        open_connections,
+        waited_ms,
+        drain_supported,
+        cancelled: token.is_cancelled(),
+    };
+
+    if token.is_cancelled() {
+        log::info!("Drain of PID {} cancelled after {}ms", pid, waited_ms);
+        return Ok(drained(KillResult {
+            success: false,
+            message: format!("Drain of {} (PID {}) cancelled; it was left running", info.name, pid),
+            required_elevation: false,
+        }));
+    }
+
+    // The wait can take minutes, so make sure the PID still names the same process
+    let Some(current) = refreshed_process_info(&state, pid).await else {
+        return Ok(drained(KillResult {
+            success: true,
+            message: format!("{} (PID {}) exited while draining", info.name, pid),
+            required_elevation: false,
+        }));
+    };
+    if current.start_time != info.start_time {
+        return Err(AppError::new(
+            ErrorCode::PidReused,
+            &format!("PID {} was reused by {} while draining {}", pid, current.name, info.name),
+        ));
+    }
+    if let Some(reason) = check_process_safety(pid, &current.name).reason() {
+        return Err(AppError::with_details(
+            ErrorCode::SafetyViolation,
+            "Operation Forbidden: Protected Process",
+            &format!("Cannot stop {}: {}", current.name, reason),
+        ));
+    }
+
+    log::info!(
+        "Stopping PID {} after {}ms with {} connections open",
+        pid,
+        waited_ms,
+        open_connections
+    );
+    let mut terminator = state.terminator.write().await;
+    let result =
+        kill_with_elevation(&mut terminator, pid, false, Some(&info.name), allow_elevation)?;
+
+    Ok(drained(result))
+}
+
+/// End the `drain_and_stop` wait on `pid`, leaving the process running
+///
+/// Returns false when no drain of that PID is in progress.
+#[tauri::command]
+pub async fn cancel_drain(state: State<'_, AppStateManager>, pid: u32) -> Result<bool, AppError> {
+    match state.drains.write().await.remove(&pid) {
+        Some(token) => {
+            token.cancel();
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Scan every socket off the async runtime
+async fn scan_owned_sockets() -> Result<Vec<PortInfo>, AppError> {
+    tokio::task::spawn_blocking(scan_ports)
+        .await
+        .map_err(anyhow::Error::from)
+        .and_then(|result| result)
+        .map(|scan| scan.ports)
+        .map_err(|e| AppError::new(ErrorCode::ScanError, &e.to_string()))
+}
+
+/// Established connections `pid` holds on any of `listening_ports`
+fn open_connection_count(ports: &[PortInfo], pid: u32, listening_ports: &[u16]) -> usize {
+    count_established_connections(ports)
+        .into_iter()
+        .filter(|((owner, (_, port, _)), _)| *owner == pid && listening_ports.contains(port))
+        .map(|(_, count)| count)
+        .sum()
+}
+
+/// Change a process's scheduling priority instead of killing it
+///
+/// `niceness` uses the Unix scale (-20 highest to 19 lowest) and is bucketed into
+/// priority classes on Windows. Returns the effective niceness afterwards. On Unix,
+/// lowering niceness (in particular below 0) needs root or CAP_SYS_NICE and is
+/// reported as `AccessDenied`. Protected processes are refused.
+#[tauri::command]
+pub async fn set_process_priority(
+    state: State<'_, AppStateManager>,
+    pid: u32,
+    niceness: i32,
+) -> Result<i32, AppError> {
+    use std::io::ErrorKind;
+
+    log::info!("Priority request for PID {} (niceness {})", pid, niceness);
+
+    let info = refreshed_process_info(&state, pid)
+        .await
+        .ok_or_else(|| AppError::process_not_found(pid))?;
+    if let Some(reason) = check_process_safety(pid, &info.name).reason() {
+        return Err(AppError::with_details(
+            ErrorCode::SafetyViolation,
+            "Operation Forbidden: Protected Process",
+            &format!("Cannot change the priority of {}: {}", info.name, reason),
+        ));
+    }
+
+    crate::surgery::set_process_priority(pid, niceness).map_err(|e| match e.kind() {
+        ErrorKind::PermissionDenied => AppError::with_details(
+            ErrorCode::AccessDenied,
+            "Insufficient privileges",
+            &e.to_string(),
+        ),
+        ErrorKind::NotFound => AppError::process_not_found(pid),
+        _ => AppError::new(ErrorCode::PriorityError, &e.to_string()),
+    })
+}
+
+/// Capture every thread's stack of a stuck process before deciding to kill it
+///
+/// Attaches gdb or lldb, or py-spy for Python processes, whichever is installed; the
+/// process is paused while the debugger is attached. Linux and macOS only. Protected
+/// processes are refused.
+#[tauri::command]
+pub async fn capture_backtrace(
+    state: State<'_, AppStateManager>,
+    pid: u32,
+) -> Result<ProcessBacktrace, AppError> {
+    use std::io::ErrorKind;
+
+    log::info!("Backtrace request for PID {}", pid);
+
+    let info = refreshed_process_info(&state, pid)
+        .await
+        .ok_or_else(|| AppError::process_not_found(pid))?;
+    if let Some(reason) = check_process_safety(pid, &info.name).reason() {
+        return Err(AppError::with_details(
+            ErrorCode::SafetyViolation,
+            "Operation Forbidden: Protected Process",
+            &format!("Cannot attach a debugger to {}: {}", info.name, reason),
+        ));
+    }
+
+    let (tool, output) = crate::surgery::capture_backtrace(pid, &info.name)
+        .await
+        .map_err(|e| match e.kind() {
+            ErrorKind::NotFound => AppError::new(ErrorCode::DebuggerNotFound, &e.to_string()),
+            ErrorKind::PermissionDenied => AppError::with_details(
+                ErrorCode::AccessDenied,
+                "Insufficient privileges",
+                &e.to_string(),
+            ),
+            ErrorKind::Unsupported => AppError::new(ErrorCode::NotSupported, &e.to_string()),
+            _ => AppError::new(ErrorCode::BacktraceError, &e.to_string()),
+        })?;
+
+    Ok(ProcessBacktrace { pid, tool, output })
+}
+
+/// Make a process more or less likely to be reaped by the OOM killer (Linux only)
+///
+/// A gentler lever than termination for a memory hog: `score` is written to
+/// `oom_score_adj` (-1000 never reaped to 1000 reaped first). Lowering it needs root
+/// or CAP_SYS_RESOURCE. Protected processes are refused.
+#[tauri::command]
+pub async fn set_oom_score_adj(
+    state: State<'_, AppStateManager>,
+    pid: u32,
+    score: i32,
+) -> Result<OomScoreChange, AppError> {
+    use std::io::ErrorKind;
+
+    log::info!("OOM score request for PID {} (score {})", pid, score);
+
+    let info = refreshed_process_info(&state, pid)
+        .await
+        .ok_or_else(|| AppError::process_not_found(pid))?;
+    if let Some(reason) = check_process_safety(pid, &info.name).reason() {
+        return Err(AppError::with_details(
+            ErrorCode::SafetyViolation,
+            "Operation Forbidden: Protected Process",
+            &format!("Cannot change the OOM score of {}: {}", info.name, reason),
+        ));
+    }
+
+    let (previous, current) =
+        crate::surgery::set_oom_score_adj(pid, score).map_err(|e| match e.kind() {
+            ErrorKind::PermissionDenied => AppError::with_details(
+                ErrorCode::AccessDenied,
+                "Insufficient privileges",
+                &e.to_string(),
+            ),
+            ErrorKind::NotFound => AppError::process_not_found(pid),
+            ErrorKind::Unsupported => AppError::new(ErrorCode::NotSupported, &e.to_string()),
+            ErrorKind::InvalidInput => AppError::new(ErrorCode::InvalidInput, &e.to_string()),
+            _ => AppError::new(ErrorCode::OomScoreError, &e.to_string()),
+        })?;
+
+    Ok(OomScoreChange {
+        pid,
+        previous,
+        current,
+    })
+}
+
+/// List the CPU cores a process may run on, as ascending core indices
+///
+/// Supported on Linux and Windows; other platforms report `NotSupported`.
+#[tauri::command]
+pub async fn get_cpu_affinity(pid: u32) -> Result<Vec<usize>, AppError> {
+    crate::surgery::get_cpu_affinity(pid).map_err(|e| affinity_app_error(pid, e))
+}
+
+/// Pin a process to the given CPU cores, returning the affinity actually applied
+///
+/// Protected processes are refused. On Windows only the first 64 cores can be
+/// addressed.
+#[tauri::command]
+pub async fn set_cpu_affinity(
+    state: State<'_, AppStateManager>,
+    pid: u32,
+    cores: Vec<usize>,
+) -> Result<Vec<usize>, AppError> {
+    log::info!("Affinity request for PID {} (cores {:?})", pid, cores);
+
+    let info = refreshed_process_info(&state, pid)
+        .await
+        .ok_or_else(|| AppError::process_not_found(pid))?;
+    if let Some(reason) = check_process_safety(pid, &info.name).reason() {
+        return Err(AppError::with_details(
+            ErrorCode::SafetyViolation,
+            "Operation Forbidden: Protected Process",
+            &format!("Cannot change the CPU affinity of {}: {}", info.name, reason),
+        ));
+    }
+
+    crate::surgery::set_cpu_affinity(pid, &cores).map_err(|e| affinity_app_error(pid, e))
+}
+
+fn affinity_app_error(pid: u32, e: std::io::Error) -> AppError {
+    use std::io::ErrorKind;
+
+    match e.kind() {
+        ErrorKind::PermissionDenied => AppError::with_details(
+            ErrorCode::AccessDenied,
+            "Insufficient privileges",
+            &e.to_string(),
+        ),
+        ErrorKind::NotFound => AppError::process_not_found(pid),
+        ErrorKind::Unsupported => AppError::new(ErrorCode::NotSupported, &e.to_string()),
+        ErrorKind::InvalidInput => AppError::new(ErrorCode::InvalidInput, &e.to_string()),
+        _ => AppError::new(ErrorCode::AffinityError, &e.to_string()),
+    }
+}
+
+/// Pause a process with SIGSTOP so it can be resumed later instead of killed
+///
+/// Subject to the same safety checks as termination. Not supported on Windows.
+#[tauri::command]
+pub async fn suspend_process(
+    state: State<'_, AppStateManager>,
+    pid: u32,
+) -> Result<KillResult, AppError> {
+    log::info!("Suspend request for PID {}", pid);
+    ensure_stop_signals_supported()?;
+
+    let result = state
+        .terminator
+        .write()
+        .await
+        .suspend(pid)
+        .map_err(|e| AppError::new(ErrorCode::KillError, &e.to_string()))?;
+
+    if result.success {
+        state.suspended.write().await.insert(pid);
+    }
+    Ok(result)
+}
+
+/// Continue a process paused by `suspend_process` with SIGCONT
+#[tauri::command]
+pub async fn resume_process(
+    state: State<'_, AppStateManager>,
+    pid: u32,
+) -> Result<KillResult, AppError> {
+    log::info!("Resume request for PID {}", pid);
+    ensure_stop_signals_supported()?;
+
+    let result = state
+        .terminator
+        .write()
+        .await
+        .resume(pid)
+        .map_err(|e| AppError::new(ErrorCode::KillError, &e.to_string()))?;
+
+    if result.success {
+        state.suspended.write().await.remove(&pid);
+    }
+    Ok(result)
+}
+
+/// Ask a daemon to reload its configuration by sending SIGHUP
+///
+/// Subject to the process safety checks. `still_running` tells whether the process
+/// survived the signal, since programs without a SIGHUP handler exit on it. Not
+/// supported on Windows.
+#[tauri::command]
+pub async fn reload_process(
+    state: State<'_, AppStateManager>,
+    pid: u32,
+) -> Result<ReloadResult, AppError> {
+    log::info!("Reload request for PID {}", pid);
+    if cfg!(windows) {
+        return Err(AppError::new(
+            ErrorCode::NotSupported,
+            "Reloading a process with SIGHUP is not supported on Windows",
+        ));
+    }
+
+    ProcessTerminator::reload(&state.terminator, pid)
+        .await
+        .map_err(|e| AppError::new(ErrorCode::KillError, &e.to_string()))
+}
+
+/// SIGSTOP/SIGCONT have no Windows equivalent
+fn ensure_stop_signals_supported() -> Result<(), AppError> {
+    if cfg!(windows) {
+        return Err(AppError::new(
+            ErrorCode::NotSupported,
+            "Suspending and resuming processes is not supported on Windows",
+        ));
+    }
+    Ok(())
+}
+
+/// Kill every process in the process group of a PID (or its process tree on Windows)
+#[tauri::command]
+pub async fn kill_process_group(
+    state: State<'_, AppStateManager>,
+    pid: u32,
+    force: bool,
+) -> Result<GroupKillResult, AppError> {
+    log::info!("Group kill request for PID {} (force: {})", pid, force);
+    check_force_allowed(&state, force).await?;
+
+    let mut terminator = state.terminator.write().await;
+    terminator
+        .terminate_group(pid, force)
+        .map_err(|e| AppError::new(ErrorCode::KillError, &e.to_string()))
+}
+
+/// Kill a process and all of its descendants, children first, one result per process
+///
+/// Unlike `kill_process_group` this follows the observed parent tree, so it reaches
+/// children that moved to their own process group and behaves the same on Windows.
+/// A protected root, or a tree containing Process Surgeon, is refused as a whole.
+#[tauri::command]
+pub async fn kill_tree(
+    state: State<'_, AppStateManager>,
+    pid: u32,
+    force: bool,
+) -> Result<Vec<KillResult>, AppError> {
+    log::info!("Tree kill request for PID {} (force: {})", pid, force);
+    let allow_elevation = check_force_allowed(&state, force).await?;
+
+    let mut terminator = state.terminator.write().await;
+    let listeners = prepare_batch(&mut terminator, force)?;
+    let targets = match terminator.tree_targets(pid, force, &listeners) {
+        Ok(targets) => targets,
+        Err(refusal) => return Ok(vec![refusal]),
+    };
+
+    // The targets were read from the table just refreshed, so there is no name to recheck
+    Ok(kill_many_with_elevation(
+        &mut terminator,
+        &targets,
+        &HashMap::new(),
+        force,
+        allow_elevation,
+        &listeners,
+    ))
+}
+
+/// Assess how risky it would be to kill a process
+///
+/// Advisory only: the frontend uses it to ask for extra confirmation on high-risk kills.
+#[tauri::command]
+pub async fn assess_kill_risk(
+    state: State<'_, AppStateManager>,
+    pid: u32,
+) -> Result<KillRisk, AppError> {
+    let info = refreshed_process_info(&state, pid)
+        .await
+        .ok_or_else(|| AppError::process_not_found(pid))?;
+
+    let (owned_by_current_user, child_count) = {
+        let mut terminator = state.terminator.write().await;
+        terminator.refresh();
+        (
+            terminator.is_owned_by_current_user(pid),
+            terminator.children_of(pid).len(),
+        )
+    };
+
+    let listening_ports = match scan_listening_ports() {
+        Ok(ports) => ports
+            .into_iter()
+            .filter(|p| p.pids.contains(&pid))
+            .map(|p| p.local_port)
+            .collect(),
+        Err(e) => {
+            log::debug!("Port scan for risk assessment failed: {}", e);
+            Vec::new()
+        }
+    };
+
+    let factors = RiskFactors {
+        user: info.user,
+        owned_by_current_user,
+        child_count,
+        listening_ports,
+    };
+
+    Ok(assess_risk(pid, &factors))
+}
+
+/// Explain why a process is or is not protected, including the rule that matched
+///
+/// Checks the same name, PID and self rules as a kill, plus the ports the PID is
+/// currently listening on, as an unforced kill would.
+#[tauri::command]
+pub async fn explain_protection(pid: u32, name: String) -> Result<ProtectionExplanation, AppError> {
+    let ports: Vec<u16> = tokio::task::spawn_blocking(scan_listening_ports)
+        .await
+        .map_err(anyhow::Error::from)
+        .and_then(|result| result)
+        .map(|listeners| {
+            listeners
+                .into_iter()
+                .filter(|p| p.pids.contains(&pid))
+                .map(|p| p.local_port)
+                .collect()
+        })
+        .unwrap_or_else(|e| {
+            log::debug!("Port scan for protection check failed: {}", e);
+            Vec::new()
+        });
+
+    Ok(check_process_safety_with_ports(pid, &name, &ports).explanation(pid))
+}
+
+/// Preview a kill without sending any signal
+#[tauri::command]
+pub async fn simulate_kill(
+    state: State<'_, AppStateManager>,
+    pid: u32,
+    force: bool,
 ) -> Result<KillResult, AppError> {
-    log::info!("Kill request for PID {} (force: {})", pid, force);
-    
+    log::debug!("Simulated kill request for PID {} (force: {})", pid, force);
+
     let mut terminator = state.terminator.write().await;
+    Ok(terminator.simulate(pid, force))
+}
+
+/// How long a process gets to exit after SIGTERM before a restart escalates to SIGKILL
+const RESTART_GRACE_PERIOD: Duration = Duration::from_secs(10);
+
+/// Restart a process by terminating it gracefully and relaunching the same command line
+///
+/// Returns the PID of the relaunched process.
+#[tauri::command]
+pub async fn restart_process(
+    state: State<'_, AppStateManager>,
+    pid: u32,
+) -> Result<u32, AppError> {
+    log::info!("Restart request for PID {}", pid);
+
+    // The terminator is only locked while signalling, not while waiting for the exit
+    let (spec, result) = {
+        let mut terminator = state.terminator.write().await;
+
+        let spec = terminator
+            .launch_spec(pid)
+            .ok_or_else(|| AppError::process_not_found(pid))?;
+
+        if !check_process_safety(pid, &spec.name).is_safe() {
+            return Err(AppError::safety_violation(&spec.name));
+        }
+
+        if spec.exe.is_none() {
+            return Err(AppError::with_details(
+                ErrorCode::RestartUnavailable,
+                "Cannot restart process",
+                &format!("Executable path for PID {} ({}) is unknown", pid, spec.name),
+            ));
+        }
+
+        let result = terminator
+            .terminate(pid, false)
+            .map_err(|e| AppError::new(ErrorCode::KillError, &e.to_string()))?;
+        (spec, result)
+    };
+    if !result.success {
+        return Err(AppError::with_details(
+            ErrorCode::KillError,
+            "Failed to stop process before restart",
+            &result.message,
+        ));
+    }
+
+    if !wait_for_pid_exit(pid, RESTART_GRACE_PERIOD).await {
+        if *state.safe_mode.read().await {
+            return Err(AppError::with_details(
+                ErrorCode::SafeModeBlocked,
+                "Blocked by safe mode",
+                &format!(
+                    "PID {} did not exit after SIGTERM and force killing is not allowed \
+                     while safe mode is on; not relaunching",
+                    pid
+                ),
+            ));
+        }
+        log::warn!("Process {} did not exit gracefully, forcing termination", pid);
+        let forced = state
+            .terminator
+            .write()
+            .await
+            .terminate_expecting(pid, true, Some(&spec.name));
+        match forced {
+            // It may also have exited just before the SIGKILL
+            Ok(result) if result.success || live_pids(&[pid]).is_empty() => {}
+            Ok(result) => {
+                return Err(AppError::with_details(
+                    ErrorCode::KillError,
+                    "Failed to force-stop process before restart",
+                    &result.message,
+                ));
+            }
+            Err(e) if e.is::<PidReused>() => {
+                return Err(AppError::new(ErrorCode::PidReused, &e.to_string()));
+            }
+            Err(e) => return Err(AppError::new(ErrorCode::KillError, &e.to_string())),
+        }
+
+        if !wait_for_pid_exit(pid, Duration::from_secs(2)).await {
+            return Err(AppError::with_details(
+                ErrorCode::RestartTimeout,
+                "Process did not exit",
+                &format!("PID {} is still running; not relaunching", pid),
+            ));
+        }
+    }
+
+    let new_pid = spec.spawn().map_err(|e| {
+        AppError::with_details(ErrorCode::SpawnError, "Failed to relaunch process", &e.to_string())
+    })?;
+
+    log::info!("Restarted {} (PID {} -> {})", spec.name, pid, new_pid);
+    Ok(new_pid)
+}
+
+/// Execute a container action (stop, kill, remove) on a container id or name
+///
+/// `Stop` honours the request's `timeout_secs` (default 10): Docker sends SIGTERM and
+/// then SIGKILL once the timeout expires.
+#[tauri::command]
+pub async fn container_action(
+    state: State<'_, AppStateManager>,
+    request: ContainerActionRequest,
+) -> Result<KillResult, AppError> {
+    let ContainerActionRequest {
+        container_id,
+        action,
+        timeout_secs,
+    } = request;
+    log::info!("Container action {:?} for {}", action, container_id);
+    check_container_action_allowed(&state, &action).await?;
+
+    let docker = state.docker.read().await;
     
-    match terminator.terminate(pid, force) {
-        Ok(result) => {
-            if !result.success && result.required_elevation {
-                log::info!("Requesting elevated termination for PID {}", pid);
-                // Try elevated termination
-                match request_elevated_termination(pid, force) {
-                    Ok(elevated_result) => Ok(elevated_result),
-                    Err(e) => Ok(KillResult {
-                        success: false,
-                        message: format!("Elevated termination failed: {}", e),
-                        required_elevation: true,
-                    }),
-                }
-            } else {
-                Ok(result)
+    if !docker.is_available() {
+        return Err(AppError::new(ErrorCode::DockerUnavailable, "Docker is not available"));
+    }
+
+    // Accept either an id or a container name
+    let container_id = docker
+        .resolve_container(&container_id)
+        .await
+        .map_err(|e| docker_app_error(&e))?;
+
+    let stop_timeout_secs = timeout_secs.unwrap_or(DEFAULT_STOP_TIMEOUT_SECS);
+    let outcome = docker
+        .execute_action(&container_id, action.clone(), stop_timeout_secs)
+        .await;
+    container_action_result(&container_id, &action, outcome)
+}
+
+/// Recreate the container removed most recently, if within `RECREATE_WINDOW`
+///
+/// The new container gets the old name, image, environment, ports and mounts, but a
+/// new id. Anything that could not be restored is listed in the result's warnings.
+#[tauri::command]
+pub async fn recreate_last_removed(
+    state: State<'_, AppStateManager>,
+) -> Result<RecreateResult, AppError> {
+    let docker = state.docker.read().await;
+    if !docker.is_available() {
+        return Err(AppError::new(ErrorCode::DockerUnavailable, "Docker is not available"));
+    }
+
+    let result = docker
+        .recreate_last_removed()
+        .await
+        .map_err(|e| docker_app_error(&e))?
+        .ok_or_else(|| {
+            AppError::new(
+                ErrorCode::NotFound,
+                &format!(
+                    "No container was removed in the last {} minutes",
+                    RECREATE_WINDOW.as_secs() / 60
+                ),
+            )
+        })?;
+
+    for warning in &result.warnings {
+        log::warn!("Recreated {}: {}", result.name, warning);
+    }
+    Ok(result)
+}
+
+/// Map a Docker failure to `DockerError`, or `DockerVersionMismatch` with advice
+fn docker_app_error(e: &anyhow::Error) -> AppError {
+    if is_version_mismatch(e) {
+        return AppError::with_details(
+            ErrorCode::DockerVersionMismatch,
+            "Docker API version mismatch",
+            &format!(
+                "{:#}. The engine does not support the API version this app speaks; \
+                 updating Docker Desktop, Colima or the Docker engine usually fixes this.",
+                e
+            ),
+        );
+    }
+    AppError::new(ErrorCode::DockerError, &e.to_string())
+}
+
+/// Refuse container kill and remove in safe mode; stop and restart stay allowed
+async fn check_container_action_allowed(
+    state: &AppStateManager,
+    action: &ContainerAction,
+) -> Result<(), AppError> {
+    let operation = match action {
+        ContainerAction::Kill => "Killing a container",
+        ContainerAction::Remove => "Removing a container",
+        ContainerAction::Stop | ContainerAction::Restart => return Ok(()),
+    };
+    if *state.safe_mode.read().await {
+        return Err(AppError::safe_mode_blocked(operation));
+    }
+    Ok(())
+}
+
+/// Apply a container action to every container in a Compose project
+///
+/// Containers are handled one at a time in listing order; a failure is reported in
+/// that container's result and does not stop the rest.
+#[tauri::command]
+pub async fn compose_action(
+    state: State<'_, AppStateManager>,
+    project: String,
+    action: ContainerAction,
+) -> Result<Vec<KillResult>, AppError> {
+    log::info!("Compose action {:?} for project {}", action, project);
+    check_container_action_allowed(&state, &action).await?;
+
+    let docker = state.docker.read().await;
+
+    if !docker.is_available() {
+        return Err(AppError::new(ErrorCode::DockerUnavailable, "Docker is not available"));
+    }
+
+    let containers: Vec<ContainerInfo> = docker
+        .get_all_containers()
+        .await
+        .map_err(|e| docker_app_error(&e))?
+        .into_iter()
+        .filter(|c| c.compose_project.as_deref() == Some(project.as_str()))
+        .collect();
+
+    if containers.is_empty() {
+        return Err(AppError::new(
+            ErrorCode::NotFound,
+            &format!("No containers found for Compose project {}", project),
+        ));
+    }
+
+    let mut results = Vec::with_capacity(containers.len());
+    for container in containers {
+        let outcome = docker
+            .execute_action(&container.id, action.clone(), DEFAULT_STOP_TIMEOUT_SECS)
+            .await;
+        results.push(batch_action_result(&container.name, &action, outcome));
+    }
+
+    Ok(results)
+}
+
+/// Run several container actions in the given order, e.g. stop an app before its DB
+///
+/// With `continue_on_error` unset the batch stops at the first failed entry, whose
+/// result is the last one returned. Safe mode is checked for every entry before
+/// anything runs.
+#[tauri::command]
+pub async fn container_action_batch(
+    state: State<'_, AppStateManager>,
+    actions: Vec<ContainerActionRequest>,
+    continue_on_error: bool,
+) -> Result<Vec<KillResult>, AppError> {
+    log::info!(
+        "Container action batch of {} (continue on error: {})",
+        actions.len(),
+        continue_on_error
+    );
+    for request in &actions {
+        check_container_action_allowed(&state, &request.action).await?;
+    }
+
+    let docker = state.docker.read().await;
+
+    if !docker.is_available() {
+        return Err(AppError::new(ErrorCode::DockerUnavailable, "Docker is not available"));
+    }
+
+    let mut results = Vec::with_capacity(actions.len());
+    for request in actions {
+        let stop_timeout_secs = request.timeout_secs.unwrap_or(DEFAULT_STOP_TIMEOUT_SECS);
+        let outcome = match docker.resolve_container(&request.container_id).await {
+            Ok(container_id) => {
+                docker
+                    .execute_action(&container_id, request.action.clone(), stop_timeout_secs)
+                    .await
             }
+            Err(e) => Err(e),
+        };
+        let result = batch_action_result(&request.container_id, &request.action, outcome);
+        let failed = !result.success;
+        results.push(result);
+        if failed && !continue_on_error {
+            break;
         }
-        Err(e) => Err(AppError::new("KILL_ERROR", &e.to_string())),
     }
+
+    Ok(results)
+}
+
+/// Apply a container action to whichever container publishes a host port
+///
+/// Frees a port without knowing whether a process or a container holds it. When
+/// several containers publish the port on different host IPs, nothing is done and an
+/// `AMBIGUOUS_TARGET` error lists their ids for use with `container_action`.
+#[tauri::command]
+pub async fn stop_container_by_port(
+    state: State<'_, AppStateManager>,
+    port: u16,
+    action: ContainerAction,
+) -> Result<KillResult, AppError> {
+    log::info!("Container action {:?} for host port {}", action, port);
+    check_container_action_allowed(&state, &action).await?;
+
+    let docker = state.docker.read().await;
+
+    if !docker.is_available() {
+        return Err(AppError::new(ErrorCode::DockerUnavailable, "Docker is not available"));
+    }
+    docker.refresh().await.map_err(|e| docker_app_error(&e))?;
+
+    let container = match docker.containers_for_port(port).await.as_slice() {
+        [] => {
+            return Err(AppError::new(
+                ErrorCode::NotFound,
+                &format!("No running container publishes port {}", port),
+            ))
+        }
+        [container] => container.clone(),
+        containers => {
+            let ids: Vec<String> = containers
+                .iter()
+                .map(|c| format!("{} ({})", c.id, c.name))
+                .collect();
+            return Err(AppError::with_details(
+                ErrorCode::AmbiguousTarget,
+                &format!("{} containers publish port {}", containers.len(), port),
+                &ids.join(", "),
+            ));
+        }
+    };
+
+    let outcome = docker
+        .execute_action(&container.id, action.clone(), DEFAULT_STOP_TIMEOUT_SECS)
+        .await;
+    container_action_result(&container.name, &action, outcome)
+}
+
+/// Describe the outcome of one action on the container called `label`
+///
+/// Failures become an unsuccessful result, except an API version mismatch, which
+/// would fail every later action too and is returned as `DOCKER_VERSION_MISMATCH`.
+fn container_action_result(
+    label: &str,
+    action: &ContainerAction,
+    outcome: anyhow::Result<ActionStatus>,
+) -> Result<KillResult, AppError> {
+    Ok(match outcome {
+        Ok(ActionStatus::Completed) => KillResult {
+            success: true,
+            message: format!("Container {} action {:?} completed", label, action),
+            required_elevation: false,
+        },
+        Ok(ActionStatus::AlreadyInProgress) => KillResult {
+            success: true,
+            message: format!("Container {} action {:?} already in progress", label, action),
+            required_elevation: false,
+        },
+        Err(e) if is_version_mismatch(&e) => return Err(docker_app_error(&e)),
+        Err(e) => KillResult {
+            success: false,
+            message: format!("Container {} action failed: {}", label, e),
+            required_elevation: false,
+        },
+    })
+}
+
+/// `container_action_result` for one item of several, where every outcome is a result
+///
+/// A version mismatch becomes an unsuccessful result like any other failure, so the
+/// results already collected for earlier items are still returned.
+fn batch_action_result(
+    label: &str,
+    action: &ContainerAction,
+    outcome: anyhow::Result<ActionStatus>,
+) -> KillResult {
+    container_action_result(label, action, outcome).unwrap_or_else(|e| KillResult {
+        success: false,
+        message: match e.details {
+            Some(details) => format!("Container {} action failed: {}", label, details),
+            None => format!("Container {} action failed: {}", label, e.message),
+        },
+        required_elevation: false,
+    })
 }
 
-/// Execute a container action (stop, kill, remove)
+/// Sample CPU and memory usage for a container, identified by id or name
 #[tauri::command]
-pub async fn container_action(
+pub async fn container_stats(
     state: State<'_, AppStateManager>,
     container_id: String,
-    action: ContainerAction,
-) -> Result<KillResult, AppError> {
-    log::info!("Container action {:?} for {}", action, container_id);
-    
+) -> Result<ContainerStats, AppError> {
     let docker = state.docker.read().await;
-    
+
     if !docker.is_available() {
-        return Err(AppError::new("DOCKER_UNAVAILABLE", "Docker is not available"));
+        return Err(AppError::new(ErrorCode::DockerUnavailable, "Docker is not available"));
     }
-    
-    match docker.execute_action(&container_id, action.clone()).await {
-        Ok(_) => Ok(KillResult {
-            success: true,
-            message: format!("Container {} action {:?} completed", container_id, action),
-            required_elevation: false,
-        }),
-        Err(e) => Ok(KillResult {
-            success: false,
-            message: format!("Container action failed: {}", e),
-            required_elevation: false,
-        }),
+
+    let container_id = docker
+        .resolve_container(&container_id)
+        .await
+        .map_err(|e| docker_app_error(&e))?;
+
+    docker
+        .container_stats(&container_id)
+        .await
+        .map_err(|e| docker_app_error(&e))
+}
+
+/// Host processes connected to a container's published ports
+///
+/// These are the clients that stopping or killing the container would cut off. Only
+/// connections made through a published host port are visible; peers on the
+/// container's own Docker network talk to it directly. Ports are narrowed to the
+/// matching connections. Read-only: nothing is signalled.
+#[tauri::command]
+pub async fn container_dependents(
+    state: State<'_, AppStateManager>,
+    container_id: String,
+) -> Result<Vec<ProcessNode>, AppError> {
+    let published: HashSet<u16> = {
+        let docker = state.docker.read().await;
+        if !docker.is_available() {
+            return Err(AppError::new(ErrorCode::DockerUnavailable, "Docker is not available"));
+        }
+        if !docker.can_correlate_local_ports() {
+            return Err(AppError::new(
+                ErrorCode::NotSupported,
+                "Host clients can only be found for a local Docker engine",
+            ));
+        }
+
+        let container_id = docker
+            .resolve_container(&container_id)
+            .await
+            .map_err(|e| docker_app_error(&e))?;
+        let containers = docker
+            .get_all_containers()
+            .await
+            .map_err(|e| docker_app_error(&e))?;
+        let container = containers
+            .into_iter()
+            .find(|container| container.id == container_id)
+            .ok_or_else(|| {
+                AppError::new(
+                    ErrorCode::NotFound,
+                    &format!("Container {} not found", container_id),
+                )
+            })?;
+        container.ports.iter().map(|port| port.host_port).collect()
+    };
+    if published.is_empty() {
+        return Ok(Vec::new());
     }
+
+    let options = ScanOptions {
+        show_all_connections: true,
+        ..Default::default()
+    };
+    let app_state = build_app_state(&state, options).await?;
+    let host_addresses: HashSet<String> = app_state
+        .processes
+        .iter()
+        .flat_map(|node| node.ports.iter().map(|port| port.local_address.clone()))
+        .collect();
+
+    Ok(app_state
+        .processes
+        .into_iter()
+        .filter(|node| !node.is_docker_proxy)
+        .filter_map(|mut node| {
+            node.ports
+                .retain(|port| connects_to_published_port(port, &published, &host_addresses));
+            (!node.ports.is_empty()).then_some(node)
+        })
+        .collect())
+}
+
+/// Whether a socket is an established connection to one of `published` on this host
+///
+/// The remote end counts as this host when it is loopback, the socket's own address,
+/// or an address some other local socket is bound to.
+fn connects_to_published_port(
+    port: &PortEntry,
+    published: &HashSet<u16>,
+    host_addresses: &HashSet<String>,
+) -> bool {
+    let (Some(remote_address), Some(remote_port)) = (&port.remote_address, port.remote_port)
+    else {
+        return false;
+    };
+    port.state == SocketState::Established
+        && published.contains(&remote_port)
+        && (remote_address
+            .parse::<IpAddr>()
+            .is_ok_and(|ip| ip.to_canonical().is_loopback())
+            || *remote_address == port.local_address
+            || host_addresses.contains(remote_address))
+}
+
+/// Published container ports that a non-Docker host process also listens on
+///
+/// Explains "port is already allocated" when a container starts, so stopped and created
+/// containers are checked against the bindings they will claim. Only a local engine
+/// shares ports with this host, so a remote one is reported as not supported.
+#[tauri::command]
+pub async fn container_host_port_conflicts(
+    state: State<'_, AppStateManager>,
+) -> Result<Vec<HostPortConflict>, AppError> {
+    let containers = {
+        let docker = state.docker.read().await;
+        if !docker.is_available() {
+            return Err(AppError::new(ErrorCode::DockerUnavailable, "Docker is not available"));
+        }
+        if !docker.can_correlate_local_ports() {
+            return Err(AppError::new(
+                ErrorCode::NotSupported,
+                "Port conflicts can only be checked for a local Docker engine",
+            ));
+        }
+        docker
+            .containers_with_port_bindings()
+            .await
+            .map_err(|e| docker_app_error(&e))?
+    };
+
+    let app_state = build_app_state(&state, ScanOptions::default()).await?;
+    Ok(host_port_conflicts(&containers, &app_state.processes))
 }
 
 /// Get Docker containers
@@ -335,7 +4391,87 @@ pub async fn get_containers(
     docker
         .get_all_containers()
         .await
-        .map_err(|e| AppError::new("DOCKER_ERROR", &e.to_string()))
+        .map_err(|e| docker_app_error(&e))
+}
+
+/// Re-list containers and rebuild the port-to-container map without a process scan
+///
+/// For refreshing the container panel on its own, e.g. after a `docker run`. Empty
+/// when Docker is not available, like `get_containers`.
+#[tauri::command]
+pub async fn refresh_docker(
+    state: State<'_, AppStateManager>,
+) -> Result<Vec<ContainerInfo>, AppError> {
+    let docker = state.docker.read().await;
+
+    if !docker.is_available() {
+        return Ok(vec![]);
+    }
+
+    docker.refresh().await.map_err(|e| docker_app_error(&e))?;
+    docker
+        .get_all_containers()
+        .await
+        .map_err(|e| docker_app_error(&e))
+}
+
+/// List containers in one state (e.g. `running`, `exited`, `paused`), sorted server-side
+///
+/// The state filter is case-insensitive; a missing or empty one keeps every container.
+#[tauri::command]
+pub async fn get_containers_filtered(
+    state: State<'_, AppStateManager>,
+    state_filter: Option<String>,
+    sort_by: ContainerSortKey,
+) -> Result<Vec<ContainerInfo>, AppError> {
+    let containers = get_containers(state).await?;
+    Ok(filter_and_sort_containers(containers, state_filter.as_deref(), sort_by))
+}
+
+fn filter_and_sort_containers(
+    mut containers: Vec<ContainerInfo>,
+    state_filter: Option<&str>,
+    sort_by: ContainerSortKey,
+) -> Vec<ContainerInfo> {
+    if let Some(wanted) = state_filter.map(str::trim).filter(|f| !f.is_empty()) {
+        containers.retain(|c| c.state.eq_ignore_ascii_case(wanted));
+    }
+
+    match sort_by {
+        ContainerSortKey::Name => containers.sort_by_cached_key(|c| c.name.to_lowercase()),
+        ContainerSortKey::State => {
+            containers.sort_by_cached_key(|c| (c.state.to_lowercase(), c.name.to_lowercase()))
+        }
+        ContainerSortKey::PortCount => containers.sort_by_cached_key(|c| {
+            (std::cmp::Reverse(c.ports.len()), c.name.to_lowercase())
+        }),
+    }
+    containers
+}
+
+/// Reconnect to Docker through an explicit socket path and refresh the port map
+///
+/// On failure the existing connection is kept and the error is returned.
+#[tauri::command]
+pub async fn set_docker_socket(
+    state: State<'_, AppStateManager>,
+    path: String,
+) -> Result<bool, AppError> {
+    let resolver = DockerResolver::connect_socket(&path).await.map_err(|e| {
+        AppError::with_details(
+            ErrorCode::DockerUnavailable,
+            "Failed to connect to Docker socket",
+            &format!("{:#}", e),
+        )
+    })?;
+
+    if let Err(e) = resolver.refresh().await {
+        log::warn!("Failed to refresh Docker port map: {}", e);
+    }
+
+    let mut docker = state.docker.write().await;
+    *docker = resolver;
+    Ok(docker.is_available())
 }
 
 /// Check if Docker is available
@@ -344,3 +4480,838 @@ pub async fn is_docker_available(state: State<'_, AppStateManager>) -> Result<bo
     let docker = state.docker.read().await;
     Ok(docker.is_available())
 }
+
+/// Explain why Docker is or isn't available
+///
+/// Lists each endpoint tried with its error, plus the runtime and server version
+/// when an engine responds.
+#[tauri::command]
+pub async fn docker_diagnostics(
+    state: State<'_, AppStateManager>,
+) -> Result<DockerDiagnostics, AppError> {
+    let docker = state.docker.read().await;
+    Ok(docker.diagnostics().await)
+}
+
+/// Container names and host ports claimed on more than one local engine
+///
+/// Checks every engine reachable on a well-known local socket, so Docker and Podman
+/// running side by side are compared even though only one is used for actions. Run it
+/// before acting on a container by name. Empty when a single engine responds.
+#[tauri::command]
+pub async fn list_runtime_conflicts() -> Result<Vec<RuntimeConflict>, AppError> {
+    let engines = DockerResolver::local_engines().await;
+    log::debug!("Checking {} local engines for conflicts", engines.len());
+    Ok(runtime_conflicts(&engines))
+}
+
+/// Recent backend log entries, oldest first
+///
+/// Only entries at `level_filter` or more severe are returned (default: all buffered).
+#[tauri::command]
+pub async fn get_recent_logs(
+    logs: State<'_, LogBuffer>,
+    level_filter: Option<LogLevel>,
+) -> Result<Vec<LogEntry>, AppError> {
+    Ok(logs.recent(level_filter.unwrap_or(LogLevel::Trace)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{self, listener, port_entry, process_info, process_node, published_port};
+
+    fn node(pid: u32, exe_path: Option<&str>, port: u16) -> ProcessNode {
+        ProcessNode {
+            id: format!("{}-{}", pid, port),
+            exe_path: exe_path.map(|p| p.to_string()),
+            memory_usage: 100,
+            cpu_usage: 1.0,
+            ports: vec![port_entry("0.0.0.0", port)],
+            ..process_node(pid, "worker")
+        }
+    }
+
+    #[test]
+    fn test_group_by_executable_merges_workers() {
+        let groups = group_by_executable(vec![
+            node(12, Some("/usr/sbin/nginx"), 80),
+            node(10, Some("/usr/sbin/nginx"), 80),
+            node(11, Some("/usr/sbin/nginx"), 443),
+            node(20, Some("/usr/bin/node"), 3000),
+        ]);
+
+        assert_eq!(groups.len(), 2);
+        let nginx = &groups[0];
+        assert_eq!(nginx.node.pid, 10);
+        assert_eq!(nginx.worker_pids, vec![10, 11, 12]);
+        assert_eq!(nginx.node.memory_usage, 300);
+        assert_eq!(nginx.node.ports.len(), 2);
+        assert_eq!(groups[1].worker_pids, vec![20]);
+    }
+
+    #[test]
+    fn test_truncate_processes() {
+        let mut state = app_state((1..=5).map(|pid| node(pid, None, 8000)).collect());
+        truncate_processes(&mut state, 10);
+        assert!(!state.truncated);
+        assert_eq!(state.processes.len(), 5);
+
+        truncate_processes(&mut state, 3);
+        assert!(state.truncated);
+        assert_eq!(state.processes.len(), 3);
+        assert_eq!(state.total_processes, 5);
+    }
+
+    #[test]
+    fn test_asn_matches() {
+        let amazon = AsnInfo {
+            asn: 16509,
+            org: "AMAZON-02".to_string(),
+            country: Some("US".to_string()),
+        };
+        assert!(asn_matches(&amazon, "amazon"));
+        assert!(asn_matches(&amazon, "16509"));
+        assert!(asn_matches(&amazon, "as16509"));
+        assert!(!asn_matches(&amazon, "as1650"));
+        assert!(!asn_matches(&amazon, "google"));
+    }
+
+    #[test]
+    fn test_process_node_id() {
+        let a = node(10, None, 8080);
+        assert_eq!(process_node_id(a.pid, &a.ports), "10-8080");
+
+        let ids: HashSet<String> =
+            [11, 12].iter().map(|&pid| process_node_id(pid, &[])).collect();
+        assert_eq!(ids, HashSet::from(["11".to_string(), "12".to_string()]));
+    }
+
+    #[test]
+    fn test_sort_processes() {
+        let mut a = node(3, None, 8080);
+        a.name = "zeta".to_string();
+        a.memory_usage = 10;
+        a.cpu_usage = 5.0;
+        let mut b = node(1, None, 443);
+        b.name = "Alpha".to_string();
+        b.memory_usage = 30;
+        b.cpu_usage = 1.0;
+        let mut c = node(2, None, 22);
+        c.name = "beta".to_string();
+        c.memory_usage = 20;
+        c.cpu_usage = 9.0;
+        c.ports.clear();
+
+        let mut processes = vec![a, b, c];
+        let pids = |p: &[ProcessNode]| p.iter().map(|n| n.pid).collect::<Vec<_>>();
+
+        sort_processes(&mut processes, SortKey::Pid);
+        assert_eq!(pids(&processes), vec![1, 2, 3]);
+        sort_processes(&mut processes, SortKey::Name);
+        assert_eq!(pids(&processes), vec![1, 2, 3]);
+        sort_processes(&mut processes, SortKey::Memory);
+        assert_eq!(pids(&processes), vec![1, 2, 3]);
+        sort_processes(&mut processes, SortKey::Cpu);
+        assert_eq!(pids(&processes), vec![2, 3, 1]);
+        sort_processes(&mut processes, SortKey::Port);
+        assert_eq!(pids(&processes), vec![1, 3, 2]);
+    }
+
+    #[test]
+    fn test_socket_node_id() {
+        let v4 = listener("0.0.0.0", 80, 7);
+        let v6 = listener("::", 80, 7);
+        assert_eq!(socket_node_id(7, &v4), "7-tcp:80/0.0.0.0");
+        assert_ne!(socket_node_id(7, &v4), socket_node_id(7, &v6));
+
+        let udp = PortInfo {
+            protocol: Protocol::UDP,
+            ..listener("0.0.0.0", 80, 7)
+        };
+        assert_ne!(socket_node_id(7, &v4), socket_node_id(7, &udp));
+
+        let accepted = PortInfo {
+            remote_address: Some("10.0.0.2".to_string()),
+            remote_port: Some(51000),
+            state: SocketState::Established,
+            ..listener("0.0.0.0", 80, 7)
+        };
+        assert_eq!(socket_node_id(7, &accepted), "7-tcp:80/0.0.0.0->10.0.0.2:51000");
+    }
+
+    #[test]
+    fn test_respawn_candidates() {
+        let process = |pid: u32, name: &str, exe_path: Option<&str>| ProcessInfo {
+            exe_path: exe_path.map(|p| p.to_string()),
+            ..process_info(pid, name)
+        };
+        let processes = vec![
+            process(50, "vite", Some("/usr/bin/node")),
+            process(51, "node", Some("/usr/bin/node")),
+            process(52, "postgres", Some("/usr/lib/postgresql/bin/postgres")),
+            process(53, "node", Some("/usr/bin/node")),
+        ];
+        let listeners = vec![listener("127.0.0.1", 5173, 50), listener("0.0.0.0", 5432, 52)];
+        let watched = HashSet::from([5173]);
+        // 51 was already running when the target was killed
+        let preexisting = HashSet::from([(51, None)]);
+
+        let candidates = respawn_candidates(
+            &listeners,
+            &processes,
+            &preexisting,
+            &watched,
+            Some("/usr/bin/node"),
+        );
+        let pids: Vec<u32> = candidates.iter().map(|(pid, _, _)| *pid).collect();
+        assert_eq!(pids, vec![50, 53]);
+        assert!(!pids.contains(&51));
+        assert_eq!(candidates[0].2, "listening on watched port 5173");
+        assert_eq!(candidates[1].1, "node");
+
+        let (no_processes, no_ports) = (HashSet::new(), HashSet::new());
+        assert!(
+            respawn_candidates(&listeners, &processes, &no_processes, &no_ports, None).is_empty()
+        );
+    }
+
+    #[test]
+    fn test_port_exposure() {
+        assert_eq!(port_exposure(&[]), None);
+
+        let loopback = vec![listener("127.0.0.1", 5432, 1), listener("::1", 5432, 1)];
+        assert_eq!(port_exposure(&loopback), Some(Exposure::LoopbackOnly));
+
+        let mut wildcard = loopback.clone();
+        wildcard.push(listener("::", 5432, 2));
+        assert_eq!(port_exposure(&wildcard), Some(Exposure::External));
+
+        let specific = vec![
+            listener("192.168.1.20", 8080, 3),
+            listener("127.0.0.1", 8080, 3),
+            listener("10.0.0.5", 8080, 4),
+            listener("192.168.1.20", 8080, 4),
+        ];
+        assert_eq!(
+            port_exposure(&specific),
+            Some(Exposure::SpecificInterfaces(vec![
+                "10.0.0.5".to_string(),
+                "192.168.1.20".to_string(),
+            ]))
+        );
+
+        // A link-local listener is one specific interface, not all of them or loopback
+        let mut link_local = listener("fe80::1", 22, 5);
+        link_local.local_address = "fe80::1%eth0".to_string();
+        assert_eq!(
+            port_exposure(&[link_local]),
+            Some(Exposure::SpecificInterfaces(vec!["fe80::1%eth0".to_string()]))
+        );
+
+        // Established sockets on the port don't count as bindings
+        let mut accepted = listener("0.0.0.0", 8080, 3);
+        accepted.state = SocketState::Established;
+        assert_eq!(port_exposure(&[accepted]), None);
+    }
+
+    #[test]
+    fn test_filter_and_sort_containers() {
+        let container = |name: &str, state: &str, ports: usize| ContainerInfo {
+            state: state.to_string(),
+            ports: (0..ports).map(|i| published_port(8000 + i as u16, 80)).collect(),
+            ..fixtures::container(name, name)
+        };
+        let containers = vec![
+            container("web", "running", 1),
+            container("Cache", "exited", 0),
+            container("api", "running", 3),
+            container("db", "paused", 1),
+        ];
+        let names = |containers: Vec<ContainerInfo>| {
+            containers.into_iter().map(|c| c.name).collect::<Vec<_>>()
+        };
+
+        let running =
+            filter_and_sort_containers(containers.clone(), Some("Running"), ContainerSortKey::Name);
+        assert_eq!(names(running), vec!["api", "web"]);
+
+        let by_state =
+            filter_and_sort_containers(containers.clone(), None, ContainerSortKey::State);
+        assert_eq!(names(by_state), vec!["Cache", "db", "api", "web"]);
+
+        let by_ports =
+            filter_and_sort_containers(containers, Some(" "), ContainerSortKey::PortCount);
+        assert_eq!(names(by_ports), vec!["api", "db", "web", "Cache"]);
+    }
+
+    #[test]
+    fn test_port_owner_pids() {
+        assert!(port_owner_pids(&[]).is_empty());
+
+        let mut shared = listener("0.0.0.0", 8080, 7);
+        shared.pids = vec![9, 7];
+        let ports = vec![listener("::", 8080, 9), shared, listener("127.0.0.1", 8080, 3)];
+        assert_eq!(port_owner_pids(&ports), vec![3, 7, 9]);
+    }
+
+    #[test]
+    fn test_count_established_connections() {
+        let mut accepted = listener("10.0.0.2", 8080, 42);
+        accepted.state = SocketState::Established;
+        accepted.remote_address = Some("10.0.0.9".to_string());
+        accepted.remote_port = Some(51000);
+        let mut other_pid = accepted.clone();
+        other_pid.pids = vec![7];
+
+        let ports = vec![listener("0.0.0.0", 8080, 42), accepted.clone(), accepted, other_pid];
+        let counts = count_established_connections(&ports);
+
+        let tcp = |port| (Protocol::TCP, port, None);
+        assert_eq!(counts.get(&(42, tcp(8080))), Some(&2));
+        assert_eq!(counts.get(&(7, tcp(8080))), Some(&1));
+        assert_eq!(counts.get(&(42, tcp(9090))), None);
+    }
+
+    #[test]
+    fn test_connects_to_published_port() {
+        let client = |remote: &str, remote_port: u16| {
+            to_port_entry(
+                &PortInfo {
+                    state: SocketState::Established,
+                    remote_address: Some(remote.to_string()),
+                    remote_port: Some(remote_port),
+                    ..listener("192.168.1.5", 51000, 42)
+                },
+                &HashSet::new(),
+            )
+        };
+        let published = HashSet::from([8080]);
+        let host_addresses = HashSet::from(["10.8.0.2".to_string()]);
+        let connects = |port: &PortEntry| {
+            connects_to_published_port(port, &published, &host_addresses)
+        };
+
+        assert!(connects(&client("127.0.0.1", 8080)));
+        assert!(connects(&client("::ffff:127.0.0.1", 8080)));
+        assert!(connects(&client("192.168.1.5", 8080)));
+        assert!(connects(&client("10.8.0.2", 8080)));
+        // Same port number on another machine
+        assert!(!connects(&client("203.0.113.7", 8080)));
+        assert!(!connects(&client("127.0.0.1", 5432)));
+        assert!(!connects(&to_port_entry(&listener("0.0.0.0", 8080, 42), &HashSet::new())));
+    }
+
+    #[test]
+    fn test_port_entry_service_name() {
+        let connection = |local_port: u16, remote_port: u16| PortInfo {
+            state: SocketState::Established,
+            remote_address: Some("192.168.1.9".to_string()),
+            remote_port: Some(remote_port),
+            ..listener("192.168.1.5", local_port, 42)
+        };
+        let ports = vec![listener("0.0.0.0", 22, 42), connection(22, 51000)];
+        let listeners = listener_ports(&ports);
+        let service = |port: &PortInfo| to_port_entry(port, &listeners).service_name;
+
+        // Inbound connections are named after the listener that accepted them
+        assert_eq!(service(&ports[1]).as_deref(), Some("ssh"));
+        // Outbound connections are named after the port they connect to
+        assert_eq!(service(&connection(51000, 5432)).as_deref(), Some("postgresql"));
+        assert_eq!(service(&ports[0]).as_deref(), Some("ssh"));
+    }
+
+    #[test]
+    fn test_open_connection_count() {
+        let established = |port: u16, pid: u32| PortInfo {
+            state: SocketState::Established,
+            remote_address: Some("10.0.0.9".to_string()),
+            remote_port: Some(51000),
+            ..listener("10.0.0.2", port, pid)
+        };
+        let ports = vec![
+            listener("0.0.0.0", 8080, 42),
+            established(8080, 42),
+            established(8443, 42),
+            established(9000, 42),
+            established(8080, 7),
+        ];
+
+        assert_eq!(open_connection_count(&ports, 42, &[8080, 8443]), 2);
+        assert_eq!(open_connection_count(&ports, 7, &[8080]), 1);
+        assert_eq!(open_connection_count(&ports, 42, &[5432]), 0);
+    }
+
+    #[test]
+    fn test_count_ports() {
+        let mut udp = listener("::", 53, 7);
+        udp.protocol = Protocol::UDP;
+        let mut established = listener("10.0.0.2", 8080, 42);
+        established.state = SocketState::Established;
+
+        let ports = vec![
+            listener("0.0.0.0", 8080, 42),
+            listener("::1", 8080, 42),
+            udp,
+            established,
+        ];
+
+        assert_eq!(
+            count_ports(&ports),
+            PortCounts {
+                tcp_count: 3,
+                udp_count: 1,
+                established_count: 1,
+                ipv6_count: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn test_shared_listener_ports() {
+        let mut udp = listener("0.0.0.0", 8080, 7);
+        udp.protocol = Protocol::UDP;
+        // Two containers, each listening on 80 in its own namespace
+        let in_netns = |pid: u32, netns: &str| PortInfo {
+            netns: Some(netns.to_string()),
+            ..listener("0.0.0.0", 80, pid)
+        };
+        let ports = vec![
+            listener("0.0.0.0", 8080, 42),
+            listener("0.0.0.0", 8080, 43),
+            listener("0.0.0.0", 9090, 42),
+            listener("::", 9090, 42),
+            udp,
+            in_netns(50, "net:[4026532281]"),
+            in_netns(51, "net:[4026532282]"),
+        ];
+        let shared = shared_listener_ports(&ports);
+
+        assert!(shared.contains(&(Protocol::TCP, 8080, None)));
+        assert!(!shared.contains(&(Protocol::TCP, 9090, None)));
+        assert!(!shared.contains(&(Protocol::UDP, 8080, None)));
+        assert!(!shared.contains(&(Protocol::TCP, 80, Some("net:[4026532281]".to_string()))));
+    }
+
+    #[test]
+    fn test_inherited_sockets_after_fork() {
+        // A pre-fork server: 10 opened the socket and forked 11 and 12; 12 forked 13
+        let parents: HashMap<u32, u32> = [(10, 1), (11, 10), (12, 10), (13, 12)].into();
+        let parent_of = |pid: u32| parents.get(&pid).copied();
+
+        let mut forked = listener("0.0.0.0", 8080, 10);
+        forked.pids = vec![11, 10, 13, 12];
+        // SO_REUSEPORT siblings: 20 and 21 bound their own sockets, neither inherited
+        let mut reuseport = listener("0.0.0.0", 9090, 20);
+        reuseport.pids = vec![20, 21];
+        let parents_of_siblings = |pid: u32| parent_of(pid).or(Some(1));
+
+        let inherited = inherited_sockets(&[forked], parent_of);
+        let key = |pid: u32| (pid, "tcp:8080".to_string(), None);
+        assert!(!inherited.contains(&key(10)));
+        assert!(inherited.contains(&key(11)));
+        assert!(inherited.contains(&key(12)));
+        assert!(inherited.contains(&key(13)));
+
+        assert!(inherited_sockets(&[reuseport], parents_of_siblings).is_empty());
+        assert!(inherited_sockets(&[listener("0.0.0.0", 8080, 11)], parent_of).is_empty());
+
+        // Only listeners are owned; a shared connection is not attributed to anyone
+        let mut connection = listener("10.0.0.5", 8080, 10);
+        connection.state = SocketState::Established;
+        connection.pids = vec![10, 11];
+        assert!(inherited_sockets(&[connection], parent_of).is_empty());
+
+        // A parent that holds nothing doesn't end the walk: 30 forked the shell 31,
+        // which ran 32
+        let parents: HashMap<u32, u32> = [(31, 30), (32, 31)].into();
+        let mut through_shell = listener("0.0.0.0", 7070, 30);
+        through_shell.pids = vec![30, 32];
+        let inherited = inherited_sockets(&[through_shell], |pid| parents.get(&pid).copied());
+        assert!(inherited.contains(&(32, "tcp:7070".to_string(), None)));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_inherited_listener_of_spawned_child() {
+        use std::os::fd::AsRawFd;
+        use std::os::unix::process::CommandExt;
+
+        let socket = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = socket.local_addr().unwrap().port();
+        let fd = socket.as_raw_fd();
+        let mut command = std::process::Command::new("sleep");
+        command.arg("30");
+        // Keep the listener open across exec, as a pre-fork server's worker would
+        unsafe {
+            command.pre_exec(move || match libc::fcntl(fd, libc::F_SETFD, 0) {
+                -1 => Err(std::io::Error::last_os_error()),
+                _ => Ok(()),
+            });
+        }
+        let mut child = command.spawn().unwrap();
+
+        let state = AppStateManager::new(None).await;
+        let nodes = build_port_nodes(&state, find_port_users(port).unwrap()).await;
+        let _ = child.kill();
+        let _ = child.wait();
+
+        let inherited = |pid: u32| {
+            let node = nodes.iter().find(|node| node.pid == pid)?;
+            Some(node.ports[0].inherited_socket)
+        };
+        assert_eq!(inherited(std::process::id()), Some(false));
+        assert_eq!(inherited(child.id()), Some(true));
+    }
+
+    #[test]
+    fn test_loopback_and_wildcard_listeners_are_not_deduped() {
+        let ports = vec![
+            listener("::1", 8080, 42),
+            listener("0.0.0.0", 8080, 42),
+            listener("127.0.0.1", 8080, 42),
+            listener("::", 8080, 42),
+        ];
+
+        let grouped = group_ports_by_pid(&ports);
+        let addresses: Vec<&str> = grouped[&42]
+            .iter()
+            .map(|p| p.local_address.as_str())
+            .collect();
+        assert_eq!(addresses, vec!["::1", "0.0.0.0"]);
+    }
+
+    #[test]
+    fn test_port_keys_distinguish_protocols() {
+        // A DNS server listening on TCP and UDP 53, on all interfaces and the stub address
+        let udp = |address: &str| PortInfo {
+            protocol: Protocol::UDP,
+            ..listener(address, 53, 53)
+        };
+        let ports = vec![
+            listener("0.0.0.0", 53, 53),
+            udp("0.0.0.0"),
+            listener("127.0.0.53", 53, 53),
+            udp("127.0.0.53"),
+            udp("::"),
+        ];
+
+        let grouped = group_ports_by_pid(&ports);
+        let keys: Vec<&str> = grouped[&53].iter().map(|p| p.port_key.as_str()).collect();
+        assert_eq!(keys, vec!["tcp:53", "udp:53", "tcp:53@loopback", "udp:53@loopback"]);
+
+        let unique: HashSet<&str> = keys.iter().copied().collect();
+        assert_eq!(unique.len(), keys.len());
+    }
+
+    #[test]
+    fn test_container_action_result_reports_version_mismatch() {
+        let failed = container_action_result(
+            "web",
+            &ContainerAction::Stop,
+            Err(anyhow::anyhow!("No such container: web")),
+        )
+        .unwrap();
+        assert!(!failed.success);
+
+        let mismatch = container_action_result(
+            "web",
+            &ContainerAction::Stop,
+            Err(anyhow::anyhow!(
+                "client version 1.47 is too new. Maximum supported API version is 1.43"
+            )),
+        )
+        .unwrap_err();
+        assert_eq!(mismatch.code, ErrorCode::DockerVersionMismatch);
+
+        let batched = batch_action_result(
+            "web",
+            &ContainerAction::Stop,
+            Err(anyhow::anyhow!(
+                "client version 1.47 is too new. Maximum supported API version is 1.43"
+            )),
+        );
+        assert!(!batched.success);
+        assert!(batched.message.contains("Maximum supported API version is 1.43"));
+    }
+
+    #[test]
+    fn test_matches_name_filter() {
+        let mut server = node(1, None, 3000);
+        server.name = "Node".to_string();
+        let mut runner = node(2, None, 3001);
+        runner.name = "bun".to_string();
+        runner.command_line = Some("bun run /srv/node_modules/.bin/vite".to_string());
+        let other = node(3, None, 5432);
+
+        assert!(matches_name_filter(&server, "node"));
+        assert!(matches_name_filter(&runner, "node"));
+        assert!(!matches_name_filter(&other, "node"));
+    }
+
+    #[test]
+    fn test_group_by_executable_keeps_unknown_exe_separate() {
+        let groups = group_by_executable(vec![node(1, None, 80), node(2, None, 81)]);
+        assert_eq!(groups.len(), 2);
+    }
+
+    #[test]
+    fn test_threshold_tracker_requires_consecutive_samples() {
+        let thresholds = ResourceThresholds {
+            cpu_percent: Some(90.0),
+            memory_bytes: None,
+            consecutive_samples: Some(2),
+        };
+        let mut busy = node(1, None, 80);
+        busy.cpu_usage = 95.0;
+        let idle = node(1, None, 80);
+        let mut tracker = ThresholdTracker::default();
+
+        // A single spike followed by a quiet sample never alerts
+        assert!(tracker.observe(&[busy.clone()], &thresholds).is_empty());
+        assert!(tracker.observe(&[idle], &thresholds).is_empty());
+
+        assert!(tracker.observe(&[busy.clone()], &thresholds).is_empty());
+        let alerts = tracker.observe(&[busy.clone()], &thresholds);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].threshold, ThresholdKind::Cpu);
+        assert_eq!(alerts[0].process.pid, 1);
+
+        // Sustained load does not re-alert on every sample
+        assert!(tracker.observe(&[busy], &thresholds).is_empty());
+    }
+
+    #[test]
+    fn test_tunnel_info_for_sshd() {
+        let args = |line: &str| -> Vec<String> { line.split(' ').map(String::from).collect() };
+        let daemon = args("/usr/sbin/sshd -D");
+
+        // The daemon's Port may come from sshd_config alone
+        assert_eq!(tunnel_info("sshd", &daemon, &[2222], true), (false, None));
+        // A per-connection sshd holding a client's -R forward
+        let session = args("sshd: alice@pts/0");
+        assert_eq!(tunnel_info("sshd", &session, &[8080], false), (true, None));
+        assert_eq!(tunnel_info("sshd", &session, &[22], false), (false, None));
+        assert_eq!(tunnel_info("nginx", &daemon, &[8080], false), (false, None));
+    }
+
+    #[test]
+    fn test_combined_monitor_settings() {
+        let settings = |interval_ms: u64, show_all_connections: bool| MonitorSettings {
+            interval: Duration::from_millis(interval_ms),
+            show_all_connections,
+            thresholds: None,
+        };
+        let subscribers = HashMap::from([
+            ("main".to_string(), settings(5000, false)),
+            ("detail".to_string(), settings(1000, true)),
+        ]);
+
+        let (interval, options) = combined_monitor_settings(&subscribers);
+        assert_eq!(interval, Duration::from_secs(1));
+        assert!(options.show_all_connections);
+
+        let (interval, options) = combined_monitor_settings(&HashMap::new());
+        assert_eq!(interval, MIN_MONITOR_INTERVAL);
+        assert!(!options.show_all_connections);
+    }
+
+    #[tokio::test]
+    async fn test_run_blocking_cancellable_returns_promptly() {
+        let token = CancellationToken::new();
+        let canceller = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            canceller.cancel();
+        });
+
+        let started = std::time::Instant::now();
+        let result = run_blocking_cancellable(&token, || {
+            std::thread::sleep(Duration::from_secs(2));
+        })
+        .await;
+
+        assert_eq!(result.unwrap_err().code, ErrorCode::ScanCancelled);
+        assert!(started.elapsed() < Duration::from_secs(1));
+
+        let done = run_blocking_cancellable(&CancellationToken::new(), || 7).await;
+        assert_eq!(done.unwrap(), 7);
+    }
+
+    #[test]
+    fn test_scan_cache_serves_default_scans_only() {
+        let mut cache = ScanCache::default();
+        assert!(matches!(cache.update_since(0, 10), ScanUpdate::NotModified { generation: 0 }));
+
+        let filtered = ScanOptions {
+            show_all_connections: true,
+            ..Default::default()
+        };
+        cache.record(filtered, &app_state(vec![node(9, None, 22)]));
+        assert_eq!(cache.generation, 0);
+
+        let scan = app_state(vec![node(1, None, 3000), node(2, None, 5432)]);
+        cache.record(ScanOptions::default(), &scan);
+        match cache.update_since(0, 1) {
+            ScanUpdate::Updated { generation, state } => {
+                assert_eq!(generation, 1);
+                assert_eq!(state.processes.len(), 1);
+                assert!(state.truncated);
+            }
+            other => panic!("expected an update, got {:?}", other),
+        }
+        assert_eq!(cache.state.as_ref().unwrap().processes.len(), 2);
+        assert!(matches!(cache.update_since(1, 10), ScanUpdate::NotModified { generation: 1 }));
+    }
+
+    #[test]
+    fn test_protection_settings_reach_safety_checks() {
+        let _settings = crate::surgery::safety::PROTECTION_SETTINGS_LOCK
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let dir = std::env::temp_dir()
+            .join(format!("process-surgeon-protection-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(crate::config::CONFIG_FILE_NAME);
+        std::fs::write(
+            &path,
+            r#"{
+                "protectedPorts": [22, 53, 631, 65000],
+                "protectedPidRanges": [
+                    { "start": 0, "end": 2 },
+                    { "start": 4000000000, "end": 4000000010 }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        apply_protection_settings(&ConfigStore::load(path));
+        assert!(!check_process_safety(4_000_000_005, "worker").is_safe());
+        assert!(!check_process_safety_with_ports(4242, "worker", &[65000]).is_safe());
+
+        apply_protection_settings(&ConfigStore::default());
+        assert!(check_process_safety(4_000_000_005, "worker").is_safe());
+        assert!(check_process_safety_with_ports(4242, "worker", &[65000]).is_safe());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_supersede_scan_only_cancels_same_caller() {
+        let state = AppStateManager::new(None).await;
+        let ui = supersede_scan(&state, ScanCaller::Ui).await;
+        let monitor = supersede_scan(&state, ScanCaller::Monitor).await;
+        assert!(!ui.is_cancelled());
+
+        let newer_ui = supersede_scan(&state, ScanCaller::Ui).await;
+        assert!(ui.is_cancelled());
+        assert!(!newer_ui.is_cancelled());
+        assert!(!monitor.is_cancelled());
+        state.cpu_sampler.stop();
+    }
+
+    fn app_state(processes: Vec<ProcessNode>) -> AppState {
+        let total_processes = processes.len();
+        AppState {
+            processes,
+            total_connections: 0,
+            listening_ports: 0,
+            port_counts: PortCounts::default(),
+            docker_available: false,
+            last_updated: Utc::now(),
+            warnings: Vec::new(),
+            unix_sockets: Vec::new(),
+            limited_privileges: false,
+            truncated: false,
+            total_processes,
+            state_histogram: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_diff_scans() {
+        let previous = app_state(vec![
+            node(1, None, 3000),
+            node(2, None, 5432),
+            node(3, None, 80),
+        ]);
+        let mut reused = node(3, None, 80);
+        reused.name = "other".to_string();
+        let current = app_state(vec![
+            node(1, None, 3001),
+            node(2, None, 5432),
+            reused,
+            node(4, None, 9000),
+        ]);
+
+        let diff = scan_diff(&previous, &current);
+
+        // Same PID on a new port is a port change, not add + remove
+        assert_eq!(diff.port_changes.len(), 1);
+        assert_eq!(diff.port_changes[0].pid, 1);
+        assert_eq!(diff.port_changes[0].added[0].local_port, 3001);
+        assert_eq!(diff.port_changes[0].removed[0].local_port, 3000);
+
+        let added: Vec<u32> = diff.added.iter().map(|p| p.pid).collect();
+        let removed: Vec<u32> = diff.removed.iter().map(|p| p.pid).collect();
+        assert_eq!(added, vec![3, 4]);
+        assert_eq!(removed, vec![3]);
+
+        assert!(scan_diff(&current, &current).is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_kill_many_with_elevation_keeps_pid_order() {
+        let mut children: Vec<std::process::Child> = (0..2)
+            .map(|_| {
+                std::process::Command::new("sleep")
+                    .arg("30")
+                    .spawn()
+                    .expect("failed to spawn sleep")
+            })
+            .collect();
+        let pids: Vec<u32> = children.iter().map(|child| child.id()).collect();
+
+        let mut terminator = ProcessTerminator::new();
+        let listeners = terminator.prepare_batch(true).unwrap();
+        let results = kill_many_with_elevation(
+            &mut terminator,
+            &pids,
+            &HashMap::new(),
+            true,
+            false,
+            &listeners,
+        );
+
+        assert_eq!(results.len(), 2);
+        for (pid, result) in pids.iter().zip(&results) {
+            assert!(result.success);
+            assert!(result.message.contains(&pid.to_string()));
+        }
+        for child in &mut children {
+            let _ = child.wait();
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_kill_many_with_elevation_skips_reused_pids() {
+        let mut child = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .expect("failed to spawn sleep");
+        let pid = child.id();
+
+        let mut terminator = ProcessTerminator::new();
+        let listeners = terminator.prepare_batch(true).unwrap();
+        let expected = HashMap::from([(pid, "postgres".to_string())]);
+        let results =
+            kill_many_with_elevation(&mut terminator, &[pid], &expected, true, false, &listeners);
+
+        assert!(!results[0].success);
+        assert!(child.try_wait().unwrap().is_none());
+        child.kill().unwrap();
+        let _ = child.wait();
+    }
+}
@@ -0,0 +1,208 @@
+// Config Module - User settings persisted as JSON in the app config directory
+use crate::surgery::{DEFAULT_PROTECTED_PID_CEILING, DEFAULT_PROTECTED_PORTS};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+
+/// File name of the settings file inside the app config directory
+pub const CONFIG_FILE_NAME: &str = "settings.json";
+
+/// Process nodes `get_processes` returns before truncating, unless configured otherwise
+pub const DEFAULT_MAX_RESULTS: usize = 5000;
+
+/// User-editable settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct UserConfig {
+    /// Process names hidden as noise, compared case-insensitively
+    pub ignored_processes: Vec<String>,
+    /// Cap on process nodes per scan; `DEFAULT_MAX_RESULTS` when unset
+    pub max_results: Option<usize>,
+    /// Free-form notes keyed by executable path, so they survive restarts and PID reuse
+    pub process_notes: BTreeMap<String, String>,
+    /// Ports whose holders are refused a graceful kill; the built-in set when unset
+    pub protected_ports: Option<Vec<u16>>,
+    /// PID ranges refused on top of PIDs 0 and 1; the platform's low system PIDs when
+    /// unset
+    pub protected_pid_ranges: Option<Vec<PidRange>>,
+}
+
+/// Inclusive range of PIDs, stored as `{ "start": 0, "end": 300 }`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PidRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+/// Settings plus the file they are saved to
+///
+/// Without a path (e.g. the config directory could not be resolved) changes are kept
+/// in memory for the session only.
+#[derive(Debug, Default)]
+pub struct ConfigStore {
+    path: Option<PathBuf>,
+    pub settings: UserConfig,
+}
+
+impl ConfigStore {
+    /// Load settings from `path`, falling back to defaults if missing or unreadable
+    pub fn load(path: PathBuf) -> Self {
+        let settings = match std::fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                log::warn!("Ignoring malformed settings in {}: {}", path.display(), e);
+                UserConfig::default()
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => UserConfig::default(),
+            Err(e) => {
+                log::warn!("Failed to read settings from {}: {}", path.display(), e);
+                UserConfig::default()
+            }
+        };
+
+        Self {
+            path: Some(path),
+            settings,
+        }
+    }
+
+    /// Write the current settings back to disk
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(&self.settings)?)?;
+        Ok(())
+    }
+
+    /// Add a process name to the ignore list; returns false if it was already present
+    pub fn add_ignored_process(&mut self, name: &str) -> bool {
+        if self.is_ignored(name) {
+            return false;
+        }
+        self.settings.ignored_processes.push(name.to_string());
+        true
+    }
+
+    /// Remove a process name from the ignore list; returns false if it was not present
+    pub fn remove_ignored_process(&mut self, name: &str) -> bool {
+        let name = name.to_lowercase();
+        let before = self.settings.ignored_processes.len();
+        self.settings
+            .ignored_processes
+            .retain(|ignored| ignored.to_lowercase() != name);
+        self.settings.ignored_processes.len() != before
+    }
+
+    /// Check whether a process name is on the ignore list
+    pub fn is_ignored(&self, name: &str) -> bool {
+        self.ignored_set().contains(&name.to_lowercase())
+    }
+
+    /// Attach a note to an executable path, or clear it when `note` is blank
+    ///
+    /// Returns false if nothing changed.
+    pub fn set_process_note(&mut self, exe_path: &str, note: &str) -> bool {
+        let note = note.trim();
+        if note.is_empty() {
+            return self.settings.process_notes.remove(exe_path).is_some();
+        }
+        if self.process_note(exe_path) == Some(note) {
+            return false;
+        }
+        self.settings
+            .process_notes
+            .insert(exe_path.to_string(), note.to_string());
+        true
+    }
+
+    /// Note attached to an executable path, if any
+    pub fn process_note(&self, exe_path: &str) -> Option<&str> {
+        self.settings.process_notes.get(exe_path).map(String::as_str)
+    }
+
+    /// Effective cap on process nodes returned by a scan
+    pub fn max_results(&self) -> usize {
+        self.settings.max_results.unwrap_or(DEFAULT_MAX_RESULTS)
+    }
+
+    /// Effective ports whose holders are refused a graceful kill
+    pub fn protected_ports(&self) -> Vec<u16> {
+        self.settings
+            .protected_ports
+            .clone()
+            .unwrap_or_else(|| DEFAULT_PROTECTED_PORTS.to_vec())
+    }
+
+    /// Effective PID ranges refused on top of PIDs 0 and 1
+    pub fn protected_pid_ranges(&self) -> Vec<RangeInclusive<u32>> {
+        match &self.settings.protected_pid_ranges {
+            Some(ranges) => ranges.iter().map(|range| range.start..=range.end).collect(),
+            None => vec![0..=DEFAULT_PROTECTED_PID_CEILING],
+        }
+    }
+
+    /// Lowercased ignore list for repeated lookups during a scan
+    pub fn ignored_set(&self) -> HashSet<String> {
+        self.settings
+            .ignored_processes
+            .iter()
+            .map(|name| name.to_lowercase())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ignore_list_is_case_insensitive() {
+        let mut store = ConfigStore::default();
+        assert!(store.add_ignored_process("mDNSResponder"));
+        assert!(!store.add_ignored_process("mdnsresponder"));
+        assert!(store.is_ignored("MDNSRESPONDER"));
+        assert!(store.ignored_set().contains("mdnsresponder"));
+
+        assert!(store.remove_ignored_process("MDNSResponder"));
+        assert!(!store.is_ignored("mDNSResponder"));
+        assert!(!store.remove_ignored_process("rapportd"));
+    }
+
+    #[test]
+    fn test_process_notes() {
+        let mut store = ConfigStore::default();
+        assert!(store.set_process_note("/usr/local/bin/mystery", " from last week "));
+        assert!(!store.set_process_note("/usr/local/bin/mystery", "from last week"));
+        assert_eq!(store.process_note("/usr/local/bin/mystery"), Some("from last week"));
+        assert_eq!(store.process_note("/usr/bin/other"), None);
+
+        assert!(store.set_process_note("/usr/local/bin/mystery", "  "));
+        assert_eq!(store.process_note("/usr/local/bin/mystery"), None);
+        assert!(!store.set_process_note("/usr/local/bin/mystery", ""));
+    }
+
+    #[test]
+    fn test_config_round_trip() {
+        let path = std::env::temp_dir()
+            .join(format!("process-surgeon-config-{}", std::process::id()))
+            .join(CONFIG_FILE_NAME);
+
+        let mut store = ConfigStore::load(path.clone());
+        assert_eq!(store.protected_ports(), DEFAULT_PROTECTED_PORTS);
+        store.add_ignored_process("rapportd");
+        store.settings.protected_ports = Some(vec![22, 8443]);
+        store.save().unwrap();
+
+        let reloaded = ConfigStore::load(path.clone());
+        assert!(reloaded.is_ignored("rapportd"));
+        assert_eq!(reloaded.protected_ports(), vec![22, 8443]);
+
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
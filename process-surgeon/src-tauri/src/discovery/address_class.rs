@@ -0,0 +1,145 @@
+// Address Class Module - Bucketing local bind addresses for dedup and exposure
+use std::fmt;
+use std::net::IpAddr;
+
+/// Which interfaces a local bind address covers
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AddressClass {
+    /// Every interface: `0.0.0.0`, `::` or the IPv4-mapped wildcard
+    Wildcard,
+    /// Loopback only: `127.0.0.0/8`, `::1` or their IPv4-mapped forms
+    Loopback,
+    /// An IPv6 link-local address (`fe80::/10`), with its scope id when one was given
+    LinkLocal(String),
+    /// One interface address; IPv4-mapped IPv6 addresses are shown in IPv4 form
+    Specific(String),
+}
+
+/// Classify a socket's local address, treating both families alike
+///
+/// A dual-stack wildcard and an IPv4 one land in the same class, while a loopback
+/// bind is never merged with an all-interfaces one. Scope ids are only kept for
+/// link-local addresses, where the same address can exist on several interfaces.
+/// Unparseable addresses are kept verbatim as `Specific`.
+pub fn normalize_listen_address(addr: &str) -> AddressClass {
+    let (ip, scope_id) = split_scope_id(addr);
+    let Ok(ip) = ip.parse::<IpAddr>() else {
+        return AddressClass::Specific(addr.to_string());
+    };
+
+    let canonical = ip.to_canonical();
+    if canonical.is_unspecified() {
+        AddressClass::Wildcard
+    } else if canonical.is_loopback() {
+        AddressClass::Loopback
+    } else if is_ipv6_link_local(canonical) {
+        AddressClass::LinkLocal(format_address(canonical, scope_id))
+    } else {
+        AddressClass::Specific(canonical.to_string())
+    }
+}
+
+/// Split an address into the IP and its IPv6 scope id, e.g. `fe80::1%eth0`
+///
+/// Surrounding brackets, as in `[fe80::1%2]`, are dropped.
+pub fn split_scope_id(addr: &str) -> (&str, Option<&str>) {
+    let addr = addr.trim_start_matches('[').trim_end_matches(']');
+    match addr.split_once('%') {
+        Some((ip, scope_id)) if !scope_id.is_empty() => (ip, Some(scope_id)),
+        Some((ip, _)) => (ip, None),
+        None => (addr, None),
+    }
+}
+
+/// Parse an address string, ignoring brackets and any scope id
+pub fn parse_address(addr: &str) -> Option<IpAddr> {
+    split_scope_id(addr).0.parse().ok()
+}
+
+/// Render an address, appending the scope id only for IPv6 link-local addresses
+///
+/// Elsewhere a scope id adds nothing and would make equal addresses compare unequal.
+pub fn format_address(ip: IpAddr, scope_id: Option<&str>) -> String {
+    match scope_id {
+        Some(scope_id) if is_ipv6_link_local(ip) => format!("{}%{}", ip, scope_id),
+        _ => ip.to_string(),
+    }
+}
+
+/// Whether `ip` is in the IPv6 link-local range `fe80::/10`
+pub fn is_ipv6_link_local(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V6(ip) => ip.segments()[0] & 0xffc0 == 0xfe80,
+        IpAddr::V4(_) => false,
+    }
+}
+
+impl fmt::Display for AddressClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AddressClass::Wildcard => f.write_str("wildcard"),
+            AddressClass::Loopback => f.write_str("loopback"),
+            AddressClass::LinkLocal(addr) | AddressClass::Specific(addr) => f.write_str(addr),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_listen_address() {
+        for wildcard in ["0.0.0.0", "::", "::ffff:0.0.0.0"] {
+            assert_eq!(normalize_listen_address(wildcard), AddressClass::Wildcard);
+        }
+        for loopback in ["127.0.0.1", "127.0.0.53", "::1", "::ffff:127.0.0.1"] {
+            assert_eq!(normalize_listen_address(loopback), AddressClass::Loopback);
+        }
+        assert_eq!(
+            normalize_listen_address("::ffff:192.168.1.5"),
+            AddressClass::Specific("192.168.1.5".to_string())
+        );
+    }
+
+    #[test]
+    fn test_normalize_link_local_address() {
+        for scoped in ["fe80::1%eth0", "[FE80:0:0::1%eth0]", "fe80:0000::0001%eth0"] {
+            assert_eq!(
+                normalize_listen_address(scoped),
+                AddressClass::LinkLocal("fe80::1%eth0".to_string())
+            );
+        }
+        assert_eq!(
+            normalize_listen_address("fe80::1"),
+            AddressClass::LinkLocal("fe80::1".to_string())
+        );
+        assert_ne!(
+            normalize_listen_address("fe80::1%eth0"),
+            normalize_listen_address("fe80::1%eth1")
+        );
+        // A scope id on anything else is dropped
+        assert_eq!(
+            normalize_listen_address("2001:db8::1%eth0"),
+            AddressClass::Specific("2001:db8::1".to_string())
+        );
+        assert!(!is_ipv6_link_local("fec0::1".parse().unwrap()));
+        assert!(!is_ipv6_link_local("169.254.1.1".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_split_scope_id() {
+        assert_eq!(split_scope_id("[fe80::1%2]"), ("fe80::1", Some("2")));
+        assert_eq!(split_scope_id("fe80::1%"), ("fe80::1", None));
+        assert_eq!(split_scope_id("10.0.0.5"), ("10.0.0.5", None));
+        assert_eq!(parse_address("[::1]"), Some("::1".parse().unwrap()));
+        assert_eq!(parse_address("not an address"), None);
+    }
+
+    #[test]
+    fn test_address_class_display() {
+        assert_eq!(AddressClass::Wildcard.to_string(), "wildcard");
+        assert_eq!(AddressClass::Loopback.to_string(), "loopback");
+        assert_eq!(AddressClass::Specific("10.0.0.5".to_string()).to_string(), "10.0.0.5");
+    }
+}
@@ -0,0 +1,101 @@
+// CPU Sampler Module - Per-process CPU usage refreshed on a fixed cadence
+use std::collections::HashMap;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System};
+use tokio::sync::RwLock;
+
+/// How often the sampler thread refreshes CPU usage
+pub const CPU_SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Background sampler keeping per-process CPU usage current
+///
+/// CPU usage is the delta between two refreshes, so a scan that refreshes once reports
+/// whatever happened since the previous scan, however long ago that was. The sampler
+/// owns its own `System` and refreshes it on a dedicated thread, letting scans read a
+/// reading over a full interval without waiting for one.
+pub struct CpuSampler {
+    /// CPU usage by PID, in percent of one core, from the latest sample
+    usage: Arc<RwLock<HashMap<u32, f32>>>,
+    /// Dropping the sender stops the thread
+    stop: Mutex<Option<Sender<()>>>,
+}
+
+impl CpuSampler {
+    /// Start sampling every `interval` on a new thread
+    pub fn start(interval: Duration) -> Self {
+        let usage = Arc::new(RwLock::new(HashMap::new()));
+        let (stop, stopped) = mpsc::channel::<()>();
+
+        let published = usage.clone();
+        let spawned = std::thread::Builder::new()
+            .name("cpu-sampler".to_string())
+            .spawn(move || {
+                let mut system = System::new();
+                // The first refresh has nothing to compare against and reads 0% everywhere
+                let mut primed = false;
+                loop {
+                    system.refresh_processes_specifics(
+                        ProcessesToUpdate::All,
+                        ProcessRefreshKind::new().with_cpu(),
+                    );
+                    if primed {
+                        let sample = system
+                            .processes()
+                            .iter()
+                            .map(|(pid, process)| (pid.as_u32(), process.cpu_usage()))
+                            .collect();
+                        *published.blocking_write() = sample;
+                    }
+                    primed = true;
+
+                    match stopped.recv_timeout(interval) {
+                        Err(RecvTimeoutError::Timeout) => continue,
+                        _ => break,
+                    }
+                }
+                log::debug!("CPU sampler stopped");
+            });
+        if let Err(e) = spawned {
+            log::warn!("Failed to start CPU sampler, CPU usage will be per-scan: {}", e);
+        }
+
+        Self {
+            usage,
+            stop: Mutex::new(Some(stop)),
+        }
+    }
+
+    /// CPU usage by PID from the latest sample; empty until two refreshes have run
+    pub async fn snapshot(&self) -> HashMap<u32, f32> {
+        self.usage.read().await.clone()
+    }
+
+    /// Stop the sampler thread after its current refresh; later calls do nothing
+    pub fn stop(&self) {
+        let mut stop = self.stop.lock().unwrap_or_else(|e| e.into_inner());
+        stop.take();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_sampler_reports_own_process_until_stopped() {
+        let sampler = CpuSampler::start(Duration::from_millis(250));
+        assert!(sampler.snapshot().await.is_empty());
+
+        tokio::time::sleep(Duration::from_millis(1000)).await;
+        assert!(sampler.snapshot().await.contains_key(&std::process::id()));
+
+        sampler.stop();
+        sampler.stop();
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        sampler.usage.write().await.clear();
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        assert!(sampler.snapshot().await.is_empty());
+    }
+}
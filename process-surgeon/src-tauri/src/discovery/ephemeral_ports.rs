@@ -0,0 +1,126 @@
+// Ephemeral Ports Module - How close the system is to running out of outbound ports
+use crate::models::{PortPressure, SocketState};
+use std::collections::HashSet;
+
+/// Utilization at which `summarize_port_pressure` flags a warning unless told otherwise
+pub const DEFAULT_PRESSURE_WARN_PERCENT: f64 = 80.0;
+
+/// Linux's default `ip_local_port_range`
+#[cfg(target_os = "linux")]
+const DEFAULT_EPHEMERAL_RANGE: (u16, u16) = (32768, 60999);
+
+/// The IANA dynamic range, used by Windows and macOS out of the box
+#[cfg(not(target_os = "linux"))]
+const DEFAULT_EPHEMERAL_RANGE: (u16, u16) = (49152, 65535);
+
+/// Inclusive range the OS picks outbound (ephemeral) ports from
+///
+/// Read from `/proc/sys/net/ipv4/ip_local_port_range` on Linux, which also governs
+/// IPv6. Elsewhere, and when the file can't be read, the platform default is assumed.
+pub fn ephemeral_port_range() -> (u16, u16) {
+    #[cfg(target_os = "linux")]
+    {
+        match std::fs::read_to_string("/proc/sys/net/ipv4/ip_local_port_range") {
+            Ok(contents) => {
+                if let Some(range) = parse_port_range(&contents) {
+                    return range;
+                }
+                log::debug!("Unexpected ip_local_port_range: {:?}", contents);
+            }
+            Err(e) => log::debug!("Failed to read ip_local_port_range: {}", e),
+        }
+    }
+
+    DEFAULT_EPHEMERAL_RANGE
+}
+
+/// Parse the two whitespace-separated bounds of `ip_local_port_range`
+#[cfg(any(target_os = "linux", test))]
+fn parse_port_range(contents: &str) -> Option<(u16, u16)> {
+    let mut bounds = contents.split_whitespace().map(str::parse::<u16>);
+    match (bounds.next(), bounds.next()) {
+        (Some(Ok(start)), Some(Ok(end))) if start <= end => Some((start, end)),
+        _ => None,
+    }
+}
+
+/// Summarize how many ports in `range` are taken by TCP sockets
+///
+/// Each port counts once however many sockets share it. Listening sockets are left
+/// out: a server bound inside the range is not using it up for outbound connections.
+pub fn summarize_port_pressure(
+    sockets: &[(u16, SocketState)],
+    range: (u16, u16),
+    warn_percent: f64,
+) -> PortPressure {
+    let (start, end) = range;
+    let mut in_use = HashSet::new();
+    let mut established = HashSet::new();
+    let mut time_wait = HashSet::new();
+
+    for &(port, state) in sockets {
+        if !(start..=end).contains(&port) || state == SocketState::Listening {
+            continue;
+        }
+        in_use.insert(port);
+        match state {
+            SocketState::Established => {
+                established.insert(port);
+            }
+            SocketState::TimeWait => {
+                time_wait.insert(port);
+            }
+            _ => {}
+        }
+    }
+
+    let range_size = usize::from(end - start) + 1;
+    let utilization_percent = in_use.len() as f64 * 100.0 / range_size as f64;
+
+    PortPressure {
+        range_start: start,
+        range_end: end,
+        established: established.len(),
+        time_wait: time_wait.len(),
+        in_use: in_use.len(),
+        utilization_percent,
+        warning: utilization_percent >= warn_percent,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_port_range() {
+        assert_eq!(parse_port_range("32768\t60999\n"), Some((32768, 60999)));
+        assert_eq!(parse_port_range("1024 65535"), Some((1024, 65535)));
+        assert_eq!(parse_port_range("60999 32768"), None);
+        assert_eq!(parse_port_range("32768"), None);
+    }
+
+    #[test]
+    fn test_port_pressure() {
+        let sockets = [
+            (50000, SocketState::Established),
+            (50000, SocketState::TimeWait),
+            (50001, SocketState::TimeWait),
+            (50002, SocketState::FinWait2),
+            (50003, SocketState::Listening),
+            // Outside the range
+            (443, SocketState::Established),
+        ];
+
+        let pressure = summarize_port_pressure(&sockets, (50000, 50009), 30.0);
+        assert_eq!(pressure.in_use, 3);
+        assert_eq!(pressure.established, 1);
+        assert_eq!(pressure.time_wait, 2);
+        assert!((pressure.utilization_percent - 30.0).abs() < f64::EPSILON);
+        assert!(pressure.warning);
+
+        let pressure =
+            summarize_port_pressure(&sockets, (50000, 50009), DEFAULT_PRESSURE_WARN_PERCENT);
+        assert!(!pressure.warning);
+    }
+}
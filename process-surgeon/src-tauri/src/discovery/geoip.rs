@@ -0,0 +1,226 @@
+// GeoIP Module - ASN / organisation / country annotation for remote addresses
+use crate::models::AsnInfo;
+use anyhow::{Context, Result};
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+
+/// Address block owned by a single autonomous system
+#[derive(Debug, Clone)]
+struct AsnBlock<T> {
+    start: T,
+    end: T,
+    info: AsnInfo,
+}
+
+/// In-memory ASN database loaded from a MaxMind-style CSV export
+///
+/// Each row is `network,autonomous_system_number,autonomous_system_organization[,country_iso_code]`
+/// where `network` is a CIDR block, matching the GeoLite2 ASN CSV layout with an
+/// optional country column. Header and comment lines are skipped.
+pub struct GeoIpDatabase {
+    path: PathBuf,
+    v4: Vec<AsnBlock<u32>>,
+    v6: Vec<AsnBlock<u128>>,
+}
+
+impl GeoIpDatabase {
+    /// Load a database from disk
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read GeoIP database {}", path.display()))?;
+
+        let mut database = Self::parse(&contents)?;
+        database.path = path.to_path_buf();
+
+        log::info!(
+            "Loaded GeoIP database {} ({} IPv4 / {} IPv6 blocks)",
+            path.display(),
+            database.v4.len(),
+            database.v6.len()
+        );
+        Ok(database)
+    }
+
+    /// Parse a database from CSV text
+    pub fn parse(contents: &str) -> Result<Self> {
+        let mut v4 = Vec::new();
+        let mut v6 = Vec::new();
+
+        for (line_no, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields = split_csv_line(line);
+            let Some((network, prefix)) = fields.first().and_then(|f| parse_cidr(f)) else {
+                // Header row or unrecognised network column
+                continue;
+            };
+
+            let asn = fields
+                .get(1)
+                .and_then(|f| f.trim_start_matches("AS").parse().ok())
+                .with_context(|| format!("Invalid ASN on line {}", line_no + 1))?;
+            let info = AsnInfo {
+                asn,
+                org: fields.get(2).cloned().unwrap_or_default(),
+                country: fields.get(3).filter(|c| !c.is_empty()).cloned(),
+            };
+
+            match network {
+                IpAddr::V4(addr) => {
+                    let (start, end) = block_bounds(u32::from(addr) as u128, prefix, 32);
+                    v4.push(AsnBlock {
+                        start: start as u32,
+                        end: end as u32,
+                        info,
+                    });
+                }
+                IpAddr::V6(addr) => {
+                    let (start, end) = block_bounds(u128::from(addr), prefix, 128);
+                    v6.push(AsnBlock { start, end, info });
+                }
+            }
+        }
+
+        v4.sort_by_key(|b| b.start);
+        v6.sort_by_key(|b| b.start);
+
+        Ok(Self {
+            path: PathBuf::new(),
+            v4,
+            v6,
+        })
+    }
+
+    /// Path the database was loaded from
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Look up the autonomous system announcing an address
+    pub fn lookup(&self, ip: IpAddr) -> Option<AsnInfo> {
+        match ip.to_canonical() {
+            IpAddr::V4(addr) => find_block(&self.v4, u32::from(addr)),
+            IpAddr::V6(addr) => find_block(&self.v6, u128::from(addr)),
+        }
+    }
+}
+
+/// Whether an address is publicly routable (and therefore worth annotating)
+pub fn is_public_address(ip: IpAddr) -> bool {
+    match ip.to_canonical() {
+        IpAddr::V4(addr) => {
+            !(addr.is_private()
+                || addr.is_loopback()
+                || addr.is_link_local()
+                || addr.is_unspecified()
+                || addr.is_broadcast()
+                || addr.is_multicast()
+                || addr.is_documentation()
+                // 100.64.0.0/10 carrier-grade NAT
+                || (addr.octets()[0] == 100 && (addr.octets()[1] & 0xc0) == 64))
+        }
+        IpAddr::V6(addr) => {
+            !(addr.is_loopback()
+                || addr.is_unspecified()
+                || addr.is_multicast()
+                || addr.is_unicast_link_local()
+                || addr.is_unique_local()
+                // 2001:db8::/32 documentation
+                || (addr.segments()[0] == 0x2001 && addr.segments()[1] == 0x0db8))
+        }
+    }
+}
+
+/// Binary search for the block containing `ip`
+fn find_block<T: Ord + Copy>(blocks: &[AsnBlock<T>], ip: T) -> Option<AsnInfo> {
+    let index = blocks.partition_point(|b| b.start <= ip);
+    let block = blocks.get(index.checked_sub(1)?)?;
+    (ip <= block.end).then(|| block.info.clone())
+}
+
+/// First and last address of a block, for an address `bits` wide
+fn block_bounds(addr: u128, prefix: u8, bits: u8) -> (u128, u128) {
+    let host_bits = (bits - prefix.min(bits)) as u32;
+    let host_mask = if host_bits >= 128 {
+        u128::MAX
+    } else {
+        (1u128 << host_bits) - 1
+    };
+    (addr & !host_mask, addr | host_mask)
+}
+
+/// Parse `a.b.c.d/nn` or `x::y/nn`
+fn parse_cidr(field: &str) -> Option<(IpAddr, u8)> {
+    let (addr, prefix) = field.trim().split_once('/')?;
+    let addr: IpAddr = addr.parse().ok()?;
+    let prefix: u8 = prefix.parse().ok()?;
+    let max = if addr.is_ipv4() { 32 } else { 128 };
+    (prefix <= max).then_some((addr, prefix))
+}
+
+/// Split a CSV line, honouring double-quoted fields
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+
+    fields.into_iter().map(|f| f.trim().to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+network,autonomous_system_number,autonomous_system_organization,country_iso_code
+1.1.1.0/24,13335,CLOUDFLARENET,US
+8.8.8.0/24,15169,GOOGLE,US
+2a00:1450::/32,15169,\"Google, LLC\",
+";
+
+    #[test]
+    fn test_lookup() {
+        let db = GeoIpDatabase::parse(SAMPLE).unwrap();
+
+        let cf = db.lookup("1.1.1.1".parse().unwrap()).unwrap();
+        assert_eq!(cf.asn, 13335);
+        assert_eq!(cf.country.as_deref(), Some("US"));
+
+        let google_v6 = db.lookup("2a00:1450:4001::1".parse().unwrap()).unwrap();
+        assert_eq!(google_v6.org, "Google, LLC");
+        assert_eq!(google_v6.country, None);
+
+        assert!(db.lookup("8.8.9.1".parse().unwrap()).is_none());
+        assert!(db.lookup("::ffff:8.8.8.8".parse().unwrap()).is_some());
+    }
+
+    #[test]
+    fn test_is_public_address() {
+        assert!(is_public_address("8.8.8.8".parse().unwrap()));
+        assert!(!is_public_address("10.0.0.1".parse().unwrap()));
+        assert!(!is_public_address("127.0.0.1".parse().unwrap()));
+        assert!(!is_public_address("169.254.1.1".parse().unwrap()));
+        assert!(!is_public_address("fe80::1".parse().unwrap()));
+        assert!(!is_public_address("::ffff:192.168.1.1".parse().unwrap()));
+        assert!(!is_public_address("2001:db8::1".parse().unwrap()));
+        assert!(is_public_address("2001:4860:4860::8888".parse().unwrap()));
+    }
+}
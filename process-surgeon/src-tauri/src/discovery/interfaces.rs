@@ -0,0 +1,136 @@
+// Interfaces Module - Host interface addresses and where a listener can be reached
+use super::{format_address, parse_address};
+use std::io;
+use std::net::IpAddr;
+
+/// One address assigned to a network interface
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InterfaceAddress {
+    /// Interface name, e.g. `eth0` or `en0`
+    pub name: String,
+    pub ip: IpAddr,
+    /// Operationally up, i.e. running
+    pub is_up: bool,
+}
+
+/// Every IPv4 and IPv6 address assigned to this host's interfaces
+pub fn interface_addresses() -> io::Result<Vec<InterfaceAddress>> {
+    Ok(if_addrs::get_if_addrs()?
+        .into_iter()
+        .map(|iface| InterfaceAddress {
+            ip: iface.ip(),
+            is_up: iface.is_oper_up(),
+            name: iface.name,
+        })
+        .collect())
+}
+
+/// Whether an IPv6 wildcard listener also accepts IPv4 by default
+///
+/// Linux and macOS create dual-stack sockets; Windows sets IPV6_V6ONLY unless the
+/// program clears it, so `::` there serves IPv6 only.
+const DUAL_STACK_BY_DEFAULT: bool = !cfg!(windows);
+
+/// Concrete addresses a listener bound to `listen_address` is reachable on
+///
+/// A wildcard bind serves every interface that is up: `0.0.0.0` their IPv4 addresses
+/// and `::` their IPv6 ones, plus IPv4 where sockets are dual-stack by default.
+/// Any other bind serves only its own address. Each address comes with the interface
+/// it is assigned to, when known; link-local ones are scoped to that interface.
+pub fn reachable_addresses(
+    listen_address: &str,
+    interfaces: &[InterfaceAddress],
+) -> Vec<(Option<String>, String)> {
+    reachable_addresses_with(listen_address, interfaces, DUAL_STACK_BY_DEFAULT)
+}
+
+/// `reachable_addresses`, with whether `::` also serves IPv4 given explicitly
+fn reachable_addresses_with(
+    listen_address: &str,
+    interfaces: &[InterfaceAddress],
+    dual_stack: bool,
+) -> Vec<(Option<String>, String)> {
+    let Some(bound) = parse_address(listen_address) else {
+        return vec![(None, listen_address.to_string())];
+    };
+
+    if !bound.to_canonical().is_unspecified() {
+        let canonical = bound.to_canonical();
+        let interface = interfaces
+            .iter()
+            .find(|iface| iface.ip.to_canonical() == canonical)
+            .map(|iface| iface.name.clone());
+        return vec![(interface, listen_address.to_string())];
+    }
+
+    let ipv4_only = bound.to_canonical().is_ipv4();
+    interfaces
+        .iter()
+        .filter(|iface| iface.is_up)
+        .filter(|iface| {
+            if iface.ip.is_ipv4() {
+                ipv4_only || dual_stack
+            } else {
+                !ipv4_only
+            }
+        })
+        .map(|iface| {
+            (
+                Some(iface.name.clone()),
+                format_address(iface.ip, Some(&iface.name)),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn iface(name: &str, ip: &str, is_up: bool) -> InterfaceAddress {
+        InterfaceAddress {
+            name: name.to_string(),
+            ip: ip.parse().unwrap(),
+            is_up,
+        }
+    }
+
+    #[test]
+    fn test_reachable_addresses() {
+        let interfaces = vec![
+            iface("lo", "127.0.0.1", true),
+            iface("eth0", "192.168.1.20", true),
+            iface("eth0", "fe80::1", true),
+            iface("wlan0", "10.0.0.7", false),
+        ];
+        let addresses_with = |bind: &str, dual_stack: bool| -> Vec<String> {
+            reachable_addresses_with(bind, &interfaces, dual_stack)
+                .into_iter()
+                .map(|(_, address)| address)
+                .collect()
+        };
+        let addresses = |bind: &str| addresses_with(bind, true);
+
+        assert_eq!(addresses("0.0.0.0"), vec!["127.0.0.1", "192.168.1.20"]);
+        assert_eq!(addresses("::"), vec!["127.0.0.1", "192.168.1.20", "fe80::1%eth0"]);
+        assert_eq!(addresses("::ffff:0.0.0.0"), vec!["127.0.0.1", "192.168.1.20"]);
+        // IPV6_V6ONLY wildcards, the Windows default
+        assert_eq!(addresses_with("::", false), vec!["fe80::1%eth0"]);
+        assert_eq!(addresses_with("0.0.0.0", false), vec!["127.0.0.1", "192.168.1.20"]);
+
+        assert_eq!(
+            reachable_addresses("192.168.1.20", &interfaces),
+            vec![(Some("eth0".to_string()), "192.168.1.20".to_string())]
+        );
+        assert_eq!(
+            reachable_addresses("10.9.9.9", &interfaces),
+            vec![(None, "10.9.9.9".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_interface_addresses_include_loopback() {
+        let addresses = interface_addresses().unwrap();
+        assert!(addresses.iter().any(|a| a.ip.is_loopback()));
+    }
+}
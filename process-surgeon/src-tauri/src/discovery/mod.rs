@@ -1,6 +1,24 @@
 // Discovery module - Cross-platform port and process discovery
+pub mod address_class;
+pub mod cpu_sampler;
+pub mod ephemeral_ports;
+pub mod geoip;
+pub mod interfaces;
 pub mod port_scanner;
 pub mod process_info;
+pub mod reverse_dns;
+pub mod services;
+pub mod ssh_tunnel;
+pub mod unix_sockets;
 
+pub use address_class::*;
+pub use cpu_sampler::*;
+pub use ephemeral_ports::*;
+pub use geoip::*;
+pub use interfaces::*;
 pub use port_scanner::*;
 pub use process_info::*;
+pub use reverse_dns::*;
+pub use services::*;
+pub use ssh_tunnel::*;
+pub use unix_sockets::*;
@@ -1,69 +1,564 @@
 // Port Scanner Module - Cross-platform socket enumeration
-use crate::models::{PortInfo, Protocol, SocketState};
-use anyhow::Result;
-use netstat2::{get_sockets_info, AddressFamilyFlags, ProtocolFlags, ProtocolSocketInfo};
+use super::{format_address, parse_address};
+use crate::models::{PortInfo, PortStatus, Protocol, Reachability, SocketState};
+use anyhow::{bail, ensure, Result};
+use std::collections::HashMap;
+use std::io;
+use std::net::{IpAddr, SocketAddr, TcpStream};
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+use netstat2::{
+    get_sockets_info, AddressFamilyFlags, ProtocolFlags, ProtocolSocketInfo, SocketInfo,
+};
+
+/// Sockets gathered by a scan, plus any parts of the scan that failed
+#[derive(Debug, Clone, Default)]
+pub struct PortScan {
+    pub ports: Vec<PortInfo>,
+    pub warnings: Vec<String>,
+    /// Most sockets came back without an owning PID, which on Windows means the app
+    /// is not elevated; those sockets are missing from `ports`
+    pub limited_privileges: bool,
+    /// Every enumerated socket per state, including the ownerless ones left out of `ports`
+    pub state_histogram: HashMap<SocketState, usize>,
+}
+
+/// Below this many sockets, missing PIDs are too few to diagnose anything
+const MIN_SOCKETS_FOR_PRIVILEGE_CHECK: usize = 8;
 
 /// Scans all active network sockets on the system
-pub fn scan_ports() -> Result<Vec<PortInfo>> {
-    let af_flags = AddressFamilyFlags::IPV4 | AddressFamilyFlags::IPV6;
-    let proto_flags = ProtocolFlags::TCP | ProtocolFlags::UDP;
+///
+/// Each protocol and address family is queried separately so that a transient failure
+/// in one of them only produces a warning. Errors only when every query failed.
+pub fn scan_ports() -> Result<PortScan> {
+    scan_ports_cancellable(&CancellationToken::new())
+}
 
-    let sockets = get_sockets_info(af_flags, proto_flags)?;
+/// `scan_ports` that gives up with an error once `cancel` fires
+///
+/// Checked before each protocol and address family query, since one query can take
+/// a while with many sockets.
+pub fn scan_ports_cancellable(cancel: &CancellationToken) -> Result<PortScan> {
+    let queries = [
+        ("TCP/IPv4", ProtocolFlags::TCP, AddressFamilyFlags::IPV4),
+        ("TCP/IPv6", ProtocolFlags::TCP, AddressFamilyFlags::IPV6),
+        ("UDP/IPv4", ProtocolFlags::UDP, AddressFamilyFlags::IPV4),
+        ("UDP/IPv6", ProtocolFlags::UDP, AddressFamilyFlags::IPV6),
+    ];
 
-    let mut ports: Vec<PortInfo> = sockets
-        .into_iter()
-        .filter_map(|socket| {
-            let (protocol, local_addr, local_port, remote_addr, remote_port, state) =
-                match &socket.protocol_socket_info {
-                    ProtocolSocketInfo::Tcp(tcp) => {
-                        let state = tcp_state_to_socket_state(&tcp.state);
-                        (
-                            Protocol::TCP,
-                            tcp.local_addr,
-                            tcp.local_port,
-                            Some(tcp.remote_addr),
-                            Some(tcp.remote_port),
-                            state,
-                        )
+    let mut scan = PortScan::default();
+    let mut succeeded = false;
+    let udp_peers = connected_udp_peers();
+    let mut owner_fallback = SocketOwnerFallback::default();
+    let mut total_sockets = 0;
+    let mut ownerless_sockets = 0;
+
+    for (label, proto_flags, af_flags) in queries {
+        ensure!(!cancel.is_cancelled(), "Socket scan cancelled");
+        match get_sockets_info(af_flags, proto_flags) {
+            Ok(sockets) => {
+                succeeded = true;
+                total_sockets += sockets.len();
+                for mut socket in sockets {
+                    let state = socket_state(&socket, &udp_peers);
+                    *scan.state_histogram.entry(state).or_default() += 1;
+                    if socket.associated_pids.is_empty() && wants_owner_fallback(state) {
+                        socket.associated_pids = owner_fallback.owners(&socket);
+                    }
+                    if socket.associated_pids.is_empty() {
+                        ownerless_sockets += 1;
                     }
-                    ProtocolSocketInfo::Udp(udp) => (
-                        Protocol::UDP,
-                        udp.local_addr,
-                        udp.local_port,
-                        None,
-                        None,
-                        SocketState::Listening, // UDP is connectionless
-                    ),
-                };
-
-            // Get associated PIDs
-            let pids: Vec<u32> = socket.associated_pids.iter().map(|&p| p as u32).collect();
-
-            if pids.is_empty() {
-                return None;
+                    scan.ports.extend(to_port_info(socket, &udp_peers));
+                }
             }
+            Err(e) => {
+                log::warn!("Failed to enumerate {} sockets: {}", label, e);
+                scan.warnings.push(format!("Failed to enumerate {} sockets: {}", label, e));
+            }
+        }
+    }
 
-            Some(PortInfo {
-                protocol,
-                local_address: local_addr.to_string(),
-                local_port,
-                remote_address: remote_addr.map(|a| a.to_string()),
-                remote_port,
-                state,
-                pids,
-            })
-        })
-        .collect();
+    if !succeeded {
+        bail!("{}", scan.warnings.join("; "));
+    }
+
+    // Unelevated processes on Windows may not be told who owns other users' sockets
+    if cfg!(windows) && looks_privilege_limited(total_sockets, ownerless_sockets) {
+        log::warn!(
+            "{} of {} sockets have no owning process; not running elevated?",
+            ownerless_sockets,
+            total_sockets
+        );
+        scan.limited_privileges = true;
+        scan.warnings.push(format!(
+            "{} of {} sockets were hidden because their owning process could not be \
+             determined; relaunch as administrator to see them",
+            ownerless_sockets, total_sockets
+        ));
+    }
 
     // Sort by local port for consistency
-    ports.sort_by_key(|p| p.local_port);
+    scan.ports.sort_by_key(|p| p.local_port);
+
+    Ok(scan)
+}
+
+/// Local port and state of every TCP socket, owned or not
+///
+/// Unlike `scan_ports`, sockets without an owning process are kept; TIME_WAIT
+/// sockets never have one, but still hold their local port.
+pub fn scan_tcp_socket_states() -> Result<Vec<(u16, SocketState)>> {
+    let sockets = get_sockets_info(
+        AddressFamilyFlags::IPV4 | AddressFamilyFlags::IPV6,
+        ProtocolFlags::TCP,
+    )?;
+    Ok(sockets
+        .into_iter()
+        .filter_map(|socket| match socket.protocol_socket_info {
+            ProtocolSocketInfo::Tcp(tcp) => {
+                Some((tcp.local_port, tcp_state_to_socket_state(&tcp.state)))
+            }
+            ProtocolSocketInfo::Udp(_) => None,
+        })
+        .collect())
+}
+
+/// State of a netstat2 socket, judging UDP sockets by their `udp_peers` entry
+fn socket_state(socket: &SocketInfo, udp_peers: &UdpPeers) -> SocketState {
+    match &socket.protocol_socket_info {
+        ProtocolSocketInfo::Tcp(tcp) => tcp_state_to_socket_state(&tcp.state),
+        ProtocolSocketInfo::Udp(udp) => {
+            udp_state(udp_peers.get(&(udp.local_addr, udp.local_port)).copied()).2
+        }
+    }
+}
+
+/// Whether an ownerless socket is worth the `/proc` walk of `SocketOwnerFallback`
+///
+/// Only listeners and live connections hold a port someone would want to free;
+/// closing and TIME_WAIT sockets are left ownerless rather than walking every
+/// process's descriptors for them.
+fn wants_owner_fallback(state: SocketState) -> bool {
+    matches!(state, SocketState::Listening | SocketState::Established)
+}
+
+/// Whether at least half of a non-trivial number of sockets lack an owning PID
+fn looks_privilege_limited(total_sockets: usize, ownerless_sockets: usize) -> bool {
+    total_sockets >= MIN_SOCKETS_FOR_PRIVILEGE_CHECK && ownerless_sockets * 2 >= total_sockets
+}
+
+/// Convert a netstat2 socket into a `PortInfo`, skipping sockets without an owning process
+///
+/// `udp_peers` supplies the remote end of connected UDP sockets, which netstat2 omits.
+fn to_port_info(socket: SocketInfo, udp_peers: &UdpPeers) -> Option<PortInfo> {
+    let (protocol, local_addr, local_port, remote_addr, remote_port, state) =
+        match &socket.protocol_socket_info {
+            ProtocolSocketInfo::Tcp(tcp) => {
+                let state = tcp_state_to_socket_state(&tcp.state);
+                (
+                    Protocol::TCP,
+                    tcp.local_addr,
+                    tcp.local_port,
+                    Some(tcp.remote_addr),
+                    Some(tcp.remote_port),
+                    state,
+                )
+            }
+            ProtocolSocketInfo::Udp(udp) => {
+                let peer = udp_peers.get(&(udp.local_addr, udp.local_port)).copied();
+                let (remote_addr, remote_port, state) = udp_state(peer);
+                (
+                    Protocol::UDP,
+                    udp.local_addr,
+                    udp.local_port,
+                    remote_addr,
+                    remote_port,
+                    state,
+                )
+            }
+        };
+
+    // Get associated PIDs
+    let pids: Vec<u32> = socket.associated_pids.iter().map(|&p| p as u32).collect();
+
+    if pids.is_empty() {
+        return None;
+    }
+
+    let flags = AddressFlags::of(local_addr);
+
+    // netstat2 drops IPv6 scope ids, so link-local addresses are rendered unscoped
+    Some(PortInfo {
+        protocol,
+        local_address: format_address(local_addr, None),
+        local_port,
+        remote_address: remote_addr.map(|a| format_address(a, None)),
+        remote_port,
+        state,
+        pids,
+        is_loopback: flags.is_loopback,
+        is_wildcard: flags.is_wildcard,
+        is_ipv6: flags.is_ipv6,
+        rx_bytes: None,
+        tx_bytes: None,
+        netns: None,
+    })
+}
+
+/// Classification of a local address, so consumers needn't re-parse the string
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AddressFlags {
+    pub is_loopback: bool,
+    pub is_wildcard: bool,
+    pub is_ipv6: bool,
+}
+
+impl AddressFlags {
+    /// Classify an address; IPv4-mapped IPv6 addresses count as their IPv4 form
+    /// for loopback and wildcard, but still report the IPv6 family
+    pub fn of(ip: IpAddr) -> Self {
+        let canonical = ip.to_canonical();
+        Self {
+            is_loopback: canonical.is_loopback(),
+            is_wildcard: canonical.is_unspecified(),
+            is_ipv6: ip.is_ipv6(),
+        }
+    }
+}
+
+/// Remote endpoints of connected UDP sockets keyed by (local ip, local port)
+type UdpPeers = HashMap<(IpAddr, u16), SocketAddr>;
+
+/// Classify a UDP socket by its remote endpoint
+///
+/// A socket `connect(2)`ed to a peer behaves like an established flow; unbound or
+/// wildcard remotes mean it accepts datagrams from anyone, i.e. it is listening.
+fn udp_state(peer: Option<SocketAddr>) -> (Option<IpAddr>, Option<u16>, SocketState) {
+    match peer {
+        Some(peer) if peer.port() != 0 && !peer.ip().is_unspecified() => {
+            (Some(peer.ip()), Some(peer.port()), SocketState::Established)
+        }
+        _ => (None, None, SocketState::Listening),
+    }
+}
+
+/// Read connected UDP sockets from `/proc/net/udp` and `/proc/net/udp6`
+#[cfg(target_os = "linux")]
+fn connected_udp_peers() -> UdpPeers {
+    let mut entries = Vec::new();
+    for (label, result) in [("udp", procfs::net::udp()), ("udp6", procfs::net::udp6())] {
+        match result {
+            Ok(found) => entries.extend(found),
+            Err(e) => log::debug!("Failed to read /proc/net/{}: {}", label, e),
+        }
+    }
+
+    entries
+        .into_iter()
+        .filter(|entry| udp_state(Some(entry.remote_address)).2 == SocketState::Established)
+        .map(|entry| {
+            let local = entry.local_address;
+            ((local.ip(), local.port()), entry.remote_address)
+        })
+        .collect()
+}
+
+/// netstat2 does not report UDP peers and there is no cheap source elsewhere
+#[cfg(not(target_os = "linux"))]
+fn connected_udp_peers() -> UdpPeers {
+    UdpPeers::new()
+}
+
+/// Socket identity shared by netstat2 and `/proc/net/*`: protocol, local end and, for
+/// TCP, remote end (netstat2 reports no UDP peers)
+#[cfg(target_os = "linux")]
+type SocketKey = (Protocol, SocketAddr, Option<SocketAddr>);
+
+/// Finds owners of sockets netstat2 returned without PIDs, which on Linux happens for
+/// other users' sockets even when their `/proc/<pid>/fd` is readable
+///
+/// The socket's inode is looked up in `/proc/net/{tcp,udp}{,6}` and matched against
+/// every process's `socket:[inode]` descriptors. Both tables are loaded on the first
+/// ownerless listener or connection and reused for the rest of the scan; how long
+/// that took is logged at debug level.
+#[derive(Default)]
+struct SocketOwnerFallback {
+    #[cfg(target_os = "linux")]
+    tables: Option<(HashMap<SocketKey, u64>, HashMap<u64, Vec<u32>>)>,
+}
+
+impl SocketOwnerFallback {
+    #[cfg(target_os = "linux")]
+    fn owners(&mut self, socket: &SocketInfo) -> Vec<u32> {
+        let key = match &socket.protocol_socket_info {
+            ProtocolSocketInfo::Tcp(tcp) => (
+                Protocol::TCP,
+                SocketAddr::new(tcp.local_addr, tcp.local_port),
+                Some(SocketAddr::new(tcp.remote_addr, tcp.remote_port)),
+            ),
+            ProtocolSocketInfo::Udp(udp) => {
+                (Protocol::UDP, SocketAddr::new(udp.local_addr, udp.local_port), None)
+            }
+        };
+
+        let (inodes, owners) = self.tables.get_or_insert_with(|| {
+            let started = std::time::Instant::now();
+            let tables = load_socket_owners();
+            log::debug!(
+                "Socket owner fallback read {} inodes and {} owned sockets in {:?}",
+                tables.0.len(),
+                tables.1.len(),
+                started.elapsed()
+            );
+            tables
+        });
+        inodes
+            .get(&key)
+            .and_then(|inode| owners.get(inode))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Other platforms have no per-process descriptor table to fall back on
+    #[cfg(not(target_os = "linux"))]
+    fn owners(&mut self, _socket: &SocketInfo) -> Vec<u32> {
+        Vec::new()
+    }
+}
+
+/// Read socket inodes from `/proc/net/*` and their owners from `/proc/*/fd`
+#[cfg(target_os = "linux")]
+fn load_socket_owners() -> (HashMap<SocketKey, u64>, HashMap<u64, Vec<u32>>) {
+    let mut inodes = HashMap::new();
+
+    for (label, result) in [("tcp", procfs::net::tcp()), ("tcp6", procfs::net::tcp6())] {
+        match result {
+            Ok(entries) => inodes.extend(
+                entries
+                    .into_iter()
+                    .filter(|entry| wants_owner_fallback(proc_tcp_state(&entry.state)))
+                    .map(|entry| {
+                        let key = (Protocol::TCP, entry.local_address, Some(entry.remote_address));
+                        (key, entry.inode)
+                    }),
+            ),
+            Err(e) => log::debug!("Failed to read /proc/net/{}: {}", label, e),
+        }
+    }
+    for (label, result) in [("udp", procfs::net::udp()), ("udp6", procfs::net::udp6())] {
+        match result {
+            Ok(entries) => inodes.extend(
+                entries
+                    .into_iter()
+                    .map(|entry| ((Protocol::UDP, entry.local_address, None), entry.inode)),
+            ),
+            Err(e) => log::debug!("Failed to read /proc/net/{}: {}", label, e),
+        }
+    }
+    // TIME_WAIT and other orphaned sockets have no inode, so nothing can own them
+    inodes.retain(|_, inode| *inode != 0);
+
+    let owners = match procfs::process::all_processes() {
+        Ok(processes) => socket_inode_owners(processes.flatten()),
+        Err(e) => {
+            log::debug!("Failed to list processes: {}", e);
+            HashMap::new()
+        }
+    };
+    (inodes, owners)
+}
+
+/// Map each socket inode to the processes holding a descriptor for it, PIDs sorted
+///
+/// Processes whose descriptors can't be read (another user's, without root) are
+/// skipped.
+#[cfg(target_os = "linux")]
+fn socket_inode_owners(
+    processes: impl IntoIterator<Item = impl std::borrow::Borrow<procfs::process::Process>>,
+) -> HashMap<u64, Vec<u32>> {
+    use procfs::process::FDTarget;
+
+    let mut owners: HashMap<u64, Vec<u32>> = HashMap::new();
+    for process in processes {
+        let process = process.borrow();
+        let Ok(fds) = process.fd() else {
+            continue;
+        };
+        for fd in fds.flatten() {
+            if let FDTarget::Socket(inode) = fd.target {
+                owners.entry(inode).or_default().push(process.pid as u32);
+            }
+        }
+    }
+    for pids in owners.values_mut() {
+        pids.sort_unstable();
+        pids.dedup();
+    }
+    owners
+}
+
+/// Enumerate sockets in network namespaces other than our own, e.g. containers'
+///
+/// Socket APIs only see the caller's namespace, so each other namespace is read
+/// through a member process's `/proc/<pid>/net/*` tables and its socket inodes are
+/// matched to the members' descriptors. Namespaces whose processes can't be inspected
+/// (another user's, without root) yield nothing. Walks every process, so it is opt-in,
+/// and gives up with an error as soon as `cancel` fires.
+#[cfg(target_os = "linux")]
+pub fn scan_namespace_ports(cancel: &CancellationToken) -> Result<Vec<PortInfo>> {
+    use procfs::process::{all_processes, Process};
+    use std::collections::BTreeMap;
+
+    let host_netns = std::fs::read_link("/proc/self/ns/net")?;
+
+    let mut namespaces: BTreeMap<String, Vec<Process>> = BTreeMap::new();
+    for process in all_processes()?.flatten() {
+        ensure!(!cancel.is_cancelled(), "Namespace scan cancelled");
+        let Ok(netns) = std::fs::read_link(format!("/proc/{}/ns/net", process.pid)) else {
+            continue;
+        };
+        if netns != host_netns {
+            let netns = netns.to_string_lossy().into_owned();
+            namespaces.entry(netns).or_default().push(process);
+        }
+    }
 
+    let mut ports = Vec::new();
+    for (netns, members) in namespaces {
+        ensure!(!cancel.is_cancelled(), "Namespace scan cancelled");
+        let owners = socket_inode_owners(&members);
+        if owners.is_empty() {
+            continue;
+        }
+
+        // Every member sees the same tables, but some may have exited meanwhile
+        let Some(sockets) = members.iter().find_map(|process| namespace_sockets(process).ok())
+        else {
+            continue;
+        };
+        for socket in sockets {
+            let Some(pids) = owners.get(&socket.inode) else {
+                continue;
+            };
+            ports.push(socket.into_port_info(pids.clone(), &netns));
+        }
+    }
+
+    ports.sort_by_key(|p| p.local_port);
     Ok(ports)
 }
 
+/// Other network namespaces are a Linux concept
+#[cfg(not(target_os = "linux"))]
+pub fn scan_namespace_ports(_cancel: &CancellationToken) -> Result<Vec<PortInfo>> {
+    Ok(Vec::new())
+}
+
+/// One row of a namespace's `/proc/<pid>/net/{tcp,udp}{,6}` tables
+#[cfg(target_os = "linux")]
+struct NamespaceSocket {
+    protocol: Protocol,
+    local: SocketAddr,
+    remote: SocketAddr,
+    /// TCP state; UDP sockets are classified from `remote` instead
+    tcp_state: Option<SocketState>,
+    inode: u64,
+}
+
+#[cfg(target_os = "linux")]
+impl NamespaceSocket {
+    fn into_port_info(self, pids: Vec<u32>, netns: &str) -> PortInfo {
+        let (remote_address, remote_port, state) = match self.tcp_state {
+            Some(state) => (Some(self.remote.ip()), Some(self.remote.port()), state),
+            None => udp_state(Some(self.remote)),
+        };
+        let flags = AddressFlags::of(self.local.ip());
+
+        PortInfo {
+            protocol: self.protocol,
+            local_address: format_address(self.local.ip(), None),
+            local_port: self.local.port(),
+            remote_address: remote_address.map(|a| format_address(a, None)),
+            remote_port,
+            state,
+            pids,
+            is_loopback: flags.is_loopback,
+            is_wildcard: flags.is_wildcard,
+            is_ipv6: flags.is_ipv6,
+            rx_bytes: None,
+            tx_bytes: None,
+            netns: Some(netns.to_string()),
+        }
+    }
+}
+
+/// Read the TCP and UDP tables of the namespace `process` lives in
+///
+/// The IPv4 TCP table must be readable; the others are skipped when missing, e.g.
+/// with IPv6 disabled.
+#[cfg(target_os = "linux")]
+fn namespace_sockets(
+    process: &procfs::process::Process,
+) -> procfs::ProcResult<Vec<NamespaceSocket>> {
+    let mut tcp = process.tcp()?;
+    tcp.extend(process.tcp6().unwrap_or_default());
+    let mut udp = process.udp().unwrap_or_default();
+    udp.extend(process.udp6().unwrap_or_default());
+
+    let tcp = tcp.into_iter().map(|entry| NamespaceSocket {
+        protocol: Protocol::TCP,
+        local: entry.local_address,
+        remote: entry.remote_address,
+        tcp_state: Some(proc_tcp_state(&entry.state)),
+        inode: entry.inode,
+    });
+    let udp = udp.into_iter().map(|entry| NamespaceSocket {
+        protocol: Protocol::UDP,
+        local: entry.local_address,
+        remote: entry.remote_address,
+        tcp_state: None,
+        inode: entry.inode,
+    });
+    Ok(tcp.chain(udp).collect())
+}
+
+/// Convert a procfs TCP state to our SocketState enum
+#[cfg(target_os = "linux")]
+fn proc_tcp_state(state: &procfs::net::TcpState) -> SocketState {
+    use procfs::net::TcpState;
+
+    match state {
+        TcpState::Listen => SocketState::Listening,
+        TcpState::Established => SocketState::Established,
+        TcpState::SynSent => SocketState::SynSent,
+        TcpState::SynRecv | TcpState::NewSynRecv => SocketState::SynReceived,
+        TcpState::FinWait1 => SocketState::FinWait1,
+        TcpState::FinWait2 => SocketState::FinWait2,
+        TcpState::CloseWait => SocketState::CloseWait,
+        TcpState::Closing => SocketState::Closing,
+        TcpState::LastAck => SocketState::LastAck,
+        TcpState::TimeWait => SocketState::TimeWait,
+        TcpState::Close => SocketState::Closed,
+    }
+}
+
+/// Whether a socket connects two loopback endpoints
+///
+/// Listeners never count, even when bound to loopback, since they are still exposed
+/// to local clients.
+pub fn is_loopback_connection(port: &PortInfo) -> bool {
+    port.state != SocketState::Listening
+        && port.is_loopback
+        && port
+            .remote_address
+            .as_deref()
+            .and_then(parse_address)
+            .is_some_and(|ip| ip.to_canonical().is_loopback())
+}
+
 /// Scan only listening ports (servers)
 pub fn scan_listening_ports() -> Result<Vec<PortInfo>> {
-    let all_ports = scan_ports()?;
+    let all_ports = scan_ports()?.ports;
     Ok(all_ports
         .into_iter()
         .filter(|p| matches!(p.state, SocketState::Listening))
@@ -72,13 +567,274 @@ pub fn scan_listening_ports() -> Result<Vec<PortInfo>> {
 
 /// Find processes using a specific port
 pub fn find_port_users(port: u16) -> Result<Vec<PortInfo>> {
-    let all_ports = scan_ports()?;
+    let all_ports = scan_ports()?.ports;
     Ok(all_ports
         .into_iter()
         .filter(|p| p.local_port == port)
         .collect())
 }
 
+/// Scan ports whose local port falls within `[start, end]` (inclusive)
+pub fn scan_port_range(start: u16, end: u16) -> Result<Vec<PortInfo>> {
+    ensure!(start <= end, "Invalid port range: {} > {}", start, end);
+
+    let all_ports = scan_ports()?.ports;
+    Ok(filter_port_range(all_ports, start, end))
+}
+
+/// Keep only ports whose local port falls within `[start, end]`
+fn filter_port_range(ports: Vec<PortInfo>, start: u16, end: u16) -> Vec<PortInfo> {
+    ports
+        .into_iter()
+        .filter(|p| (start..=end).contains(&p.local_port))
+        .collect()
+}
+
+/// A port search: one port (`80`), an inclusive range (`80-90`) or a prefix (`8*`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PortQuery {
+    Exact(u16),
+    Range(u16, u16),
+    /// Leading decimal digits, e.g. `8` for 8, 80 and 8080
+    Prefix(String),
+}
+
+impl PortQuery {
+    /// Parse a query, rejecting anything that is not one of the three forms
+    pub fn parse(query: &str) -> Result<Self> {
+        let query = query.trim();
+        let port = |text: &str| -> Result<u16> {
+            let text = text.trim();
+            ensure!(
+                !text.is_empty() && text.bytes().all(|b| b.is_ascii_digit()),
+                "'{}' is not a port number",
+                text
+            );
+            text.parse()
+                .map_err(|_| anyhow::anyhow!("{} is not a port number (0-65535)", text))
+        };
+
+        if let Some(prefix) = query.strip_suffix('*') {
+            let value = port(prefix)?;
+            // A leading zero can never match, since ports are printed without one
+            ensure!(value.to_string() == prefix, "'{}' is not a port number prefix", prefix);
+            return Ok(PortQuery::Prefix(prefix.to_string()));
+        }
+        if let Some((start, end)) = query.split_once('-') {
+            let (start, end) = (port(start)?, port(end)?);
+            ensure!(start <= end, "Invalid port range: {} > {}", start, end);
+            return Ok(PortQuery::Range(start, end));
+        }
+        Ok(PortQuery::Exact(port(query)?))
+    }
+
+    pub fn matches(&self, port: u16) -> bool {
+        match self {
+            PortQuery::Exact(exact) => port == *exact,
+            PortQuery::Range(start, end) => (*start..=*end).contains(&port),
+            PortQuery::Prefix(prefix) => port.to_string().starts_with(prefix.as_str()),
+        }
+    }
+}
+
+/// Listening sockets whose local port matches `query`
+pub fn search_listening_ports(query: &PortQuery) -> Result<Vec<PortInfo>> {
+    Ok(scan_listening_ports()?
+        .into_iter()
+        .filter(|p| query.matches(p.local_port))
+        .collect())
+}
+
+/// Check whether a port can be bound right now
+///
+/// Attempts to bind the port on the IPv4 and IPv6 loopback and wildcard addresses,
+/// releasing each socket immediately. This reflects real bindability, including
+/// OS-reserved and privileged ranges, which a socket scan cannot see.
+pub fn check_port_available(port: u16, protocol: Protocol) -> PortStatus {
+    use std::io::ErrorKind;
+    use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, TcpListener, UdpSocket};
+
+    let addresses: [SocketAddr; 4] = [
+        (Ipv4Addr::LOCALHOST, port).into(),
+        (Ipv4Addr::UNSPECIFIED, port).into(),
+        (Ipv6Addr::LOCALHOST, port).into(),
+        (Ipv6Addr::UNSPECIFIED, port).into(),
+    ];
+
+    let mut permission_denied = false;
+
+    for address in addresses {
+        let result = match protocol {
+            Protocol::TCP => TcpListener::bind(address).map(drop),
+            Protocol::UDP => UdpSocket::bind(address).map(drop),
+        };
+
+        match result {
+            Ok(()) => {}
+            Err(e) if e.kind() == ErrorKind::AddrInUse => return PortStatus::InUse,
+            Err(e) if e.kind() == ErrorKind::PermissionDenied => permission_denied = true,
+            // e.g. IPv6 disabled on this host - not a statement about the port
+            Err(e) => log::debug!("Bind check on {} failed: {}", address, e),
+        }
+    }
+
+    if permission_denied {
+        PortStatus::PermissionDenied
+    } else {
+        PortStatus::Free
+    }
+}
+
+/// Address of the interface the OS would route outbound traffic through
+///
+/// Connecting a UDP socket sends nothing; it only makes the OS pick a source address
+/// for the (documentation-range) destination. IPv4 is preferred over IPv6.
+pub fn primary_interface_ip() -> Result<IpAddr> {
+    use std::net::{Ipv4Addr, Ipv6Addr, UdpSocket};
+
+    let probes: [(SocketAddr, SocketAddr); 2] = [
+        ((Ipv4Addr::UNSPECIFIED, 0).into(), (Ipv4Addr::new(192, 0, 2, 1), 9).into()),
+        (
+            (Ipv6Addr::UNSPECIFIED, 0).into(),
+            (Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1), 9).into(),
+        ),
+    ];
+
+    for (bind, target) in probes {
+        let local = UdpSocket::bind(bind)
+            .and_then(|socket| socket.connect(target).map(|()| socket))
+            .and_then(|socket| socket.local_addr());
+        match local {
+            Ok(address) if !address.ip().is_loopback() && !address.ip().is_unspecified() => {
+                return Ok(address.ip());
+            }
+            Ok(address) => log::debug!("Route to {} uses {}", target, address.ip()),
+            Err(e) => log::debug!("No route to {}: {}", target, e),
+        }
+    }
+    bail!("No non-loopback network interface has a route")
+}
+
+/// Try a TCP connect to `address`, giving up after `timeout`
+///
+/// Errors other than a refusal or a timeout (e.g. the network is unreachable) are
+/// returned as-is.
+pub fn check_reachability(address: SocketAddr, timeout: Duration) -> io::Result<Reachability> {
+    match TcpStream::connect_timeout(&address, timeout) {
+        Ok(_) => Ok(Reachability::Connected),
+        Err(e) if e.kind() == io::ErrorKind::ConnectionRefused => Ok(Reachability::Refused),
+        Err(e) if matches!(e.kind(), io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock) => {
+            Ok(Reachability::Timeout)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Augment established TCP sockets with cumulative byte counters
+///
+/// On Linux the counters come from `ss --info`; elsewhere the fields are left as `None`.
+#[cfg(target_os = "linux")]
+pub fn enrich_socket_traffic(ports: &mut [PortInfo]) {
+    let output = match std::process::Command::new("ss")
+        .args(["--info", "--tcp", "--numeric", "--processes"])
+        .output()
+    {
+        Ok(output) if output.status.success() => output,
+        Ok(output) => {
+            log::debug!("ss exited with {}", output.status);
+            return;
+        }
+        Err(e) => {
+            log::debug!("Failed to run ss: {}", e);
+            return;
+        }
+    };
+
+    let counters = parse_ss_traffic(&String::from_utf8_lossy(&output.stdout));
+
+    for port in ports.iter_mut() {
+        if port.protocol != Protocol::TCP || port.state != SocketState::Established {
+            continue;
+        }
+
+        let key = match (
+            parse_address(&port.local_address),
+            port.remote_address.as_deref().and_then(parse_address),
+            port.remote_port,
+        ) {
+            (Some(local), Some(remote), Some(remote_port)) => {
+                (local, port.local_port, remote, remote_port)
+            }
+            _ => continue,
+        };
+
+        if let Some(&(rx, tx)) = counters.get(&key) {
+            port.rx_bytes = rx;
+            port.tx_bytes = tx;
+        }
+    }
+}
+
+/// Per-socket byte counters are not available on this platform
+#[cfg(not(target_os = "linux"))]
+pub fn enrich_socket_traffic(_ports: &mut [PortInfo]) {}
+
+/// Socket key used to match `ss` output: (local ip, local port, remote ip, remote port)
+#[cfg(target_os = "linux")]
+type SocketKey = (IpAddr, u16, IpAddr, u16);
+
+/// Parse `ss --info` output into (rx_bytes, tx_bytes) keyed by socket endpoints
+#[cfg(target_os = "linux")]
+fn parse_ss_traffic(output: &str) -> HashMap<SocketKey, (Option<u64>, Option<u64>)> {
+    let mut counters = HashMap::new();
+    let mut current: Option<SocketKey> = None;
+
+    for line in output.lines() {
+        // Info lines are indented beneath the socket line they describe
+        if line.starts_with(char::is_whitespace) {
+            let Some(key) = current.take() else {
+                continue;
+            };
+
+            let mut rx = None;
+            let mut tx = None;
+            for field in line.split_whitespace() {
+                if let Some((name, value)) = field.split_once(':') {
+                    match name {
+                        "bytes_received" => rx = value.parse().ok(),
+                        "bytes_sent" => tx = value.parse().ok(),
+                        "bytes_acked" if tx.is_none() => tx = value.parse().ok(),
+                        _ => {}
+                    }
+                }
+            }
+            counters.insert(key, (rx, tx));
+            continue;
+        }
+
+        // Socket line: State Recv-Q Send-Q Local:Port Peer:Port [Process]
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        current = match (fields.get(3), fields.get(4)) {
+            (Some(local), Some(peer)) => match (parse_ss_endpoint(local), parse_ss_endpoint(peer)) {
+                (Some((local_ip, local_port)), Some((peer_ip, peer_port))) => {
+                    Some((local_ip, local_port, peer_ip, peer_port))
+                }
+                _ => None,
+            },
+            _ => None,
+        };
+    }
+
+    counters
+}
+
+/// Parse an `ss` endpoint such as `10.0.0.1:443`, `[::1]:8080` or `[fe80::1%eth0]:22`
+#[cfg(target_os = "linux")]
+fn parse_ss_endpoint(endpoint: &str) -> Option<(IpAddr, u16)> {
+    let (addr, port) = endpoint.rsplit_once(':')?;
+    Some((parse_address(addr)?, port.parse().ok()?))
+}
+
 /// Convert netstat2 TCP state to our SocketState enum
 fn tcp_state_to_socket_state(state: &netstat2::TcpState) -> SocketState {
     match state {
@@ -100,13 +856,233 @@ fn tcp_state_to_socket_state(state: &netstat2::TcpState) -> SocketState {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fixtures::listener;
 
     #[test]
     fn test_scan_ports() {
         let result = scan_ports();
         assert!(result.is_ok());
         // Should find at least some ports on any system
-        let ports = result.unwrap();
-        println!("Found {} ports", ports.len());
+        let scan = result.unwrap();
+        println!("Found {} ports ({} warnings)", scan.ports.len(), scan.warnings.len());
+        // Ownerless sockets are counted but not returned
+        assert!(scan.state_histogram.values().sum::<usize>() >= scan.ports.len());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_ss_traffic() {
+        let output = "\
+State Recv-Q Send-Q Local Address:Port Peer Address:Port Process
+ESTAB 0      0      192.168.1.5:22     192.168.1.10:54321 users:((\"sshd\",pid=123,fd=4))
+\t cubic wscale:7,7 rto:204 rtt:0.5/0.25 bytes_sent:4096 bytes_acked:4097 bytes_received:1024 segs_out:10
+ESTAB 0      0      [::ffff:127.0.0.1]:5432 [::ffff:127.0.0.1]:40000
+\t cubic bytes_acked:77 bytes_received:99
+";
+        let counters = parse_ss_traffic(output);
+
+        let ssh = (
+            "192.168.1.5".parse().unwrap(),
+            22,
+            "192.168.1.10".parse().unwrap(),
+            54321,
+        );
+        assert_eq!(counters.get(&ssh), Some(&(Some(1024), Some(4096))));
+
+        let pg = (
+            "::ffff:127.0.0.1".parse().unwrap(),
+            5432,
+            "::ffff:127.0.0.1".parse().unwrap(),
+            40000,
+        );
+        assert_eq!(counters.get(&pg), Some(&(Some(99), Some(77))));
+    }
+
+    #[test]
+    fn test_address_flags() {
+        let flags = |addr: &str| AddressFlags::of(addr.parse().unwrap());
+
+        assert_eq!(
+            flags("0.0.0.0"),
+            AddressFlags { is_loopback: false, is_wildcard: true, is_ipv6: false }
+        );
+        assert_eq!(
+            flags("::1"),
+            AddressFlags { is_loopback: true, is_wildcard: false, is_ipv6: true }
+        );
+        assert_eq!(
+            flags("::ffff:127.0.0.1"),
+            AddressFlags { is_loopback: true, is_wildcard: false, is_ipv6: true }
+        );
+        assert_eq!(flags("192.168.1.5"), AddressFlags::default());
+        assert_eq!(
+            flags("fe80::1"),
+            AddressFlags { is_loopback: false, is_wildcard: false, is_ipv6: true }
+        );
+    }
+
+    #[test]
+    fn test_wants_owner_fallback() {
+        assert!(wants_owner_fallback(SocketState::Listening));
+        assert!(wants_owner_fallback(SocketState::Established));
+        assert!(!wants_owner_fallback(SocketState::TimeWait));
+        assert!(!wants_owner_fallback(SocketState::CloseWait));
+    }
+
+    #[test]
+    fn test_looks_privilege_limited() {
+        assert!(looks_privilege_limited(40, 30));
+        assert!(looks_privilege_limited(40, 20));
+        assert!(!looks_privilege_limited(40, 5));
+        // Too few sockets to tell
+        assert!(!looks_privilege_limited(4, 4));
+    }
+
+    #[test]
+    fn test_is_loopback_connection() {
+        let socket = |local: &str, remote: Option<&str>, state| PortInfo {
+            remote_address: remote.map(str::to_string),
+            remote_port: remote.map(|_| 40000),
+            state,
+            ..listener(local, 5432, 1)
+        };
+
+        let established = SocketState::Established;
+        assert!(is_loopback_connection(&socket("127.0.0.1", Some("127.0.0.1"), established)));
+        assert!(is_loopback_connection(&socket("::1", Some("::ffff:127.0.0.1"), established)));
+        assert!(!is_loopback_connection(&socket("127.0.0.1", Some("10.0.0.2"), established)));
+        assert!(!is_loopback_connection(&socket("10.0.0.1", Some("127.0.0.1"), established)));
+        // Loopback listeners stay visible
+        let listener = socket("127.0.0.1", Some("0.0.0.0"), SocketState::Listening);
+        assert!(!is_loopback_connection(&listener));
+        assert!(!is_loopback_connection(&socket("127.0.0.1", None, SocketState::Listening)));
+    }
+
+    #[test]
+    fn test_udp_state() {
+        let peer: SocketAddr = "10.0.0.53:53".parse().unwrap();
+        assert_eq!(
+            udp_state(Some(peer)),
+            (Some(peer.ip()), Some(53), SocketState::Established)
+        );
+
+        let wildcard: SocketAddr = "0.0.0.0:0".parse().unwrap();
+        assert_eq!(udp_state(Some(wildcard)), (None, None, SocketState::Listening));
+        let unspecified_v6: SocketAddr = "[::]:0".parse().unwrap();
+        assert_eq!(udp_state(Some(unspecified_v6)), (None, None, SocketState::Listening));
+        assert_eq!(udp_state(None), (None, None, SocketState::Listening));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_namespace_socket_into_port_info() {
+        let listener = NamespaceSocket {
+            protocol: Protocol::TCP,
+            local: "0.0.0.0:8080".parse().unwrap(),
+            remote: "0.0.0.0:0".parse().unwrap(),
+            tcp_state: Some(proc_tcp_state(&procfs::net::TcpState::Listen)),
+            inode: 42,
+        };
+        let info = listener.into_port_info(vec![7], "net:[4026532280]");
+        assert_eq!(info.local_port, 8080);
+        assert_eq!(info.state, SocketState::Listening);
+        assert!(info.is_wildcard);
+        assert_eq!(info.pids, vec![7]);
+        assert_eq!(info.netns.as_deref(), Some("net:[4026532280]"));
+
+        let udp = NamespaceSocket {
+            protocol: Protocol::UDP,
+            local: "127.0.0.1:5353".parse().unwrap(),
+            remote: "0.0.0.0:0".parse().unwrap(),
+            tcp_state: None,
+            inode: 43,
+        };
+        let info = udp.into_port_info(vec![7], "net:[4026532280]");
+        assert_eq!(info.state, SocketState::Listening);
+        assert_eq!(info.remote_address, None);
+        assert!(info.is_loopback);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_load_socket_owners() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let local = listener.local_addr().unwrap();
+
+        let (inodes, owners) = load_socket_owners();
+        let unspecified: SocketAddr = "0.0.0.0:0".parse().unwrap();
+        let inode = inodes[&(Protocol::TCP, local, Some(unspecified))];
+        assert_eq!(owners[&inode], vec![std::process::id()]);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_connected_udp_peers() {
+        let server = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+        let client = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+        client.connect(server.local_addr().unwrap()).unwrap();
+        let local = client.local_addr().unwrap();
+        let remote = server.local_addr().unwrap();
+
+        let peers = connected_udp_peers();
+        assert_eq!(peers.get(&(local.ip(), local.port())), Some(&remote));
+        assert!(!peers.contains_key(&(remote.ip(), remote.port())));
+    }
+
+    #[test]
+    fn test_check_port_available() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        assert_eq!(check_port_available(port, Protocol::TCP), PortStatus::InUse);
+
+        drop(listener);
+        assert_eq!(check_port_available(port, Protocol::TCP), PortStatus::Free);
+
+        let socket = std::net::UdpSocket::bind("0.0.0.0:0").unwrap();
+        let port = socket.local_addr().unwrap().port();
+        assert_eq!(check_port_available(port, Protocol::UDP), PortStatus::InUse);
+    }
+
+    #[test]
+    fn test_check_reachability() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let timeout = Duration::from_secs(1);
+        let status = check_reachability(address, timeout).unwrap();
+        assert_eq!(status, Reachability::Connected);
+
+        drop(listener);
+        assert_eq!(check_reachability(address, timeout).unwrap(), Reachability::Refused);
+    }
+
+    #[test]
+    fn test_scan_port_range_rejects_inverted_range() {
+        assert!(scan_port_range(9000, 3000).is_err());
+    }
+
+    #[test]
+    fn test_filter_port_range_is_inclusive() {
+        let port = |local_port| listener("127.0.0.1", local_port, 1);
+
+        let ports = vec![port(22), port(3000), port(8080), port(9000), port(9001)];
+        let filtered = filter_port_range(ports, 3000, 9000);
+        let ports: Vec<u16> = filtered.iter().map(|p| p.local_port).collect();
+        assert_eq!(ports, vec![3000, 8080, 9000]);
+    }
+
+    #[test]
+    fn test_port_query() {
+        assert_eq!(PortQuery::parse("80").unwrap(), PortQuery::Exact(80));
+        assert_eq!(PortQuery::parse(" 80 - 90 ").unwrap(), PortQuery::Range(80, 90));
+        assert_eq!(PortQuery::parse("8*").unwrap(), PortQuery::Prefix("8".to_string()));
+
+        let prefix = PortQuery::parse("80*").unwrap();
+        assert!(prefix.matches(80) && prefix.matches(8080) && prefix.matches(800));
+        assert!(!prefix.matches(8) && !prefix.matches(180));
+        assert!(PortQuery::parse("80-90").unwrap().matches(90));
+
+        for malformed in ["", "*", "http", "-80", "90-80", "65536", "08*", "8*0", "1-2-3"] {
+            assert!(PortQuery::parse(malformed).is_err(), "{} should be rejected", malformed);
+        }
     }
 }
@@ -1,13 +1,32 @@
 // Process Info Module - Cross-platform process metadata gathering
 use crate::models::ProcessInfo;
 use chrono::{DateTime, Utc};
-use sysinfo::{Pid, System, Users};
-use std::collections::HashMap;
+use sysinfo::{
+    Pid, Process, ProcessRefreshKind, ProcessStatus, ProcessesToUpdate, System, Uid, UpdateKind,
+    Users,
+};
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+/// Minimum time between user list reloads triggered by an unknown UID
+///
+/// Some UIDs never resolve, e.g. container users missing from the host's passwd file
+/// or, on Windows, service and domain SIDs that are not local accounts, so reloading on
+/// every miss would reload on every scan.
+pub const USER_REFRESH_MIN_INTERVAL: Duration = Duration::from_secs(30);
 
 /// Process information gatherer
+///
+/// Holds a snapshot of the process table; call `refresh_processes_only` (or
+/// `refresh_specific` for a few PIDs) once per scan and then use the batch lookups,
+/// which read from the snapshot without touching the OS.
 pub struct ProcessEnricher {
     system: System,
     users: Users,
+    /// User names keyed by UID, rebuilt on every refresh
+    user_names: HashMap<Uid, String>,
+    /// When the user list was last reloaded
+    users_refreshed_at: Instant,
 }
 
 impl ProcessEnricher {
@@ -15,26 +34,117 @@ impl ProcessEnricher {
         let mut system = System::new_all();
         system.refresh_all();
         let users = Users::new_with_refreshed_list();
+        let user_names = Self::index_users(&users);
         
-        Self { system, users }
+        Self {
+            system,
+            users,
+            user_names,
+            users_refreshed_at: Instant::now(),
+        }
     }
 
-    /// Refresh system information
+    /// Refresh system information, including disks, networks and components
+    ///
+    /// Scans only need the process table; prefer `refresh_processes_only`.
     pub fn refresh(&mut self) {
         self.system.refresh_all();
+        self.refresh_users();
+    }
+
+    /// Refresh the fields `ProcessInfo` needs for every process, dropping exited ones
+    ///
+    /// Skips the system-wide memory, CPU, disk and network refreshes `refresh` does.
+    /// The user list is only reloaded when a process runs as a UID it doesn't know.
+    pub fn refresh_processes_only(&mut self) {
+        self.system
+            .refresh_processes_specifics(ProcessesToUpdate::All, process_refresh_kind());
+        self.refresh_users_if_unknown(None);
+    }
+
+    /// Refresh just the given PIDs, e.g. before looking up a single process
+    ///
+    /// Entries for PIDs that exited would otherwise be left as they were, so if any PID
+    /// could not be refreshed this falls back to `refresh_processes_only`.
+    pub fn refresh_specific(&mut self, pids: &[u32]) {
+        let pids: Vec<Pid> = pids.iter().map(|&pid| Pid::from_u32(pid)).collect();
+        let updated = self
+            .system
+            .refresh_processes_specifics(ProcessesToUpdate::Some(&pids), process_refresh_kind());
+        if updated < pids.len() {
+            self.refresh_processes_only();
+        } else {
+            self.refresh_users_if_unknown(Some(&pids));
+        }
+    }
+
+    /// Reload the user list, e.g. to pick up accounts created since startup
+    ///
+    /// On Windows, UIDs are SIDs and the list only holds local accounts, so processes
+    /// run by services or domain users may still show as "Unknown" afterwards.
+    pub fn refresh_users(&mut self) {
+        self.users.refresh_list();
+        self.user_names = Self::index_users(&self.users);
+        self.users_refreshed_at = Instant::now();
+    }
+
+    /// Reload the user list if one of `pids` (or any process) runs as an unknown UID
+    ///
+    /// Misses reload at most once per `USER_REFRESH_MIN_INTERVAL`.
+    fn refresh_users_if_unknown(&mut self, pids: Option<&[Pid]>) {
+        if self.users_refreshed_at.elapsed() < USER_REFRESH_MIN_INTERVAL {
+            return;
+        }
+
+        let processes = self.system.processes();
+        let unknown_user = match pids {
+            Some(pids) => pids
+                .iter()
+                .filter_map(|pid| processes.get(pid))
+                .filter_map(|process| process.user_id())
+                .any(|uid| !self.user_names.contains_key(uid)),
+            None => processes
+                .values()
+                .filter_map(|process| process.user_id())
+                .any(|uid| !self.user_names.contains_key(uid)),
+        };
+        if unknown_user {
+            self.refresh_users();
+        }
+    }
+
+    fn index_users(users: &Users) -> HashMap<Uid, String> {
+        users
+            .iter()
+            .map(|u| (u.id().clone(), u.name().to_string()))
+            .collect()
     }
 
     /// Get process information by PID
+    ///
+    /// On macOS, falls back to `ps` for processes sysinfo cannot read.
     pub fn get_process_info(&self, pid: u32) -> Option<ProcessInfo> {
-        let sysinfo_pid = Pid::from_u32(pid);
-        let process = self.system.process(sysinfo_pid)?;
+        match self.system.process(Pid::from_u32(pid)) {
+            Some(process) => Some(self.to_process_info(pid, process)),
+            None => ps_process_info(pid),
+        }
+    }
 
+    /// Build a `ProcessInfo` from an entry in the loaded process table
+    fn to_process_info(&self, pid: u32, process: &Process) -> ProcessInfo {
         let user_name = process
             .user_id()
-            .and_then(|uid| self.users.get_user_by_id(uid))
-            .map(|u| u.name().to_string())
+            .and_then(|uid| self.user_names.get(uid))
+            .cloned()
             .unwrap_or_else(|| "Unknown".to_string());
 
+        let disk_usage = process.disk_usage();
+        let args: Vec<String> = process
+            .cmd()
+            .iter()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect();
+
         let start_time = if process.start_time() > 0 {
             Some(DateTime::from_timestamp(process.start_time() as i64, 0)
                 .unwrap_or_else(|| Utc::now()))
@@ -42,23 +152,36 @@ impl ProcessEnricher {
             None
         };
 
-        Some(ProcessInfo {
+        ProcessInfo {
             pid,
             name: process.name().to_string_lossy().to_string(),
             exe_path: process.exe().map(|p| p.to_string_lossy().to_string()),
-            command_line: Some(process.cmd().iter().map(|s| s.to_string_lossy()).collect::<Vec<_>>().join(" ")),
+            command_line: Some(args.join(" ")),
+            args,
             user: user_name,
             memory_usage: process.memory(),
             cpu_usage: process.cpu_usage(),
+            // Cumulative totals; `read_bytes`/`written_bytes` only cover the last refresh
+            disk_read_bytes: disk_usage.total_read_bytes,
+            disk_written_bytes: disk_usage.total_written_bytes,
             start_time,
             parent_pid: process.parent().map(|p| p.as_u32()),
-        })
+        }
     }
 
     /// Get information for multiple PIDs
+    ///
+    /// Walks the loaded process table once instead of looking up each PID. PIDs
+    /// missing from the table are left out; on macOS, `ps_processes_info` can fill
+    /// them in with a single `ps` call.
     pub fn get_processes_info(&self, pids: &[u32]) -> HashMap<u32, ProcessInfo> {
-        pids.iter()
-            .filter_map(|&pid| self.get_process_info(pid).map(|info| (pid, info)))
+        let wanted: HashSet<u32> = pids.iter().copied().collect();
+
+        self.system
+            .processes()
+            .iter()
+            .filter(|(pid, _)| wanted.contains(&pid.as_u32()))
+            .map(|(pid, process)| (pid.as_u32(), self.to_process_info(pid.as_u32(), process)))
             .collect()
     }
 
@@ -71,17 +194,423 @@ impl ProcessEnricher {
             .collect()
     }
 
+    /// Check whether a PID is present in the loaded process table
+    pub fn process_exists(&self, pid: u32) -> bool {
+        self.system.process(Pid::from_u32(pid)).is_some()
+    }
+
+    /// Parent of any PID in the loaded process table, without building its `ProcessInfo`
+    pub fn parent_pid(&self, pid: u32) -> Option<u32> {
+        self.system
+            .process(Pid::from_u32(pid))
+            .and_then(|process| process.parent())
+            .map(|parent| parent.as_u32())
+    }
+
+    /// Name of any PID in the loaded process table, without building its `ProcessInfo`
+    pub fn process_name(&self, pid: u32) -> Option<String> {
+        self.system
+            .process(Pid::from_u32(pid))
+            .map(|process| process.name().to_string_lossy().into_owned())
+    }
+
+    /// The process and its ancestors, from `pid` up to the top-level one
+    ///
+    /// Empty if `pid` is not in the loaded process table.
+    pub fn ancestor_chain(&self, pid: u32) -> Vec<ProcessInfo> {
+        ancestor_chain(pid, |pid| self.get_process_info(pid))
+    }
+
+    /// PIDs among `infos` whose parent is no longer in the loaded process table
+    pub fn orphaned_pids<'a>(
+        &self,
+        infos: impl IntoIterator<Item = &'a ProcessInfo>,
+    ) -> HashSet<u32> {
+        infos
+            .into_iter()
+            .filter(|info| is_orphaned(info.parent_pid, |ppid| self.process_exists(ppid)))
+            .map(|info| info.pid)
+            .collect()
+    }
+
     /// Check if a process is a Docker proxy
     pub fn is_docker_proxy(&self, pid: u32) -> bool {
-        if let Some(info) = self.get_process_info(pid) {
-            let name_lower = info.name.to_lowercase();
-            name_lower.contains("docker")
-                || name_lower.contains("com.docker")
-                || name_lower == "vpnkit"
-                || name_lower == "dockerd"
-                || name_lower.contains("containerd")
-        } else {
-            false
+        self.system
+            .process(Pid::from_u32(pid))
+            .map(|process| is_docker_process_name(&process.name().to_string_lossy()))
+            .unwrap_or(false)
+    }
+}
+
+/// Per-process fields read by `to_process_info`; exe, command line and user are kept once known
+fn process_refresh_kind() -> ProcessRefreshKind {
+    ProcessRefreshKind::new()
+        .with_memory()
+        .with_cpu()
+        .with_disk_usage()
+        .with_exe(UpdateKind::OnlyIfNotSet)
+        .with_cmd(UpdateKind::OnlyIfNotSet)
+        .with_user(UpdateKind::OnlyIfNotSet)
+}
+
+/// Seconds a process has been running, or `None` if its start time is unknown
+///
+/// A start time after `now` (clock skew) also yields `None` rather than wrapping.
+pub fn uptime_secs(start_time: Option<DateTime<Utc>>, now: DateTime<Utc>) -> Option<u64> {
+    let elapsed = now.signed_duration_since(start_time?).num_seconds();
+    u64::try_from(elapsed).ok()
+}
+
+/// Format an uptime as a short human-readable age, e.g. "45s", "12m", "3h 5m", "2d 4h"
+pub fn format_age(secs: u64) -> String {
+    let days = secs / 86_400;
+    let hours = (secs % 86_400) / 3_600;
+    let minutes = (secs % 3_600) / 60;
+
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m", minutes)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+/// Whether a process's parent has exited without it being reparented
+///
+/// A parent of 1 means the process was adopted by init, which is the normal fate of
+/// daemons, so it does not count as orphaned.
+pub fn is_orphaned(parent_pid: Option<u32>, parent_exists: impl Fn(u32) -> bool) -> bool {
+    match parent_pid {
+        Some(0) | Some(1) | None => false,
+        Some(ppid) => !parent_exists(ppid),
+    }
+}
+
+/// Which of `pids` are running right now, zombies excluded
+///
+/// Reads just those PIDs into a fresh table, so the answer is never stale the way a
+/// targeted refresh of a kept table is: that keeps the entries of exited PIDs.
+pub fn live_pids(pids: &[u32]) -> HashSet<u32> {
+    let wanted: Vec<Pid> = pids.iter().map(|&pid| Pid::from_u32(pid)).collect();
+    let mut system = System::new();
+    system.refresh_processes_specifics(ProcessesToUpdate::Some(&wanted), ProcessRefreshKind::new());
+    system
+        .processes()
+        .iter()
+        .filter(|(_, process)| process.status() != ProcessStatus::Zombie)
+        .map(|(pid, _)| pid.as_u32())
+        .collect()
+}
+
+/// Upper bound on the ancestor walk, far beyond any real process tree depth
+const MAX_ANCESTOR_DEPTH: usize = 256;
+
+/// Walk `parent_pid` links from `pid` until PID 1, a missing parent, a cycle or the depth cap
+pub fn ancestor_chain(pid: u32, lookup: impl Fn(u32) -> Option<ProcessInfo>) -> Vec<ProcessInfo> {
+    let mut chain = Vec::new();
+    let mut visited = HashSet::new();
+    let mut next = Some(pid);
+
+    while let Some(pid) = next {
+        if chain.len() >= MAX_ANCESTOR_DEPTH || !visited.insert(pid) {
+            break;
+        }
+        let Some(info) = lookup(pid) else {
+            break;
+        };
+
+        next = match info.parent_pid {
+            Some(ppid) if pid > 1 && ppid != 0 => Some(ppid),
+            _ => None,
+        };
+        chain.push(info);
+    }
+
+    chain
+}
+
+/// Check whether a process name belongs to Docker's port forwarding machinery
+pub fn is_docker_process_name(name: &str) -> bool {
+    let name_lower = name.to_lowercase();
+    name_lower.contains("docker")
+        || name_lower.contains("com.docker")
+        || name_lower == "vpnkit"
+        || name_lower == "dockerd"
+        || name_lower.contains("containerd")
+}
+
+/// Substrings that mark an environment variable as holding a secret
+const SECRET_KEY_PATTERNS: [&str; 4] = ["TOKEN", "SECRET", "PASSWORD", "KEY"];
+
+/// Value shown in place of a redacted secret
+pub const REDACTED_VALUE: &str = "<redacted>";
+
+/// Read a process's environment from `/proc/<pid>/environ`
+///
+/// Only readable for processes owned by the current user unless running as root.
+#[cfg(target_os = "linux")]
+pub fn read_process_env(pid: u32) -> std::io::Result<HashMap<String, String>> {
+    let raw = std::fs::read(format!("/proc/{}/environ", pid))?;
+    Ok(parse_environ(&raw))
+}
+
+/// Reading another process's environment is not supported on this platform
+#[cfg(not(target_os = "linux"))]
+pub fn read_process_env(_pid: u32) -> std::io::Result<HashMap<String, String>> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "Process environment is only available on Linux",
+    ))
+}
+
+/// Resolve a process's working directory from `/proc/<pid>/cwd`
+#[cfg(target_os = "linux")]
+pub fn read_process_cwd(pid: u32) -> std::io::Result<std::path::PathBuf> {
+    std::fs::read_link(format!("/proc/{}/cwd", pid))
+}
+
+/// Reading another process's working directory is not supported on this platform
+#[cfg(not(target_os = "linux"))]
+pub fn read_process_cwd(_pid: u32) -> std::io::Result<std::path::PathBuf> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "Process working directory is only available on Linux",
+    ))
+}
+
+/// Find the systemd unit a process runs in from `/proc/<pid>/cgroup`
+///
+/// Returns None when the process is not inside a `.service` or `.scope` unit, which
+/// includes hosts that don't run systemd.
+#[cfg(target_os = "linux")]
+pub fn read_systemd_unit(pid: u32) -> std::io::Result<Option<String>> {
+    let contents = std::fs::read_to_string(format!("/proc/{}/cgroup", pid))?;
+    Ok(parse_systemd_unit(&contents))
+}
+
+/// systemd units only exist on Linux
+#[cfg(not(target_os = "linux"))]
+pub fn read_systemd_unit(_pid: u32) -> std::io::Result<Option<String>> {
+    Ok(None)
+}
+
+/// Extract the innermost `.service`/`.scope` unit from a cgroup file
+///
+/// Uses the unified (cgroup v2) hierarchy or, on v1 hosts, systemd's named hierarchy.
+#[cfg(any(target_os = "linux", test))]
+fn parse_systemd_unit(contents: &str) -> Option<String> {
+    contents.lines().find_map(|line| {
+        // hierarchy-ID:controller-list:cgroup-path
+        let mut fields = line.splitn(3, ':');
+        let (_, controllers, path) = (fields.next()?, fields.next()?, fields.next()?);
+        if !controllers.is_empty() && controllers != "name=systemd" {
+            return None;
+        }
+
+        path.rsplit('/')
+            .find(|component| component.ends_with(".service") || component.ends_with(".scope"))
+            .map(str::to_string)
+    })
+}
+
+/// Id of the container a process runs in, from `/proc/<pid>/cgroup`
+///
+/// Recognizes Docker, Podman, containerd and CRI-O cgroups, including Kubernetes pods.
+/// Returns None for processes outside a container and on other platforms, where
+/// containers run in a VM the host cannot see into.
+#[cfg(target_os = "linux")]
+pub fn get_container_id_for_pid(pid: u32) -> std::io::Result<Option<String>> {
+    let contents = std::fs::read_to_string(format!("/proc/{}/cgroup", pid))?;
+    Ok(parse_container_id(&contents))
+}
+
+/// Container cgroups are only visible from the host on Linux
+#[cfg(not(target_os = "linux"))]
+pub fn get_container_id_for_pid(_pid: u32) -> std::io::Result<Option<String>> {
+    Ok(None)
+}
+
+/// Extract the innermost container id from a cgroup file
+///
+/// Runtimes name the cgroup after the full 64-character id, either bare
+/// (`/docker/<id>`, `/kubepods/burstable/pod<uid>/<id>`) or as a systemd scope
+/// (`docker-<id>.scope`, `cri-containerd-<id>.scope`, `crio-<id>.scope`,
+/// `libpod-<id>.scope`).
+#[cfg(any(target_os = "linux", test))]
+fn parse_container_id(contents: &str) -> Option<String> {
+    const SCOPE_PREFIXES: [&str; 4] = ["docker-", "cri-containerd-", "crio-", "libpod-"];
+
+    contents.lines().find_map(|line| {
+        let path = line.splitn(3, ':').nth(2)?;
+        path.rsplit('/').find_map(|component| {
+            let component = component.strip_suffix(".scope").unwrap_or(component);
+            let id = SCOPE_PREFIXES
+                .iter()
+                .find_map(|prefix| component.strip_prefix(prefix))
+                .unwrap_or(component);
+            let is_id = id.len() == 64 && id.bytes().all(|b| b.is_ascii_hexdigit());
+            is_id.then(|| id.to_ascii_lowercase())
+        })
+    })
+}
+
+/// Names of the Windows services hosted by a process, e.g. `RpcSs` inside svchost
+///
+/// Asks the Service Control Manager through `tasklist /svc`. Empty when the PID hosts
+/// no service or is not running.
+#[cfg(windows)]
+pub fn read_windows_services(pid: u32) -> std::io::Result<Vec<String>> {
+    let filter = format!("PID eq {}", pid);
+    let output = std::process::Command::new("tasklist")
+        .args(["/svc", "/fo", "csv", "/nh", "/fi", &filter])
+        .output()?;
+    if !output.status.success() {
+        return Err(std::io::Error::other(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(parse_tasklist_services(pid, &String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Windows services only exist on Windows
+#[cfg(not(windows))]
+pub fn read_windows_services(_pid: u32) -> std::io::Result<Vec<String>> {
+    Ok(Vec::new())
+}
+
+/// Extract the services column for `pid` from `tasklist /svc /fo csv /nh` output
+///
+/// Rows look like `"svchost.exe","1234","RpcEptMapper,RpcSs"`, with `N/A` for
+/// processes hosting no service. Anything else, such as the "no tasks" notice, is
+/// ignored.
+#[cfg(any(windows, test))]
+fn parse_tasklist_services(pid: u32, output: &str) -> Vec<String> {
+    let pid = pid.to_string();
+    output
+        .lines()
+        .find_map(|line| {
+            let line = line.trim().strip_prefix('"')?.strip_suffix('"')?;
+            let fields: Vec<&str> = line.split("\",\"").collect();
+            match fields.as_slice() {
+                [_, row_pid, services] if *row_pid == pid => Some(*services),
+                _ => None,
+            }
+        })
+        .map(|services| {
+            services
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty() && *name != "N/A")
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Look up name and user of a process sysinfo cannot read
+fn ps_process_info(pid: u32) -> Option<ProcessInfo> {
+    ps_processes_info(&[pid]).remove(&pid)
+}
+
+/// Look up name and user of processes sysinfo cannot read, with one `ps` call
+///
+/// Without elevation, macOS hides other users' processes from sysinfo, but `ps` can
+/// still report their name and owner. Memory and CPU are left at 0, and PIDs that
+/// have exited are left out. Waits for `ps`, so async callers should use
+/// `spawn_blocking`.
+#[cfg(target_os = "macos")]
+pub fn ps_processes_info(pids: &[u32]) -> HashMap<u32, ProcessInfo> {
+    if pids.is_empty() {
+        return HashMap::new();
+    }
+    let pid_list: Vec<String> = pids.iter().map(u32::to_string).collect();
+    let output = match std::process::Command::new("ps")
+        .args(["-p", &pid_list.join(","), "-o", "pid=,user=,comm="])
+        .output()
+    {
+        Ok(output) => output,
+        Err(e) => {
+            log::debug!("Failed to run ps: {}", e);
+            return HashMap::new();
+        }
+    };
+    // ps exits non-zero when some PIDs are gone but still lists the others
+    parse_ps_output(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Other platforms have no `ps` fallback
+#[cfg(not(target_os = "macos"))]
+pub fn ps_processes_info(_pids: &[u32]) -> HashMap<u32, ProcessInfo> {
+    HashMap::new()
+}
+
+/// Parse `pid=,user=,comm=` lines from `ps`, keyed by PID
+///
+/// `comm` is the executable path on macOS and may contain spaces, so it is taken
+/// from the last column, whatever follows the user.
+#[cfg(any(target_os = "macos", test))]
+fn parse_ps_output(output: &str) -> HashMap<u32, ProcessInfo> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (pid, rest) = line.trim().split_once(char::is_whitespace)?;
+            let (user, comm) = rest.trim_start().split_once(char::is_whitespace)?;
+            let pid = pid.parse().ok()?;
+            Some((pid, ps_process(pid, user, comm.trim())?))
+        })
+        .collect()
+}
+
+/// A `ProcessInfo` from the user and `comm` column `ps` reported for `pid`
+#[cfg(any(target_os = "macos", test))]
+fn ps_process(pid: u32, user: &str, comm: &str) -> Option<ProcessInfo> {
+    if comm.is_empty() {
+        return None;
+    }
+
+    let name = std::path::Path::new(comm)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| comm.to_string());
+    let exe_path = comm.starts_with('/').then(|| comm.to_string());
+
+    Some(ProcessInfo {
+        pid,
+        name,
+        exe_path,
+        command_line: None,
+        args: Vec::new(),
+        user: user.to_string(),
+        memory_usage: 0,
+        cpu_usage: 0.0,
+        disk_read_bytes: 0,
+        disk_written_bytes: 0,
+        start_time: None,
+        parent_pid: None,
+    })
+}
+
+/// Parse NUL-separated `KEY=VALUE` pairs, skipping malformed entries
+#[cfg(target_os = "linux")]
+fn parse_environ(raw: &[u8]) -> HashMap<String, String> {
+    raw.split(|&b| b == 0)
+        .filter_map(|entry| {
+            let entry = String::from_utf8_lossy(entry);
+            let (key, value) = entry.split_once('=')?;
+            (!key.is_empty()).then(|| (key.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// Replace values of variables whose names look like they hold credentials
+pub fn redact_secrets(env: &mut HashMap<String, String>) {
+    for (key, value) in env.iter_mut() {
+        let key_upper = key.to_uppercase();
+        if SECRET_KEY_PATTERNS.iter().any(|pattern| key_upper.contains(pattern)) {
+            *value = REDACTED_VALUE.to_string();
         }
     }
 }
@@ -95,6 +624,7 @@ impl Default for ProcessEnricher {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fixtures::process_info;
 
     #[test]
     fn test_process_enricher() {
@@ -104,4 +634,221 @@ mod tests {
         let info = enricher.get_process_info(1);
         println!("PID 1 info: {:?}", info);
     }
+
+    /// Times the full refresh against the scan's process-only one; run it with
+    /// `cargo test bench_refresh_strategies -- --ignored --nocapture`
+    #[test]
+    #[ignore = "timing comparison, not a correctness check"]
+    fn bench_refresh_strategies() {
+        const RUNS: usize = 20;
+        let mut enricher = ProcessEnricher::new();
+        let mut median = |refresh: fn(&mut ProcessEnricher)| {
+            let mut times: Vec<Duration> = (0..RUNS)
+                .map(|_| {
+                    let started = Instant::now();
+                    refresh(&mut enricher);
+                    started.elapsed()
+                })
+                .collect();
+            times.sort();
+            times[RUNS / 2]
+        };
+
+        let full = median(ProcessEnricher::refresh);
+        let processes_only = median(ProcessEnricher::refresh_processes_only);
+        println!("refresh: {:?}, refresh_processes_only: {:?}", full, processes_only);
+    }
+
+    #[test]
+    fn test_get_processes_info_batch() {
+        let enricher = ProcessEnricher::new();
+        let own_pid = std::process::id();
+
+        let infos = enricher.get_processes_info(&[own_pid, u32::MAX]);
+        assert_eq!(infos.len(), 1);
+        assert_eq!(infos[&own_pid].pid, own_pid);
+    }
+
+    #[test]
+    fn test_uptime_secs() {
+        let now = Utc::now();
+        assert_eq!(uptime_secs(None, now), None);
+        assert_eq!(uptime_secs(Some(now - chrono::Duration::seconds(90)), now), Some(90));
+        assert_eq!(uptime_secs(Some(now + chrono::Duration::seconds(5)), now), None);
+    }
+
+    #[test]
+    fn test_format_age() {
+        assert_eq!(format_age(42), "42s");
+        assert_eq!(format_age(125), "2m");
+        assert_eq!(format_age(3 * 3600 + 5 * 60), "3h 5m");
+        assert_eq!(format_age(2 * 86_400 + 4 * 3600 + 59), "2d 4h");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_environ() {
+        let env = parse_environ(b"PATH=/usr/bin\0EMPTY=\0URL=a=b\0garbage\0\0");
+        assert_eq!(env.len(), 3);
+        assert_eq!(env["PATH"], "/usr/bin");
+        assert_eq!(env["EMPTY"], "");
+        assert_eq!(env["URL"], "a=b");
+    }
+
+    #[test]
+    fn test_parse_systemd_unit() {
+        assert_eq!(
+            parse_systemd_unit("0::/system.slice/nginx.service\n").as_deref(),
+            Some("nginx.service")
+        );
+        // The innermost unit wins for user sessions
+        assert_eq!(
+            parse_systemd_unit(
+                "0::/user.slice/user-1000.slice/user@1000.service/app.slice/vte-spawn-4f2.scope"
+            )
+            .as_deref(),
+            Some("vte-spawn-4f2.scope")
+        );
+        // cgroup v1: only systemd's named hierarchy is considered
+        let v1 = "12:cpu,cpuacct:/system.slice/other.service\n\
+                  1:name=systemd:/system.slice/postgresql.service/payload\n";
+        assert_eq!(parse_systemd_unit(v1).as_deref(), Some("postgresql.service"));
+
+        assert_eq!(parse_systemd_unit("0::/\n"), None);
+        assert_eq!(parse_systemd_unit("0::/docker/3f1c2b\n"), None);
+    }
+
+    #[test]
+    fn test_parse_container_id() {
+        let id = "3f1c2b7d9e0a4b5c6d7e8f9a0b1c2d3e4f5a6b7c8d9e0f1a2b3c4d5e6f7a8b9c";
+
+        assert_eq!(
+            parse_container_id(&format!("12:cpu,cpuacct:/docker/{}\n", id)).as_deref(),
+            Some(id)
+        );
+        assert_eq!(
+            parse_container_id(&format!("0::/system.slice/docker-{}.scope\n", id)).as_deref(),
+            Some(id)
+        );
+        let pod = format!(
+            "0::/kubepods.slice/kubepods-burstable.slice/kubepods-burstable-pod1a2b.slice/\
+             cri-containerd-{}.scope\n",
+            id
+        );
+        assert_eq!(parse_container_id(&pod).as_deref(), Some(id));
+        assert_eq!(
+            parse_container_id(&format!("0::/kubepods/besteffort/pod1a2b-3c4d/{}\n", id))
+                .as_deref(),
+            Some(id)
+        );
+
+        assert_eq!(parse_container_id("0::/system.slice/nginx.service\n"), None);
+        assert_eq!(parse_container_id("0::/\n"), None);
+        assert_eq!(parse_container_id("0::/docker/3f1c2b\n"), None);
+    }
+
+    #[test]
+    fn test_parse_tasklist_services() {
+        let output = "\r\n\"svchost.exe\",\"1044\",\"RpcEptMapper,RpcSs\"\r\n";
+        assert_eq!(parse_tasklist_services(1044, output), vec!["RpcEptMapper", "RpcSs"]);
+        assert!(parse_tasklist_services(1045, output).is_empty());
+
+        let no_service = "\"node.exe\",\"7312\",\"N/A\"\r\n";
+        assert!(parse_tasklist_services(7312, no_service).is_empty());
+
+        let no_match = "INFO: No tasks are running which match the specified criteria.\r\n";
+        assert!(parse_tasklist_services(1, no_match).is_empty());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_read_process_cwd() {
+        let cwd = read_process_cwd(std::process::id()).unwrap();
+        assert_eq!(cwd, std::env::current_dir().unwrap());
+    }
+
+    #[test]
+    fn test_parse_ps_output() {
+        let infos = parse_ps_output(
+            "   88 _mdnsresponder   /usr/sbin/mDNSResponder\n\
+               412 alice            /Applications/Visual Studio Code.app/Contents/MacOS/Electron\n\
+             77 root\n",
+        );
+        assert_eq!(infos.len(), 2);
+
+        let info = &infos[&88];
+        assert_eq!(info.name, "mDNSResponder");
+        assert_eq!(info.exe_path.as_deref(), Some("/usr/sbin/mDNSResponder"));
+        assert_eq!(info.user, "_mdnsresponder");
+        assert_eq!(info.memory_usage, 0);
+
+        let info = &infos[&412];
+        assert_eq!(info.name, "Electron");
+        assert_eq!(info.user, "alice");
+
+        assert!(parse_ps_output("").is_empty());
+    }
+
+    #[test]
+    fn test_redact_secrets() {
+        let mut env = HashMap::from([
+            ("GITHUB_TOKEN".to_string(), "ghp_x".to_string()),
+            ("aws_secret_access_key".to_string(), "abc".to_string()),
+            ("DB_PASSWORD".to_string(), "hunter2".to_string()),
+            ("HOME".to_string(), "/home/me".to_string()),
+        ]);
+        redact_secrets(&mut env);
+
+        assert_eq!(env["GITHUB_TOKEN"], REDACTED_VALUE);
+        assert_eq!(env["aws_secret_access_key"], REDACTED_VALUE);
+        assert_eq!(env["DB_PASSWORD"], REDACTED_VALUE);
+        assert_eq!(env["HOME"], "/home/me");
+    }
+
+    fn fake_process(pid: u32, parent_pid: Option<u32>) -> ProcessInfo {
+        ProcessInfo {
+            parent_pid,
+            ..process_info(pid, &format!("proc-{}", pid))
+        }
+    }
+
+    #[test]
+    fn test_ancestor_chain() {
+        let table: HashMap<u32, Option<u32>> =
+            [(1, Some(0)), (200, Some(1)), (300, Some(200)), (400, Some(999))].into();
+        let lookup = |pid: u32| table.get(&pid).map(|&ppid| fake_process(pid, ppid));
+
+        let pids = |chain: Vec<ProcessInfo>| chain.iter().map(|p| p.pid).collect::<Vec<_>>();
+        assert_eq!(pids(ancestor_chain(300, lookup)), vec![300, 200, 1]);
+        // Stops at a parent missing from the table
+        assert_eq!(pids(ancestor_chain(400, lookup)), vec![400]);
+        assert!(ancestor_chain(12345, lookup).is_empty());
+
+        // Malformed data with a cycle terminates
+        let cyclic = |pid: u32| Some(fake_process(pid, Some(if pid == 10 { 11 } else { 10 })));
+        assert_eq!(pids(ancestor_chain(10, cyclic)), vec![10, 11]);
+
+        // A chain longer than the cap is truncated
+        let endless = |pid: u32| Some(fake_process(pid, Some(pid + 1)));
+        assert_eq!(ancestor_chain(2, endless).len(), MAX_ANCESTOR_DEPTH);
+    }
+
+    #[test]
+    fn test_is_orphaned() {
+        let running = |pid: u32| pid == 400;
+
+        assert!(!is_orphaned(Some(400), running));
+        assert!(is_orphaned(Some(401), running));
+        // Reparented to init is normal for daemons
+        assert!(!is_orphaned(Some(1), running));
+        assert!(!is_orphaned(None, running));
+    }
+
+    #[test]
+    fn test_is_docker_process_name() {
+        assert!(is_docker_process_name("com.docker.backend"));
+        assert!(is_docker_process_name("vpnkit"));
+        assert!(is_docker_process_name("containerd-shim"));
+        assert!(!is_docker_process_name("node"));
+    }
 }
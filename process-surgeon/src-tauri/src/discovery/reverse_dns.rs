@@ -0,0 +1,350 @@
+// Reverse DNS Module - Bounded, cached hostname lookups for remote addresses
+use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use tokio::task::JoinSet;
+
+/// Maximum time spent waiting on a single reverse lookup
+const LOOKUP_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// Default number of addresses remembered by the resolver
+const DEFAULT_CACHE_CAPACITY: usize = 1024;
+
+/// How long a resolved hostname is reused before looking it up again
+const POSITIVE_TTL: Duration = Duration::from_secs(600);
+
+/// How long a failed or timed-out lookup is remembered, so monitoring doesn't retry it
+/// every scan while still noticing new PTR records reasonably soon
+const NEGATIVE_TTL: Duration = Duration::from_secs(30);
+
+/// Reverse DNS resolver that caches both hits and misses
+pub struct ReverseDnsResolver {
+    cache: Arc<RwLock<DnsCache>>,
+    /// Blocking lookup run for cache misses; stubbed in tests so they stay offline
+    resolve: fn(IpAddr) -> Option<String>,
+}
+
+/// One cached lookup result
+#[derive(Debug, Clone)]
+struct CacheEntry {
+    hostname: Option<String>,
+    expires_at: Instant,
+    /// Recency stamp from `DnsCache::clock`; the smallest is evicted first
+    last_used: u64,
+}
+
+/// Bounded least-recently-used cache of lookups, with separate TTLs for hits and misses
+#[derive(Debug)]
+struct DnsCache {
+    entries: HashMap<IpAddr, CacheEntry>,
+    capacity: usize,
+    clock: u64,
+}
+
+impl DnsCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            capacity: capacity.max(1),
+            clock: 0,
+        }
+    }
+
+    /// Cached result for `ip`: `Some(None)` is a remembered miss, `None` means look it up
+    fn get(&mut self, ip: IpAddr, now: Instant) -> Option<Option<String>> {
+        self.clock += 1;
+        let entry = self.entries.get_mut(&ip)?;
+        if entry.expires_at <= now {
+            self.entries.remove(&ip);
+            return None;
+        }
+        entry.last_used = self.clock;
+        Some(entry.hostname.clone())
+    }
+
+    fn insert(&mut self, ip: IpAddr, hostname: Option<String>, now: Instant) {
+        self.clock += 1;
+        if !self.entries.contains_key(&ip) && self.entries.len() >= self.capacity {
+            // Expired entries go first, then the least recently used one
+            self.entries.retain(|_, entry| entry.expires_at > now);
+            if self.entries.len() >= self.capacity {
+                let oldest = self
+                    .entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.last_used)
+                    .map(|(ip, _)| *ip);
+                if let Some(oldest) = oldest {
+                    self.entries.remove(&oldest);
+                }
+            }
+        }
+
+        let ttl = if hostname.is_some() { POSITIVE_TTL } else { NEGATIVE_TTL };
+        self.entries.insert(
+            ip,
+            CacheEntry {
+                hostname,
+                expires_at: now + ttl,
+                last_used: self.clock,
+            },
+        );
+    }
+}
+
+impl ReverseDnsResolver {
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_CACHE_CAPACITY)
+    }
+
+    /// Create a resolver that remembers at most `capacity` addresses
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            cache: Arc::new(RwLock::new(DnsCache::new(capacity))),
+            resolve: reverse_lookup,
+        }
+    }
+
+    /// Resolve the hostname for a single address
+    pub async fn lookup(&self, ip: IpAddr) -> Option<String> {
+        self.lookup_many(&[ip]).await.remove(&ip)
+    }
+
+    /// Resolve hostnames for several addresses concurrently
+    ///
+    /// Addresses that fail to resolve (or time out) are omitted from the result. Hits
+    /// are reused for ten minutes and misses for thirty seconds.
+    pub async fn lookup_many(&self, ips: &[IpAddr]) -> HashMap<IpAddr, String> {
+        let mut resolved = HashMap::new();
+        let mut pending = JoinSet::new();
+        let mut queued = HashSet::new();
+
+        {
+            let mut cache = self.cache.write().await;
+            let now = Instant::now();
+            for &ip in ips {
+                if ip.is_unspecified() {
+                    continue;
+                }
+                match cache.get(ip, now) {
+                    Some(Some(hostname)) => {
+                        resolved.insert(ip, hostname.clone());
+                    }
+                    Some(None) => {}
+                    None => {
+                        if queued.insert(ip) {
+                            let resolve = self.resolve;
+                            pending.spawn(async move {
+                                (ip, lookup_with_timeout(ip, resolve).await)
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut answers = Vec::new();
+        while let Some(joined) = pending.join_next().await {
+            if let Ok(answer) = joined {
+                answers.push(answer);
+            }
+        }
+
+        let mut cache = self.cache.write().await;
+        let now = Instant::now();
+        for (ip, hostname) in answers {
+            if let Some(hostname) = &hostname {
+                resolved.insert(ip, hostname.clone());
+            }
+            cache.insert(ip, hostname, now);
+        }
+
+        resolved
+    }
+
+    /// Number of addresses currently cached
+    pub async fn cached_len(&self) -> usize {
+        self.cache.read().await.entries.len()
+    }
+
+    /// Forget every cached result, returning how many were dropped
+    pub async fn clear(&self) -> usize {
+        let mut cache = self.cache.write().await;
+        let dropped = cache.entries.len();
+        cache.entries.clear();
+        dropped
+    }
+}
+
+impl Default for ReverseDnsResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Run a blocking reverse lookup, giving up after `LOOKUP_TIMEOUT`
+///
+/// A timed-out task is aborted so it never starts if it is still queued behind other
+/// blocking work; one already inside getnameinfo(3) runs to completion on its own.
+async fn lookup_with_timeout(ip: IpAddr, resolve: fn(IpAddr) -> Option<String>) -> Option<String> {
+    let mut task = tokio::task::spawn_blocking(move || resolve(ip));
+
+    match tokio::time::timeout(LOOKUP_TIMEOUT, &mut task).await {
+        Ok(Ok(hostname)) => hostname,
+        Ok(Err(e)) => {
+            log::debug!("Reverse DNS task for {} failed: {}", ip, e);
+            None
+        }
+        Err(_) => {
+            task.abort();
+            log::debug!("Reverse DNS lookup for {} timed out", ip);
+            None
+        }
+    }
+}
+
+/// Blocking reverse lookup via getnameinfo(3)
+#[cfg(unix)]
+fn reverse_lookup(ip: IpAddr) -> Option<String> {
+    use std::ffi::CStr;
+    use std::mem::size_of;
+
+    const NI_MAXHOST: usize = 1025;
+    let mut host = [0 as libc::c_char; NI_MAXHOST];
+
+    let rc = match ip {
+        IpAddr::V4(v4) => {
+            let mut addr: libc::sockaddr_in = unsafe { std::mem::zeroed() };
+            addr.sin_family = libc::AF_INET as libc::sa_family_t;
+            addr.sin_addr.s_addr = u32::from_ne_bytes(v4.octets());
+            #[cfg(target_os = "macos")]
+            {
+                addr.sin_len = size_of::<libc::sockaddr_in>() as u8;
+            }
+            unsafe {
+                libc::getnameinfo(
+                    &addr as *const libc::sockaddr_in as *const libc::sockaddr,
+                    size_of::<libc::sockaddr_in>() as libc::socklen_t,
+                    host.as_mut_ptr(),
+                    NI_MAXHOST as libc::socklen_t,
+                    std::ptr::null_mut(),
+                    0,
+                    libc::NI_NAMEREQD,
+                )
+            }
+        }
+        IpAddr::V6(v6) => {
+            let mut addr: libc::sockaddr_in6 = unsafe { std::mem::zeroed() };
+            addr.sin6_family = libc::AF_INET6 as libc::sa_family_t;
+            addr.sin6_addr.s6_addr = v6.octets();
+            #[cfg(target_os = "macos")]
+            {
+                addr.sin6_len = size_of::<libc::sockaddr_in6>() as u8;
+            }
+            unsafe {
+                libc::getnameinfo(
+                    &addr as *const libc::sockaddr_in6 as *const libc::sockaddr,
+                    size_of::<libc::sockaddr_in6>() as libc::socklen_t,
+                    host.as_mut_ptr(),
+                    NI_MAXHOST as libc::socklen_t,
+                    std::ptr::null_mut(),
+                    0,
+                    libc::NI_NAMEREQD,
+                )
+            }
+        }
+    };
+
+    if rc != 0 {
+        return None;
+    }
+
+    let hostname = unsafe { CStr::from_ptr(host.as_ptr()) }
+        .to_string_lossy()
+        .to_string();
+
+    if hostname.is_empty() {
+        None
+    } else {
+        Some(hostname)
+    }
+}
+
+/// Reverse lookups are not yet implemented on Windows
+#[cfg(windows)]
+fn reverse_lookup(_ip: IpAddr) -> Option<String> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    #[tokio::test]
+    async fn test_unspecified_address_is_skipped() {
+        let resolver = ReverseDnsResolver::new();
+        assert_eq!(resolver.lookup(IpAddr::V4(Ipv4Addr::UNSPECIFIED)).await, None);
+        assert_eq!(resolver.cached_len().await, 0);
+    }
+
+    /// Resolves every address to `host-<last octet>.example` without touching the network
+    fn stub_lookup(ip: IpAddr) -> Option<String> {
+        match ip {
+            IpAddr::V4(v4) => Some(format!("host-{}.example", v4.octets()[3])),
+            IpAddr::V6(_) => None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cache_is_bounded() {
+        let resolver = ReverseDnsResolver {
+            resolve: stub_lookup,
+            ..ReverseDnsResolver::with_capacity(2)
+        };
+        let ips: Vec<IpAddr> = (1..=4)
+            .map(|i| IpAddr::V4(Ipv4Addr::new(192, 0, 2, i)))
+            .collect();
+
+        let resolved = resolver.lookup_many(&ips).await;
+        assert_eq!(resolved.len(), 4);
+        assert_eq!(resolved[&ips[0]], "host-1.example");
+        assert!(resolver.cached_len().await <= 2);
+
+        resolver.clear().await;
+        assert_eq!(resolver.cached_len().await, 0);
+    }
+
+    #[test]
+    fn test_cache_evicts_least_recently_used() {
+        let ip = |i| IpAddr::V4(Ipv4Addr::new(192, 0, 2, i));
+        let now = Instant::now();
+        let mut cache = DnsCache::new(2);
+
+        cache.insert(ip(1), Some("one.example".to_string()), now);
+        cache.insert(ip(2), Some("two.example".to_string()), now);
+        assert_eq!(cache.get(ip(1), now), Some(Some("one.example".to_string())));
+
+        cache.insert(ip(3), None, now);
+        assert!(cache.get(ip(2), now).is_none());
+        assert!(cache.get(ip(1), now).is_some());
+        assert_eq!(cache.get(ip(3), now), Some(None));
+    }
+
+    #[test]
+    fn test_cache_expires_entries() {
+        let ip = |i| IpAddr::V4(Ipv4Addr::new(192, 0, 2, i));
+        let now = Instant::now();
+        let mut cache = DnsCache::new(8);
+
+        cache.insert(ip(1), Some("one.example".to_string()), now);
+        cache.insert(ip(2), None, now);
+
+        let later = now + NEGATIVE_TTL;
+        assert!(cache.get(ip(2), later).is_none());
+        assert!(cache.get(ip(1), later).is_some());
+        assert!(cache.get(ip(1), now + POSITIVE_TTL).is_none());
+        assert!(cache.entries.is_empty());
+    }
+}
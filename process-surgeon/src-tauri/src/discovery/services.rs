@@ -0,0 +1,112 @@
+// Services Module - Built-in well-known port to service name table
+use crate::models::Protocol;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+
+/// Well-known services keyed by port and protocol, modelled on /etc/services
+static WELL_KNOWN_SERVICES: Lazy<HashMap<(u16, Protocol), &'static str>> = Lazy::new(|| {
+    let mut map = HashMap::new();
+
+    // Services registered for both TCP and UDP
+    for (port, name) in [
+        (7, "echo"),
+        (53, "domain"),
+        (88, "kerberos"),
+        (123, "ntp"),
+        (389, "ldap"),
+        (443, "https"),
+        (464, "kpasswd"),
+        (3478, "stun"),
+        (5060, "sip"),
+        (5353, "mdns"),
+    ] {
+        map.insert((port, Protocol::TCP), name);
+        map.insert((port, Protocol::UDP), name);
+    }
+
+    // TCP-only services
+    for (port, name) in [
+        (20, "ftp-data"),
+        (21, "ftp"),
+        (22, "ssh"),
+        (23, "telnet"),
+        (25, "smtp"),
+        (80, "http"),
+        (110, "pop3"),
+        (111, "sunrpc"),
+        (119, "nntp"),
+        (143, "imap"),
+        (179, "bgp"),
+        (445, "microsoft-ds"),
+        (465, "submissions"),
+        (587, "submission"),
+        (631, "ipp"),
+        (636, "ldaps"),
+        (873, "rsync"),
+        (993, "imaps"),
+        (995, "pop3s"),
+        (1433, "ms-sql-s"),
+        (1521, "oracle"),
+        (1883, "mqtt"),
+        (2049, "nfs"),
+        (2375, "docker"),
+        (2376, "docker-s"),
+        (2379, "etcd-client"),
+        (2380, "etcd-server"),
+        (3306, "mysql"),
+        (3389, "ms-wbt-server"),
+        (4369, "epmd"),
+        (5432, "postgresql"),
+        (5672, "amqp"),
+        (5900, "vnc"),
+        (6379, "redis"),
+        (6443, "kubernetes"),
+        (8080, "http-alt"),
+        (8443, "https-alt"),
+        (9092, "kafka"),
+        (9200, "elasticsearch"),
+        (11211, "memcache"),
+        (27017, "mongodb"),
+    ] {
+        map.insert((port, Protocol::TCP), name);
+    }
+
+    // UDP-only services
+    for (port, name) in [
+        (67, "bootps"),
+        (68, "bootpc"),
+        (69, "tftp"),
+        (137, "netbios-ns"),
+        (138, "netbios-dgm"),
+        (161, "snmp"),
+        (162, "snmp-trap"),
+        (500, "isakmp"),
+        (514, "syslog"),
+        (1900, "ssdp"),
+        (4500, "ipsec-nat-t"),
+        (51820, "wireguard"),
+    ] {
+        map.insert((port, Protocol::UDP), name);
+    }
+
+    map
+});
+
+/// Look up the well-known service name for a port and protocol
+pub fn service_name(port: u16, protocol: Protocol) -> Option<&'static str> {
+    WELL_KNOWN_SERVICES.get(&(port, protocol)).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_service_name_lookup() {
+        assert_eq!(service_name(443, Protocol::TCP), Some("https"));
+        assert_eq!(service_name(53, Protocol::UDP), Some("domain"));
+        assert_eq!(service_name(161, Protocol::UDP), Some("snmp"));
+        assert_eq!(service_name(80, Protocol::UDP), None);
+        assert_eq!(service_name(0, Protocol::TCP), None);
+    }
+}
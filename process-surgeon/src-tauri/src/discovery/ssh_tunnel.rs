@@ -0,0 +1,276 @@
+// SSH Tunnel Module - Explaining listeners held by ssh port forwards
+use std::path::Path;
+
+/// ssh options that take an argument, so the next token is not an option of its own
+const SSH_OPTIONS_WITH_ARG: &str = "BbcDEeFIiJLlmOoPpQRSWw";
+
+/// Port sshd listens on when its command line doesn't pass `-p`
+const DEFAULT_SSHD_PORT: u16 = 22;
+
+/// Which host a forward listens on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForwardKind {
+    /// `-L`: listens locally and relays to a fixed destination
+    Local,
+    /// `-R`: listens on the remote host, never on a local port
+    Remote,
+    /// `-D`: listens locally as a SOCKS proxy
+    Dynamic,
+}
+
+/// One `-L`, `-R` or `-D` forward from an ssh command line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SshForward {
+    pub kind: ForwardKind,
+    /// Port the forward listens on, or `None` for a Unix socket path
+    pub listen_port: Option<u16>,
+    /// Where connections are relayed, e.g. "db.internal:3306"
+    pub destination: String,
+}
+
+/// Check whether a process name is an OpenSSH client or server
+pub fn is_ssh_process_name(name: &str) -> bool {
+    is_named(name, "ssh") || is_sshd_process_name(name)
+}
+
+/// Check whether a process name is the OpenSSH server
+pub fn is_sshd_process_name(name: &str) -> bool {
+    is_named(name, "sshd")
+}
+
+fn is_named(name: &str, expected: &str) -> bool {
+    let name_lower = name.to_lowercase();
+    name_lower.strip_suffix(".exe").unwrap_or(&name_lower) == expected
+}
+
+/// Ports an sshd command line serves SSH on: its `-p` values, or 22 without any
+///
+/// A `Port` set only in sshd_config is not visible here. Listeners of sshd on other
+/// ports are `-R` forwards it holds for connected clients.
+pub fn sshd_daemon_ports<S: AsRef<str>>(args: &[S]) -> Vec<u16> {
+    let mut tokens = args.iter().map(AsRef::as_ref);
+    let mut ports = Vec::new();
+    while let Some(token) = tokens.next() {
+        let value = match token.strip_prefix("-p") {
+            Some("") => tokens.next(),
+            Some(value) => Some(value),
+            None => None,
+        };
+        ports.extend(value.and_then(|value| value.parse::<u16>().ok()));
+    }
+    if ports.is_empty() {
+        ports.push(DEFAULT_SSHD_PORT);
+    }
+    ports
+}
+
+/// Parse the `-L`/`-R`/`-D` forward specs out of an ssh argument vector
+///
+/// Handles both `-L spec` and `-Lspec`, flags bundled as in `-fNL spec`, and the
+/// `[bind:]port:host:hostport`, `port:/socket` and `-D [bind:]port` spec forms.
+/// Arguments after the destination (the remote command) are not scanned.
+pub fn parse_ssh_forwards<S: AsRef<str>>(args: &[S]) -> Vec<SshForward> {
+    let mut tokens = args.iter().map(AsRef::as_ref);
+    let is_ssh = tokens
+        .next()
+        .and_then(|program| Path::new(program).file_name())
+        .is_some_and(|program| is_ssh_process_name(&program.to_string_lossy()));
+    if !is_ssh {
+        return Vec::new();
+    }
+
+    let mut forwards = Vec::new();
+    while let Some(token) = tokens.next() {
+        let Some(flags) = token.strip_prefix('-').filter(|flags| !flags.is_empty()) else {
+            // First non-option argument is the destination host
+            break;
+        };
+        for (index, flag) in flags.char_indices() {
+            if !SSH_OPTIONS_WITH_ARG.contains(flag) {
+                continue;
+            }
+            let rest = &flags[index + flag.len_utf8()..];
+            let value = if rest.is_empty() { tokens.next() } else { Some(rest) };
+            let kind = match flag {
+                'L' => ForwardKind::Local,
+                'R' => ForwardKind::Remote,
+                'D' => ForwardKind::Dynamic,
+                _ => break,
+            };
+            if let Some(value) = value {
+                forwards.extend(parse_forward_spec(kind, value));
+            }
+            break;
+        }
+    }
+    forwards
+}
+
+/// Describe where the forwards listening on `listening_ports` relay to
+///
+/// Returns e.g. "→ db.internal:3306", with several forwards separated by ", ", or
+/// `None` when no forward in the arguments matches one of the ports. `-R` forwards
+/// listen on the remote host, so they never match a local port.
+pub fn describe_tunnel<S: AsRef<str>>(args: &[S], listening_ports: &[u16]) -> Option<String> {
+    let targets: Vec<String> = parse_ssh_forwards(args)
+        .into_iter()
+        .filter(|forward| {
+            forward.kind != ForwardKind::Remote
+                && forward
+                    .listen_port
+                    .is_some_and(|port| listening_ports.contains(&port))
+        })
+        .map(|forward| format!("→ {}", forward.destination))
+        .collect();
+
+    if targets.is_empty() {
+        None
+    } else {
+        Some(targets.join(", "))
+    }
+}
+
+/// Parse one forward spec such as `127.0.0.1:13306:db:3306` or `[::1]:8080:web:80`
+fn parse_forward_spec(kind: ForwardKind, spec: &str) -> Option<SshForward> {
+    let fields = split_spec(spec);
+    if kind == ForwardKind::Dynamic {
+        let listen_port = fields.last()?.parse().ok()?;
+        return Some(SshForward {
+            kind,
+            listen_port: Some(listen_port),
+            destination: "SOCKS proxy".to_string(),
+        });
+    }
+
+    let (listen, destination) = match fields.as_slice() {
+        [listen, socket] => (*listen, socket.to_string()),
+        [listen, host, port] | [_, listen, host, port] if host.contains(':') => {
+            (*listen, format!("[{}]:{}", host, port))
+        }
+        [listen, host, port] | [_, listen, host, port] => (*listen, format!("{}:{}", host, port)),
+        _ => return None,
+    };
+    if destination.is_empty() {
+        return None;
+    }
+
+    Some(SshForward {
+        kind,
+        listen_port: listen.parse().ok(),
+        destination,
+    })
+}
+
+/// Split a spec on ':' outside square brackets, dropping the brackets themselves
+fn split_spec(spec: &str) -> Vec<&str> {
+    let mut fields = Vec::new();
+    let mut start = 0;
+    let mut in_brackets = false;
+
+    for (index, c) in spec.char_indices() {
+        match c {
+            '[' => in_brackets = true,
+            ']' => in_brackets = false,
+            ':' if !in_brackets => {
+                fields.push(&spec[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    fields.push(&spec[start..]);
+
+    fields
+        .into_iter()
+        .map(|field| field.trim_start_matches('[').trim_end_matches(']'))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(command_line: &str) -> Vec<&str> {
+        command_line.split_whitespace().collect()
+    }
+
+    fn forward(kind: ForwardKind, listen_port: Option<u16>, destination: &str) -> SshForward {
+        SshForward {
+            kind,
+            listen_port,
+            destination: destination.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_is_ssh_process_name() {
+        assert!(is_ssh_process_name("ssh"));
+        assert!(is_ssh_process_name("sshd"));
+        assert!(is_ssh_process_name("SSH.EXE"));
+        assert!(!is_ssh_process_name("ssh-agent"));
+        assert!(!is_ssh_process_name("autossh"));
+    }
+
+    #[test]
+    fn test_parse_ssh_forwards() {
+        assert_eq!(
+            parse_ssh_forwards(&args("/usr/bin/ssh -N -L 13306:remote:3306 bastion")),
+            vec![forward(ForwardKind::Local, Some(13306), "remote:3306")]
+        );
+        assert_eq!(
+            parse_ssh_forwards(&args(
+                "ssh -fNL127.0.0.1:8080:[::1]:80 -p 2222 -R 9000:localhost:9000 h"
+            )),
+            vec![
+                forward(ForwardKind::Local, Some(8080), "[::1]:80"),
+                forward(ForwardKind::Remote, Some(9000), "localhost:9000"),
+            ]
+        );
+        assert_eq!(
+            parse_ssh_forwards(&args("ssh -L 5432:/var/run/postgresql/.s.PGSQL.5432 db")),
+            vec![forward(ForwardKind::Local, Some(5432), "/var/run/postgresql/.s.PGSQL.5432")]
+        );
+        assert_eq!(
+            parse_ssh_forwards(&args("ssh -N -D 127.0.0.1:1080 bastion")),
+            vec![forward(ForwardKind::Dynamic, Some(1080), "SOCKS proxy")]
+        );
+    }
+
+    #[test]
+    fn test_parse_ssh_forwards_ignores_other_arguments() {
+        // -i takes the key path, and the remote command's -L is not an ssh option
+        assert!(parse_ssh_forwards(&args("ssh -i 1:2:3 host ls -L 1:a:2")).is_empty());
+        assert!(parse_ssh_forwards(&args("sshd: alice@pts/0")).is_empty());
+        assert!(parse_ssh_forwards(&args("nc -L 1:a:2")).is_empty());
+        assert!(parse_ssh_forwards::<&str>(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_describe_tunnel() {
+        let command_line = args("ssh -N -L 13306:remote:3306 -L 16379:cache:6379 bastion");
+        assert_eq!(
+            describe_tunnel(&command_line, &[13306]),
+            Some("→ remote:3306".to_string())
+        );
+        assert_eq!(
+            describe_tunnel(&command_line, &[13306, 16379]),
+            Some("→ remote:3306, → cache:6379".to_string())
+        );
+        assert_eq!(describe_tunnel(&command_line, &[22]), None);
+
+        // A remote forward listens on the bastion, not on this host
+        let remote = args("ssh -N -R 8080:localhost:80 -D 1080 bastion");
+        assert_eq!(describe_tunnel(&remote, &[8080]), None);
+        assert_eq!(describe_tunnel(&remote, &[1080]), Some("→ SOCKS proxy".to_string()));
+    }
+
+    #[test]
+    fn test_sshd_daemon_ports() {
+        assert_eq!(sshd_daemon_ports(&args("/usr/sbin/sshd -D")), vec![22]);
+        assert_eq!(sshd_daemon_ports(&args("sshd: alice@pts/0")), vec![22]);
+        assert_eq!(
+            sshd_daemon_ports(&args("/usr/sbin/sshd -D -p 2222 -p2200")),
+            vec![2222, 2200]
+        );
+    }
+}
@@ -0,0 +1,202 @@
+// Unix Sockets Module - Named Unix domain sockets and the processes holding them
+use crate::models::{UnixSocket, UnixSocketState};
+use anyhow::Result;
+
+/// Enumerate named Unix domain sockets
+///
+/// Anonymous sockets (socketpairs, unnamed clients) are skipped since they have no
+/// path to show. Sockets sharing a path and state are merged into one entry.
+#[cfg(target_os = "linux")]
+pub fn scan_unix_sockets() -> Result<Vec<UnixSocket>> {
+    let contents = std::fs::read_to_string("/proc/net/unix")?;
+    let entries = parse_proc_net_unix(&contents);
+    let owners = socket_inode_owners();
+
+    Ok(merge_sockets(entries.into_iter().map(|(inode, path, state)| {
+        let pids = owners.get(&inode).cloned().unwrap_or_default();
+        (path, state, pids)
+    })))
+}
+
+/// Enumerate named Unix domain sockets via `lsof -U`
+///
+/// lsof does not report the state of Unix sockets, so every entry is `Unknown`.
+#[cfg(target_os = "macos")]
+pub fn scan_unix_sockets() -> Result<Vec<UnixSocket>> {
+    use anyhow::bail;
+
+    let output = std::process::Command::new("lsof")
+        .args(["-U", "-n", "-F", "pn"])
+        .output()?;
+    // lsof exits with 1 when some processes could not be inspected
+    if output.stdout.is_empty() && !output.status.success() {
+        bail!("lsof failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(merge_sockets(parse_lsof_unix(&stdout)))
+}
+
+/// Unix socket enumeration is not implemented on this platform
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub fn scan_unix_sockets() -> Result<Vec<UnixSocket>> {
+    Ok(Vec::new())
+}
+
+/// Merge (path, state, pids) triples by path and state, sorted by path
+#[cfg(any(target_os = "linux", target_os = "macos", test))]
+fn merge_sockets(
+    entries: impl IntoIterator<Item = (String, UnixSocketState, Vec<u32>)>,
+) -> Vec<UnixSocket> {
+    use std::collections::BTreeMap;
+
+    let mut merged: BTreeMap<(String, UnixSocketState), Vec<u32>> = BTreeMap::new();
+    for (path, state, pids) in entries {
+        merged.entry((path, state)).or_default().extend(pids);
+    }
+
+    merged
+        .into_iter()
+        .map(|((path, state), mut pids)| {
+            pids.sort_unstable();
+            pids.dedup();
+            UnixSocket { path, pids, state }
+        })
+        .collect()
+}
+
+/// `__SO_ACCEPTCON` in the Flags column marks a listening socket
+#[cfg(any(target_os = "linux", test))]
+const SO_ACCEPTCON: u32 = 0x10000;
+
+/// Parse `/proc/net/unix` into (inode, path, state) for sockets bound to a path
+///
+/// Abstract sockets keep their leading `@`, as `ss` and `netstat` show them.
+#[cfg(any(target_os = "linux", test))]
+fn parse_proc_net_unix(contents: &str) -> Vec<(u64, String, UnixSocketState)> {
+    contents
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            // Num RefCount Protocol Flags Type St Inode [Path]
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 8 {
+                return None;
+            }
+
+            let flags = u32::from_str_radix(fields[3], 16).ok()?;
+            let inode = fields[6].parse().ok()?;
+            let state = if flags & SO_ACCEPTCON != 0 {
+                UnixSocketState::Listening
+            } else {
+                match fields[5] {
+                    "01" => UnixSocketState::Unconnected,
+                    "03" => UnixSocketState::Connected,
+                    _ => UnixSocketState::Unknown,
+                }
+            };
+
+            Some((inode, fields[7..].join(" "), state))
+        })
+        .collect()
+}
+
+/// Map socket inodes to the PIDs holding a descriptor for them
+///
+/// Processes whose descriptors can't be read (other users without root) are skipped.
+#[cfg(target_os = "linux")]
+fn socket_inode_owners() -> std::collections::HashMap<u64, Vec<u32>> {
+    use procfs::process::FDTarget;
+    use std::collections::HashMap;
+
+    let mut owners: HashMap<u64, Vec<u32>> = HashMap::new();
+    let Ok(processes) = procfs::process::all_processes() else {
+        return owners;
+    };
+
+    for process in processes.flatten() {
+        let Ok(fds) = process.fd() else {
+            continue;
+        };
+        for fd in fds.flatten() {
+            if let FDTarget::Socket(inode) = fd.target {
+                owners.entry(inode).or_default().push(process.pid as u32);
+            }
+        }
+    }
+
+    owners
+}
+
+/// Parse `lsof -F pn` output into (path, state, pids), keeping only named sockets
+#[cfg(any(target_os = "macos", test))]
+fn parse_lsof_unix(output: &str) -> Vec<(String, UnixSocketState, Vec<u32>)> {
+    let mut entries = Vec::new();
+    let mut pid = None;
+
+    for line in output.lines() {
+        if let Some(value) = line.strip_prefix('p') {
+            pid = value.parse().ok();
+        } else if let (Some(name), Some(pid)) = (line.strip_prefix('n'), pid) {
+            // Unbound ends show up as "->0x..." peers rather than paths
+            if name.starts_with('/') {
+                entries.push((name.to_string(), UnixSocketState::Unknown, vec![pid]));
+            }
+        }
+    }
+
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_proc_net_unix() {
+        let contents = "\
+Num       RefCount Protocol Flags    Type St Inode Path
+0000000000000000: 00000002 00000000 00010000 0001 01 21345 /run/docker.sock
+0000000000000000: 00000003 00000000 00000000 0001 03 21400 /run/docker.sock
+0000000000000000: 00000002 00000000 00010000 0001 01 19876 @/tmp/.X11-unix/X0
+0000000000000000: 00000003 00000000 00000000 0001 03 30211
+";
+        let entries = parse_proc_net_unix(contents);
+        assert_eq!(
+            entries,
+            vec![
+                (21345, "/run/docker.sock".to_string(), UnixSocketState::Listening),
+                (21400, "/run/docker.sock".to_string(), UnixSocketState::Connected),
+                (19876, "@/tmp/.X11-unix/X0".to_string(), UnixSocketState::Listening),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_lsof_unix() {
+        let output = "p812\nf3\nn/var/run/mDNSResponder\nf4\nn->0x1234abcd\n\
+                      p901\nf7\nn/tmp/mysql.sock\n";
+        assert_eq!(
+            parse_lsof_unix(output),
+            vec![
+                ("/var/run/mDNSResponder".to_string(), UnixSocketState::Unknown, vec![812]),
+                ("/tmp/mysql.sock".to_string(), UnixSocketState::Unknown, vec![901]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_sockets() {
+        let merged = merge_sockets(vec![
+            ("/run/b.sock".to_string(), UnixSocketState::Listening, vec![20]),
+            ("/run/a.sock".to_string(), UnixSocketState::Listening, vec![30, 10]),
+            ("/run/a.sock".to_string(), UnixSocketState::Listening, vec![10]),
+            ("/run/a.sock".to_string(), UnixSocketState::Connected, vec![40]),
+        ]);
+
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged[0].path, "/run/a.sock");
+        assert_eq!(merged[0].pids, vec![10, 30]);
+        assert_eq!(merged[2].path, "/run/b.sock");
+    }
+}
@@ -0,0 +1,244 @@
+// Conflicts Module - Container names and ports claimed twice, across engines or with the host
+use super::EngineContainers;
+use crate::discovery::{normalize_listen_address, parse_address, AddressClass};
+use crate::models::{
+    ConflictKind, ConflictingContainer, ContainerInfo, HostPortConflict, ProcessNode, Protocol,
+    RuntimeConflict, SocketState,
+};
+use std::collections::{BTreeMap, HashSet};
+
+/// Names and published host ports that containers on more than one engine share
+///
+/// Containers on a single engine never conflict with each other here; the engine
+/// enforces unique names and would refuse a second binding. Sorted by kind, then
+/// value.
+pub fn runtime_conflicts(engines: &[EngineContainers]) -> Vec<RuntimeConflict> {
+    let mut claims: BTreeMap<(ConflictKind, String), Vec<ConflictingContainer>> = BTreeMap::new();
+
+    for engine in engines {
+        for container in &engine.containers {
+            let entry = ConflictingContainer {
+                runtime: engine.runtime.clone(),
+                endpoint: engine.endpoint.clone(),
+                id: container.id.clone(),
+                name: container.name.clone(),
+            };
+            claims
+                .entry((ConflictKind::Name, container.name.clone()))
+                .or_default()
+                .push(entry.clone());
+
+            let ports: HashSet<u16> = container.ports.iter().map(|p| p.host_port).collect();
+            for port in ports {
+                claims
+                    .entry((ConflictKind::Port, port.to_string()))
+                    .or_default()
+                    .push(entry.clone());
+            }
+        }
+    }
+
+    claims
+        .into_iter()
+        .filter(|(_, containers)| {
+            let endpoints: HashSet<&str> = containers.iter().map(|c| c.endpoint.as_str()).collect();
+            endpoints.len() > 1
+        })
+        .map(|((kind, value), containers)| RuntimeConflict {
+            kind,
+            value,
+            containers,
+        })
+        .collect()
+}
+
+/// Published container ports that a host process is also listening on
+///
+/// Docker-proxy processes and nodes already attributed to a container are skipped, so
+/// only foreign listeners are reported. Binds only overlap when either side is a
+/// wildcard or both name the same address in the same family. Containers that aren't
+/// running should carry the bindings they will claim on start. Each process keeps
+/// just its conflicting listeners. Sorted by port, TCP before UDP.
+pub fn host_port_conflicts(
+    containers: &[ContainerInfo],
+    processes: &[ProcessNode],
+) -> Vec<HostPortConflict> {
+    let mut conflicts: BTreeMap<(u16, bool), HostPortConflict> = BTreeMap::new();
+
+    for node in processes
+        .iter()
+        .filter(|node| !node.is_docker_proxy && node.container.is_none())
+    {
+        for entry in node.ports.iter().filter(|p| p.state == SocketState::Listening) {
+            let claimants: Vec<&ContainerInfo> = containers
+                .iter()
+                .filter(|container| {
+                    container.ports.iter().any(|port| {
+                        port.host_port == entry.local_port
+                            && port.protocol == entry.protocol
+                            && binds_overlap(port.host_ip.as_deref(), &entry.local_address)
+                    })
+                })
+                .collect();
+            if claimants.is_empty() {
+                continue;
+            }
+
+            let key = (entry.local_port, entry.protocol == Protocol::UDP);
+            let conflict = conflicts.entry(key).or_insert_with(|| HostPortConflict {
+                port: entry.local_port,
+                protocol: entry.protocol,
+                containers: Vec::new(),
+                processes: Vec::new(),
+            });
+            for container in claimants {
+                if !conflict.containers.iter().any(|c| c.id == container.id) {
+                    conflict.containers.push(container.clone());
+                }
+            }
+            match conflict.processes.iter_mut().find(|p| p.pid == node.pid) {
+                Some(process) => process.ports.push(entry.clone()),
+                None => conflict.processes.push(ProcessNode {
+                    ports: vec![entry.clone()],
+                    ..node.clone()
+                }),
+            }
+        }
+    }
+
+    conflicts.into_values().collect()
+}
+
+/// Whether a container's host binding and a host listener's address can collide
+///
+/// `127.0.0.1` and `::1` are both loopback but separate binds, so apart from wildcards
+/// only addresses of the same family are compared.
+fn binds_overlap(host_ip: Option<&str>, listener_address: &str) -> bool {
+    let Some(host_ip) = host_ip.filter(|ip| !ip.is_empty()) else {
+        return true;
+    };
+    let published = normalize_listen_address(host_ip);
+    let listener = normalize_listen_address(listener_address);
+    if published == AddressClass::Wildcard || listener == AddressClass::Wildcard {
+        return true;
+    }
+    is_ipv6(host_ip) == is_ipv6(listener_address) && published == listener
+}
+
+/// Whether an address is IPv6, counting IPv4-mapped addresses as IPv4
+fn is_ipv6(addr: &str) -> Option<bool> {
+    parse_address(addr).map(|ip| ip.to_canonical().is_ipv6())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{self, port_entry, process_node, published_port};
+    use crate::models::ContainerRuntime;
+
+    fn container(id: &str, name: &str, host_port: Option<u16>) -> ContainerInfo {
+        ContainerInfo {
+            ports: host_port
+                .map(|host_port| published_port(host_port, 80))
+                .into_iter()
+                .collect(),
+            ..fixtures::container(id, name)
+        }
+    }
+
+    fn engine(
+        endpoint: &str,
+        runtime: ContainerRuntime,
+        containers: Vec<ContainerInfo>,
+    ) -> EngineContainers {
+        EngineContainers {
+            endpoint: endpoint.to_string(),
+            runtime,
+            containers,
+        }
+    }
+
+    #[test]
+    fn test_runtime_conflicts() {
+        let engines = vec![
+            engine(
+                "unix:///var/run/docker.sock",
+                ContainerRuntime::Docker,
+                vec![container("d1", "web", Some(8080)), container("d2", "db", Some(5432))],
+            ),
+            engine(
+                "unix:///run/podman/podman.sock",
+                ContainerRuntime::Podman,
+                vec![container("p1", "web", Some(8081)), container("p2", "cache", Some(5432))],
+            ),
+        ];
+
+        let conflicts = runtime_conflicts(&engines);
+        assert_eq!(conflicts.len(), 2);
+
+        assert_eq!(conflicts[0].kind, ConflictKind::Name);
+        assert_eq!(conflicts[0].value, "web");
+        let ids: Vec<&str> = conflicts[0].containers.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["d1", "p1"]);
+
+        assert_eq!(conflicts[1].kind, ConflictKind::Port);
+        assert_eq!(conflicts[1].value, "5432");
+        assert_eq!(conflicts[1].containers[1].runtime, ContainerRuntime::Podman);
+    }
+
+    #[test]
+    fn test_runtime_conflicts_single_engine() {
+        let engines = vec![engine(
+            "unix:///var/run/docker.sock",
+            ContainerRuntime::Docker,
+            vec![container("a", "web", Some(80)), container("b", "api", Some(80))],
+        )];
+        assert!(runtime_conflicts(&engines).is_empty());
+    }
+
+    fn host_process(pid: u32, address: &str, port: u16, is_docker_proxy: bool) -> ProcessNode {
+        ProcessNode {
+            id: format!("{}-{}", pid, port),
+            ports: vec![port_entry(address, port)],
+            is_docker_proxy,
+            ..process_node(pid, "node")
+        }
+    }
+
+    #[test]
+    fn test_host_port_conflicts() {
+        let mut loopback_only = container("c", "api", Some(9000));
+        loopback_only.ports[0].host_ip = Some("127.0.0.1".to_string());
+        let containers = vec![
+            container("a", "web", Some(8080)),
+            container("b", "db", Some(5432)),
+            loopback_only,
+        ];
+        let processes = vec![
+            host_process(100, "127.0.0.1", 8080, false),
+            host_process(200, "0.0.0.0", 5432, true),
+            host_process(300, "192.168.1.10", 9000, false),
+            host_process(400, "0.0.0.0", 3000, false),
+        ];
+
+        let conflicts = host_port_conflicts(&containers, &processes);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].port, 8080);
+        assert_eq!(conflicts[0].containers[0].id, "a");
+        assert_eq!(conflicts[0].processes[0].pid, 100);
+    }
+
+    #[test]
+    fn test_binds_overlap() {
+        assert!(binds_overlap(None, "127.0.0.1"));
+        assert!(binds_overlap(Some(""), "::1"));
+        assert!(binds_overlap(Some("127.0.0.1"), "0.0.0.0"));
+        assert!(binds_overlap(Some("::"), "127.0.0.1"));
+        assert!(binds_overlap(Some("127.0.0.1"), "127.0.0.1"));
+        assert!(binds_overlap(Some("127.0.0.1"), "::ffff:127.0.0.1"));
+        assert!(binds_overlap(Some("::1"), "::1"));
+        assert!(!binds_overlap(Some("::1"), "127.0.0.1"));
+        assert!(!binds_overlap(Some("127.0.0.1"), "::1"));
+        assert!(!binds_overlap(Some("127.0.0.1"), "10.0.0.5"));
+    }
+}
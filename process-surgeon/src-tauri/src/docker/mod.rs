@@ -1,4 +1,6 @@
 // Docker module - Container integration
+pub mod conflicts;
 pub mod resolver;
 
+pub use conflicts::*;
 pub use resolver::*;
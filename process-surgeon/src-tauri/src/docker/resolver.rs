@@ -1,63 +1,482 @@
 // Docker Resolver Module - Container port resolution
-use crate::models::{ContainerAction, ContainerInfo, ContainerPort, ContainerRuntime, Protocol};
+use crate::models::{
+    ContainerAction, ContainerEvent, ContainerHealth, ContainerInfo, ContainerPort,
+    ContainerRuntime, ContainerStats, DockerDiagnostics, DockerEndpointAttempt, Protocol,
+    RecreateResult,
+};
 use anyhow::{anyhow, Result};
 use bollard::container::{
-    KillContainerOptions, ListContainersOptions, RemoveContainerOptions, StopContainerOptions,
+    Config, CreateContainerOptions, KillContainerOptions, ListContainersOptions,
+    MemoryStatsStats, RemoveContainerOptions, StartContainerOptions, Stats, StatsOptions,
+    StopContainerOptions,
+};
+use bollard::models::{
+    ContainerInspectResponse, EventMessage, EventMessageTypeEnum, MountPointTypeEnum,
 };
-use bollard::Docker;
-use std::collections::HashMap;
-use std::sync::Arc;
+use bollard::system::EventsOptions;
+use bollard::{Docker, API_DEFAULT_VERSION};
+use chrono::{DateTime, Utc};
+use futures_util::stream::BoxStream;
+use futures_util::StreamExt;
+use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 
+/// Label Compose sets on every container to name its project
+const COMPOSE_PROJECT_LABEL: &str = "com.docker.compose.project";
+
+/// Label Compose sets on every container to name its service
+const COMPOSE_SERVICE_LABEL: &str = "com.docker.compose.service";
+
+/// Seconds Docker waits after SIGTERM before sending SIGKILL when stopping a container
+pub const DEFAULT_STOP_TIMEOUT_SECS: u32 = 10;
+
+/// Container lifecycle actions streamed by `container_events`
+const WATCHED_CONTAINER_ACTIONS: [&str; 4] = ["start", "stop", "die", "destroy"];
+
+/// How long an in-flight action blocks duplicates before it is assumed to be stuck
+const ACTION_DEBOUNCE_WINDOW: Duration = Duration::from_secs(30);
+
+/// In-flight container actions keyed by container id and action, with their start time
+type InFlightActions = Arc<Mutex<HashMap<(String, ContainerAction), Instant>>>;
+
+/// Attempts made for an idempotent engine call that keeps failing with transient errors
+const RETRY_ATTEMPTS: u32 = 3;
+
+/// Delay before the first retry of an engine call; doubles after each attempt
+const RETRY_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Outcome of `execute_action`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActionStatus {
+    Completed,
+    /// The same action on the same container was already running, so this one was dropped
+    AlreadyInProgress,
+}
+
+/// How long a cgroup container id missing from the listing is skipped before the
+/// listing is reloaded for it again
+const UNKNOWN_ID_TTL: Duration = Duration::from_secs(60);
+
+/// How long after a removal `recreate_last_removed` can still bring the container back
+pub const RECREATE_WINDOW: Duration = Duration::from_secs(10 * 60);
+
+/// Configuration of a removed container, captured just before it was removed
+#[derive(Debug, Clone)]
+struct RemovedContainer {
+    name: String,
+    config: Config<String>,
+    was_running: bool,
+    /// Parts of the container that recreating it will not bring back
+    warnings: Vec<String>,
+    removed_at: Instant,
+}
+
+/// Request timeout (seconds) for explicitly configured Docker endpoints
+const CONNECT_TIMEOUT_SECS: u64 = 120;
+
+/// Docker endpoint selected from the `DOCKER_HOST` environment
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DockerHost {
+    /// `DOCKER_HOST` unset: the platform's default socket or named pipe
+    LocalDefault,
+    /// `unix://` socket or `npipe://` named pipe
+    Socket(String),
+    /// Plain `tcp://` / `http://` engine
+    Http(String),
+    /// TLS-secured engine (`https://`, or `tcp://` with `DOCKER_TLS_VERIFY`)
+    Tls(String),
+    /// `ssh://` engine, which the client cannot connect to directly
+    Ssh(String),
+}
+
+impl DockerHost {
+    /// Interpret `DOCKER_HOST` and `DOCKER_TLS_VERIFY` the way the docker CLI does
+    fn from_env_values(docker_host: Option<&str>, tls_verify: Option<&str>) -> Self {
+        let tls_verify = tls_verify.map(|v| !v.is_empty() && v != "0").unwrap_or(false);
+
+        match docker_host.map(str::trim).filter(|h| !h.is_empty()) {
+            None => DockerHost::LocalDefault,
+            Some(host) if host.starts_with("unix://") || host.starts_with("npipe://") => {
+                DockerHost::Socket(host.to_string())
+            }
+            Some(host) if host.starts_with("ssh://") => DockerHost::Ssh(host.to_string()),
+            Some(host) if host.starts_with("https://") => DockerHost::Tls(host.to_string()),
+            Some(host) if tls_verify => DockerHost::Tls(host.to_string()),
+            Some(host) => DockerHost::Http(host.to_string()),
+        }
+    }
+
+    /// Human-readable endpoint for logging
+    fn describe(&self) -> String {
+        match self {
+            DockerHost::LocalDefault => "local default socket".to_string(),
+            DockerHost::Socket(addr)
+            | DockerHost::Http(addr)
+            | DockerHost::Tls(addr)
+            | DockerHost::Ssh(addr) => addr.clone(),
+        }
+    }
+
+    /// Whether the engine runs on another machine, so its ports aren't local sockets
+    fn is_remote(&self) -> bool {
+        match self {
+            DockerHost::LocalDefault | DockerHost::Socket(_) => false,
+            DockerHost::Http(addr) | DockerHost::Tls(addr) | DockerHost::Ssh(addr) => {
+                let host = addr
+                    .split("://")
+                    .last()
+                    .unwrap_or(addr)
+                    .rsplit('@')
+                    .next()
+                    .unwrap_or(addr)
+                    .rsplit_once(':')
+                    .map(|(host, _)| host)
+                    .unwrap_or(addr)
+                    .trim_start_matches('[')
+                    .trim_end_matches(']');
+                !matches!(host, "localhost" | "127.0.0.1" | "::1")
+            }
+        }
+    }
+}
+
 /// Docker container resolver for mapping ports to containers
 pub struct DockerResolver {
     client: Option<Docker>,
-    port_map: Arc<RwLock<HashMap<u16, ContainerInfo>>>,
+    /// Containers publishing each host port and protocol; several when bound on
+    /// different host IPs
+    port_map: Arc<RwLock<HashMap<(u16, Protocol), Vec<ContainerInfo>>>>,
+    /// Containers from the most recent full listing, used to resolve names
+    containers: Arc<RwLock<Vec<ContainerInfo>>>,
+    /// Endpoint the client is connected to
+    endpoint: Option<String>,
+    /// True when the engine is on another host
+    remote: bool,
+    /// Actions currently running, so double-clicks don't fire them twice
+    in_flight: InFlightActions,
+    /// The most recently removed container, kept for `recreate_last_removed`
+    last_removed: Arc<Mutex<Option<RemovedContainer>>>,
+    /// Cgroup container ids the listing didn't contain and when that was found, e.g.
+    /// Podman or Kubernetes containers, so each scan doesn't reload it for them
+    unknown_ids: Arc<Mutex<HashMap<String, Instant>>>,
 }
 
 impl DockerResolver {
-    /// Create a new Docker resolver, connecting to the default socket
+    /// Create a new Docker resolver, honouring `DOCKER_HOST` and falling back to the default socket
     pub async fn new() -> Self {
-        let client = Self::connect().await;
+        let host = DockerHost::from_env_values(
+            std::env::var("DOCKER_HOST").ok().as_deref(),
+            std::env::var("DOCKER_TLS_VERIFY").ok().as_deref(),
+        );
+        let client = Self::connect(&host).await;
         
         if client.is_some() {
-            log::info!("Docker connection established");
+            log::info!("Docker connection established ({})", host.describe());
         } else {
             log::warn!("Docker not available - container features disabled");
         }
 
         Self {
+            endpoint: client.as_ref().map(|_| host.describe()),
+            remote: client.is_some() && host.is_remote(),
             client,
             port_map: Arc::new(RwLock::new(HashMap::new())),
+            containers: Arc::new(RwLock::new(Vec::new())),
+            in_flight: Arc::new(Mutex::new(HashMap::new())),
+            last_removed: Arc::new(Mutex::new(None)),
+            unknown_ids: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
+    /// Connect to an engine listening on an explicit socket path, e.g. Colima's
+    ///
+    /// Unlike `new`, failures are returned rather than leaving Docker disabled, so the
+    /// caller can keep its current connection. A leading `~/` is expanded.
+    pub async fn connect_socket(path: &str) -> Result<Self> {
+        let path = expand_home(path.trim());
+        if !path.exists() {
+            return Err(anyhow!("Docker socket {} does not exist", path.display()));
+        }
+
+        #[cfg(unix)]
+        let host = DockerHost::Socket(format!("unix://{}", path.display()));
+        #[cfg(windows)]
+        let host = DockerHost::Socket(format!("npipe://{}", path.display()));
+
+        let client = Self::try_connect(&host).await?;
+        log::info!("Docker connection established ({})", host.describe());
+
+        Ok(Self {
+            endpoint: Some(host.describe()),
+            remote: false,
+            client: Some(client),
+            port_map: Arc::new(RwLock::new(HashMap::new())),
+            containers: Arc::new(RwLock::new(Vec::new())),
+            in_flight: Arc::new(Mutex::new(HashMap::new())),
+            last_removed: Arc::new(Mutex::new(None)),
+            unknown_ids: Arc::new(Mutex::new(HashMap::new())),
+        })
+    }
+
     /// Attempt to connect to Docker daemon
-    async fn connect() -> Option<Docker> {
-        // Try default connection methods
-        match Docker::connect_with_local_defaults() {
-            Ok(docker) => {
-                // Verify connection works
-                match docker.ping().await {
-                    Ok(_) => Some(docker),
-                    Err(e) => {
-                        log::debug!("Docker ping failed: {}", e);
-                        None
-                    }
+    async fn connect(host: &DockerHost) -> Option<Docker> {
+        match Self::try_connect(host).await {
+            Ok(docker) => Some(docker),
+            Err(e) => {
+                log::debug!("{}", e);
+                None
+            }
+        }
+    }
+
+    /// Build a client for `host`, verify it responds to a ping and agree on an API version
+    ///
+    /// The client starts at the newest API version bollard knows and steps down to the
+    /// engine's if that is older. If negotiation fails the client is kept as it was,
+    /// since most requests still work across nearby versions.
+    async fn try_connect(host: &DockerHost) -> Result<Docker> {
+        let docker = Self::client_for(host)
+            .map_err(|e| anyhow!("Docker connection to {} failed: {}", host.describe(), e))?;
+        docker
+            .ping()
+            .await
+            .map_err(|e| anyhow!("Docker at {} did not respond: {}", host.describe(), e))?;
+
+        match docker.clone().negotiate_version().await {
+            Ok(negotiated) => {
+                log::info!(
+                    "Using Docker API {} with {}",
+                    negotiated.client_version(),
+                    host.describe()
+                );
+                Ok(negotiated)
+            }
+            Err(e) => {
+                log::warn!(
+                    "Docker API version negotiation with {} failed, using {}: {}",
+                    host.describe(),
+                    docker.client_version(),
+                    e
+                );
+                Ok(docker)
+            }
+        }
+    }
+
+    /// Explain the state of the Docker connection
+    ///
+    /// Probes the `DOCKER_HOST` endpoint and every well-known engine socket that exists
+    /// on disk, recording why each one failed, then queries the server version through
+    /// the current client (or the first probe that responded).
+    pub async fn diagnostics(&self) -> DockerDiagnostics {
+        let mut hosts = vec![DockerHost::from_env_values(
+            std::env::var("DOCKER_HOST").ok().as_deref(),
+            std::env::var("DOCKER_TLS_VERIFY").ok().as_deref(),
+        )];
+        hosts.extend(
+            candidate_sockets()
+                .into_iter()
+                .filter(|path| path.exists())
+                .map(|path| DockerHost::Socket(format!("unix://{}", path.display()))),
+        );
+
+        let mut attempts = Vec::new();
+        let mut responding = None;
+        for host in hosts {
+            let result = Self::try_connect(&host).await;
+            attempts.push(DockerEndpointAttempt {
+                endpoint: host.describe(),
+                error: result.as_ref().err().map(|e| e.to_string()),
+            });
+            if responding.is_none() {
+                responding = result.ok();
+            }
+        }
+
+        let mut diagnostics = DockerDiagnostics {
+            available: self.is_available(),
+            endpoint: self.endpoint.clone(),
+            remote: self.remote,
+            attempts,
+            runtime: None,
+            server_version: None,
+            api_version: None,
+            version_error: None,
+        };
+
+        if let Some(client) = self.client.as_ref().or(responding.as_ref()) {
+            match client.version().await {
+                Ok(version) => {
+                    let components: Vec<&str> = version
+                        .components
+                        .iter()
+                        .flatten()
+                        .map(|c| c.name.as_str())
+                        .collect();
+                    let platform = version.platform.as_ref().map(|p| p.name.as_str());
+                    diagnostics.runtime = Some(detect_runtime(platform, &components));
+                    diagnostics.server_version = version.version.clone();
+                    diagnostics.api_version = version.api_version.clone();
                 }
+                Err(e) => diagnostics.version_error = Some(e.to_string()),
+            }
+        }
+
+        diagnostics
+    }
+
+    /// List the containers of every local engine that responds, e.g. Docker and Podman
+    ///
+    /// Probes the well-known sockets `diagnostics` checks. Sockets resolving to the same
+    /// file (Docker Desktop links `/var/run/docker.sock` to its own) are queried once,
+    /// and each container is tagged with its engine's runtime.
+    pub async fn local_engines() -> Vec<EngineContainers> {
+        let mut seen = HashSet::new();
+        let mut engines = Vec::new();
+
+        for path in candidate_sockets() {
+            let Ok(canonical) = std::fs::canonicalize(&path) else {
+                continue;
+            };
+            if !seen.insert(canonical) {
+                continue;
+            }
+
+            let resolver = match Self::connect_socket(&path.to_string_lossy()).await {
+                Ok(resolver) => resolver,
+                Err(e) => {
+                    log::debug!("{}", e);
+                    continue;
+                }
+            };
+            let runtime = resolver.runtime().await;
+            let endpoint = resolver.endpoint.clone().unwrap_or_default();
+            match resolver.get_all_containers().await {
+                Ok(containers) => engines.push(EngineContainers {
+                    containers: containers
+                        .into_iter()
+                        .map(|c| ContainerInfo {
+                            runtime: runtime.clone(),
+                            ..c
+                        })
+                        .collect(),
+                    endpoint,
+                    runtime,
+                }),
+                Err(e) => log::debug!("Failed to list containers at {}: {}", endpoint, e),
+            }
+        }
+
+        engines
+    }
+
+    /// Which runtime serves the API, assuming Docker when the version can't be read
+    pub async fn runtime(&self) -> ContainerRuntime {
+        let Some(client) = &self.client else {
+            return ContainerRuntime::Unknown;
+        };
+        match client.version().await {
+            Ok(version) => {
+                let components: Vec<&str> = version
+                    .components
+                    .iter()
+                    .flatten()
+                    .map(|c| c.name.as_str())
+                    .collect();
+                let platform = version.platform.as_ref().map(|p| p.name.as_str());
+                detect_runtime(platform, &components)
             }
             Err(e) => {
-                log::debug!("Docker connection failed: {}", e);
-                None
+                log::debug!("Failed to read engine version: {}", e);
+                ContainerRuntime::Docker
             }
         }
     }
 
+    /// Build a client for the selected endpoint
+    fn client_for(host: &DockerHost) -> Result<Docker> {
+        let docker = match host {
+            DockerHost::LocalDefault => Docker::connect_with_local_defaults()?,
+            #[cfg(unix)]
+            DockerHost::Socket(addr) => {
+                Docker::connect_with_unix(addr, CONNECT_TIMEOUT_SECS, API_DEFAULT_VERSION)?
+            }
+            #[cfg(windows)]
+            DockerHost::Socket(addr) => {
+                Docker::connect_with_named_pipe(addr, CONNECT_TIMEOUT_SECS, API_DEFAULT_VERSION)?
+            }
+            DockerHost::Http(addr) => {
+                Docker::connect_with_http(addr, CONNECT_TIMEOUT_SECS, API_DEFAULT_VERSION)?
+            }
+            DockerHost::Tls(addr) => Self::connect_tls(addr)?,
+            DockerHost::Ssh(addr) => {
+                return Err(anyhow!(
+                    "{} is an SSH endpoint, which is not supported; forward the remote socket \
+                     with `ssh -L` and point DOCKER_HOST at the local end",
+                    addr
+                ))
+            }
+        };
+        Ok(docker)
+    }
+
+    /// Connect over TLS using the client certificates in `DOCKER_CERT_PATH` (default `~/.docker`)
+    #[cfg(feature = "docker-tls")]
+    fn connect_tls(addr: &str) -> Result<Docker> {
+        let cert_path = std::env::var_os("DOCKER_CERT_PATH")
+            .map(PathBuf::from)
+            .or_else(default_cert_path)
+            .ok_or_else(|| anyhow!("DOCKER_CERT_PATH is not set and no home directory found"))?;
+
+        Ok(Docker::connect_with_ssl(
+            addr,
+            &cert_path.join("key.pem"),
+            &cert_path.join("cert.pem"),
+            &cert_path.join("ca.pem"),
+            CONNECT_TIMEOUT_SECS,
+            API_DEFAULT_VERSION,
+        )?)
+    }
+
+    #[cfg(not(feature = "docker-tls"))]
+    fn connect_tls(addr: &str) -> Result<Docker> {
+        let cert_path = std::env::var_os("DOCKER_CERT_PATH")
+            .map(PathBuf::from)
+            .or_else(default_cert_path);
+        Err(anyhow!(
+            "TLS connection to {} (certs: {:?}) requires building with the `docker-tls` feature",
+            addr,
+            cert_path
+        ))
+    }
+
     /// Check if Docker is available
     pub fn is_available(&self) -> bool {
         self.client.is_some()
     }
 
+    /// Endpoint the client is connected to, if any
+    pub fn endpoint(&self) -> Option<&str> {
+        self.endpoint.as_deref()
+    }
+
+    /// Whether the connected engine runs on another host
+    pub fn is_remote(&self) -> bool {
+        self.remote
+    }
+
+    /// Whether published container ports correspond to sockets on this machine
+    ///
+    /// Ports published by a remote engine live on that host, so matching them
+    /// against local PIDs would produce false correlations.
+    pub fn can_correlate_local_ports(&self) -> bool {
+        self.is_available() && !self.remote
+    }
+
     /// Refresh the port-to-container mapping
     pub async fn refresh(&self) -> Result<()> {
         let client = self.client.as_ref().ok_or_else(|| anyhow!("Docker not available"))?;
@@ -67,18 +486,13 @@ impl DockerResolver {
             ..Default::default()
         };
 
-        let containers = client.list_containers(Some(options)).await?;
-        let mut port_map = self.port_map.write().await;
-        port_map.clear();
-
-        for container in containers {
-            let container_info = self.container_to_info(&container);
-            
-            // Map each host port to this container
-            for port in &container_info.ports {
-                port_map.insert(port.host_port, container_info.clone());
-            }
-        }
+        let containers = with_retry("Listing running containers", move || {
+            let options = options.clone();
+            async move { Ok(client.list_containers(Some(options)).await?) }
+        })
+        .await?;
+        let port_map = build_port_map(containers.iter().map(|c| self.container_to_info(c)));
+        *self.port_map.write().await = port_map;
 
         Ok(())
     }
@@ -114,6 +528,14 @@ impl DockerResolver {
             .map(|n| n.trim_start_matches('/').to_string())
             .unwrap_or_else(|| "unknown".to_string());
 
+        let label = |key: &str| {
+            container
+                .labels
+                .as_ref()
+                .and_then(|labels| labels.get(key))
+                .cloned()
+        };
+
         ContainerInfo {
             id: container.id.clone().unwrap_or_default(),
             name,
@@ -122,13 +544,84 @@ impl DockerResolver {
             state: container.state.clone().unwrap_or_default(),
             runtime: ContainerRuntime::Docker,
             ports,
+            compose_project: label(COMPOSE_PROJECT_LABEL),
+            compose_service: label(COMPOSE_SERVICE_LABEL),
+            health: container.status.as_deref().and_then(parse_health),
         }
     }
 
     /// Get container info for a specific port
-    pub async fn get_container_for_port(&self, port: u16) -> Option<ContainerInfo> {
+    ///
+    /// Only containers publishing the port over `protocol` match; without a protocol,
+    /// TCP publishers are preferred over UDP ones. `host_address` (the listener's local
+    /// address) picks between containers that publish the same port on different host
+    /// IPs; without it, or when no binding matches, the first publisher is returned.
+    pub async fn get_container_for_port(
+        &self,
+        port: u16,
+        protocol: Option<Protocol>,
+        host_address: Option<&str>,
+    ) -> Option<ContainerInfo> {
         let port_map = self.port_map.read().await;
-        port_map.get(&port).cloned()
+        let containers = match protocol {
+            Some(protocol) => port_map.get(&(port, protocol)),
+            None => port_map
+                .get(&(port, Protocol::TCP))
+                .or_else(|| port_map.get(&(port, Protocol::UDP))),
+        }?;
+        select_container(containers, port, host_address).cloned()
+    }
+
+    /// Every running container publishing `port`, over either protocol and on any host IP
+    pub async fn containers_for_port(&self, port: u16) -> Vec<ContainerInfo> {
+        let port_map = self.port_map.read().await;
+        let mut containers: Vec<ContainerInfo> = Vec::new();
+        for protocol in [Protocol::TCP, Protocol::UDP] {
+            for container in port_map.get(&(port, protocol)).into_iter().flatten() {
+                if !containers.iter().any(|c| c.id == container.id) {
+                    containers.push(container.clone());
+                }
+            }
+        }
+        containers
+    }
+
+    /// Drop the port mappings cached for a container after its lifecycle changed
+    ///
+    /// Stopped and removed containers no longer own their ports; started ones are
+    /// picked up by the next `refresh`.
+    pub async fn invalidate_container(&self, container_id: &str) {
+        let mut port_map = self.port_map.write().await;
+        port_map.retain(|_, containers| {
+            containers.retain(|c| c.id != container_id);
+            !containers.is_empty()
+        });
+    }
+
+    /// Stream start, stop, die and destroy events for containers as they happen
+    ///
+    /// The stream holds its own client handle and ends, after yielding the error, when
+    /// the engine goes away.
+    pub fn container_events(&self) -> Result<BoxStream<'static, Result<ContainerEvent>>> {
+        let client = self.client.as_ref().ok_or_else(|| anyhow!("Docker not available"))?;
+
+        let options = EventsOptions::<String> {
+            filters: HashMap::from([
+                ("type".to_string(), vec!["container".to_string()]),
+                (
+                    "event".to_string(),
+                    WATCHED_CONTAINER_ACTIONS.iter().map(|a| a.to_string()).collect(),
+                ),
+            ]),
+            ..Default::default()
+        };
+        let events = client.events(Some(options)).filter_map(|message| async move {
+            match message {
+                Ok(message) => container_event(&message).map(Ok),
+                Err(e) => Some(Err(e.into())),
+            }
+        });
+        Ok(events.boxed())
     }
 
     /// Get all containers with port mappings
@@ -140,19 +633,131 @@ impl DockerResolver {
             ..Default::default()
         };
 
-        let containers = client.list_containers(Some(options)).await?;
-        
-        Ok(containers
-            .iter()
-            .map(|c| self.container_to_info(c))
-            .collect())
+        let containers: Vec<ContainerInfo> = with_retry("Listing containers", move || {
+            let options = options.clone();
+            async move { Ok(client.list_containers(Some(options)).await?) }
+        })
+        .await?
+        .iter()
+        .map(|c| self.container_to_info(c))
+        .collect();
+
+        *self.containers.write().await = containers.clone();
+        Ok(containers)
+    }
+
+    /// Every container with the host ports it claims, including ones that aren't running
+    ///
+    /// A created or exited container lists no published ports, yet starting it binds
+    /// what its `HostConfig.PortBindings` ask for, so those containers are inspected.
+    /// Bindings to a random host port are left out, and a container that can't be
+    /// inspected keeps an empty port list.
+    pub async fn containers_with_port_bindings(&self) -> Result<Vec<ContainerInfo>> {
+        let client = self.client.as_ref().ok_or_else(|| anyhow!("Docker not available"))?;
+        let mut containers = self.get_all_containers().await?;
+
+        for container in containers
+            .iter_mut()
+            .filter(|c| c.state != "running" && c.state != "paused")
+        {
+            let id = container.id.as_str();
+            let inspected = with_retry("Inspecting a container", move || async move {
+                Ok(client.inspect_container(id, None).await?)
+            })
+            .await;
+            match inspected {
+                Ok(inspect) => container.ports = configured_port_bindings(&inspect),
+                Err(e) => log::debug!("Failed to inspect container {}: {}", container.name, e),
+            }
+        }
+
+        Ok(containers)
+    }
+
+    /// Look up containers by full id, e.g. ids read from process cgroups
+    ///
+    /// Uses the cached full listing and reloads it once when an id is missing, so
+    /// containers started since the last listing are found. Unknown ids are left out,
+    /// and don't trigger another reload for a minute.
+    pub async fn containers_by_id(&self, ids: &HashSet<String>) -> HashMap<String, ContainerInfo> {
+        let known = |containers: &[ContainerInfo]| -> HashMap<String, ContainerInfo> {
+            containers
+                .iter()
+                .filter(|c| ids.contains(&c.id))
+                .map(|c| (c.id.clone(), c.clone()))
+                .collect()
+        };
+
+        let found = known(&self.containers.read().await);
+        let now = Instant::now();
+        let needs_reload = {
+            let mut unknown = self.unknown_ids.lock().unwrap_or_else(|e| e.into_inner());
+            unknown.retain(|_, missed_at| now.duration_since(*missed_at) < UNKNOWN_ID_TTL);
+            ids.iter().any(|id| !found.contains_key(id) && !unknown.contains_key(id))
+        };
+        if !needs_reload {
+            return found;
+        }
+        match self.get_all_containers().await {
+            Ok(containers) => {
+                let found = known(&containers);
+                let mut unknown = self.unknown_ids.lock().unwrap_or_else(|e| e.into_inner());
+                unknown.extend(
+                    ids.iter()
+                        .filter(|id| !found.contains_key(*id))
+                        .map(|id| (id.clone(), now)),
+                );
+                found
+            }
+            Err(e) => {
+                log::debug!("Failed to list containers for cgroup lookup: {}", e);
+                found
+            }
+        }
+    }
+
+    /// Resolve a container id or name to an id
+    ///
+    /// Names are looked up in the cached container list, refreshing it once on a miss.
+    /// Identifiers that match nothing are passed through so the engine can resolve
+    /// short ids itself.
+    pub async fn resolve_container(&self, ident: &str) -> Result<String> {
+        if let Some(id) = match_container(&self.containers.read().await, ident)? {
+            return Ok(id);
+        }
+
+        let containers = self.get_all_containers().await?;
+        Ok(match_container(&containers, ident)?.unwrap_or_else(|| ident.to_string()))
+    }
+
+    /// Sample CPU and memory usage for a container
+    ///
+    /// Not streamed: the engine takes two samples about a second apart to compute CPU
+    /// usage, so this is meant for on-demand lookups rather than every scan.
+    pub async fn container_stats(&self, container_id: &str) -> Result<ContainerStats> {
+        let client = self.client.as_ref().ok_or_else(|| anyhow!("Docker not available"))?;
+
+        let options = StatsOptions {
+            stream: false,
+            one_shot: false, // one_shot skips the second sample needed for CPU usage
+        };
+        let stats = client
+            .stats(container_id, Some(options))
+            .next()
+            .await
+            .ok_or_else(|| anyhow!("No stats returned for container {}", container_id))??;
+
+        Ok(stats_to_container_stats(container_id, &stats))
     }
 
     /// Stop a container gracefully
-    pub async fn stop_container(&self, container_id: &str) -> Result<()> {
+    ///
+    /// Docker sends SIGTERM, then SIGKILL if the container is still running after
+    /// `timeout_secs`; 0 kills it immediately.
+    pub async fn stop_container(&self, container_id: &str, timeout_secs: u32) -> Result<()> {
         let client = self.client.as_ref().ok_or_else(|| anyhow!("Docker not available"))?;
         
-        let options = StopContainerOptions { t: 10 }; // 10 second timeout
+        let options = StopContainerOptions { t: i64::from(timeout_secs) };
         client.stop_container(container_id, Some(options)).await?;
         
         log::info!("Stopped container: {}", container_id);
@@ -171,23 +776,154 @@ impl DockerResolver {
     }
 
     /// Remove a container
+    ///
+    /// Its configuration is inspected first and kept so `recreate_last_removed` can
+    /// undo the removal within `RECREATE_WINDOW`. A failed inspect does not block the
+    /// removal; there is just nothing to undo afterwards.
     pub async fn remove_container(&self, container_id: &str, force: bool) -> Result<()> {
         let client = self.client.as_ref().ok_or_else(|| anyhow!("Docker not available"))?;
-        
+
+        let captured = match client.inspect_container(container_id, None).await {
+            Ok(inspect) => removed_container(inspect),
+            Err(e) => Err(e.into()),
+        };
+        let captured = captured
+            .map_err(|e| log::warn!("Cannot keep {} for undo: {:#}", container_id, e))
+            .ok();
+
         let options = RemoveContainerOptions {
             force,
             ..Default::default()
         };
         client.remove_container(container_id, Some(options)).await?;
-        
+
         log::info!("Removed container: {}", container_id);
+        if let Some(captured) = captured {
+            *self.last_removed.lock().unwrap_or_else(|e| e.into_inner()) = Some(captured);
+        }
         Ok(())
     }
 
+    /// Recreate the most recently removed container with its original configuration
+    ///
+    /// Returns `None` when nothing was removed within `RECREATE_WINDOW`. The container
+    /// is started again if it was running when removed. On failure the captured
+    /// configuration is kept so the call can be retried.
+    pub async fn recreate_last_removed(&self) -> Result<Option<RecreateResult>> {
+        let client = self.client.as_ref().ok_or_else(|| anyhow!("Docker not available"))?;
+
+        let removed = {
+            let mut last_removed = self.last_removed.lock().unwrap_or_else(|e| e.into_inner());
+            if last_removed
+                .as_ref()
+                .is_some_and(|removed| removed.removed_at.elapsed() > RECREATE_WINDOW)
+            {
+                *last_removed = None;
+            }
+            match last_removed.as_ref() {
+                Some(removed) => removed.clone(),
+                None => return Ok(None),
+            }
+        };
+
+        let options = CreateContainerOptions {
+            name: removed.name.clone(),
+            platform: None,
+        };
+        let created = client.create_container(Some(options), removed.config).await?;
+        *self.last_removed.lock().unwrap_or_else(|e| e.into_inner()) = None;
+        log::info!("Recreated container {} as {}", removed.name, created.id);
+
+        let mut warnings = removed.warnings;
+        warnings.extend(created.warnings);
+        let mut started = false;
+        if removed.was_running {
+            match client
+                .start_container(&created.id, None::<StartContainerOptions<String>>)
+                .await
+            {
+                Ok(()) => started = true,
+                Err(e) => warnings.push(format!("Recreated but failed to start: {}", e)),
+            }
+        }
+
+        Ok(Some(RecreateResult {
+            id: created.id,
+            name: removed.name,
+            started,
+            warnings,
+        }))
+    }
+
     /// Execute a container action
-    pub async fn execute_action(&self, container_id: &str, action: ContainerAction) -> Result<()> {
+    ///
+    /// A repeat of an action that is still running on the same container returns
+    /// `AlreadyInProgress` instead of hitting the engine again. `stop_timeout_secs` is
+    /// only used by `Stop`; see `stop_container`.
+    pub async fn execute_action(
+        &self,
+        container_id: &str,
+        action: ContainerAction,
+        stop_timeout_secs: u32,
+    ) -> Result<ActionStatus> {
+        let Some(_guard) = self.begin_action(container_id, &action) else {
+            log::debug!("{:?} already in progress for {}", action, container_id);
+            return Ok(ActionStatus::AlreadyInProgress);
+        };
+
+        self.run_action_with_retry(container_id, action, stop_timeout_secs).await?;
+        Ok(ActionStatus::Completed)
+    }
+
+    /// Run an action, retrying it while the engine is unreachable if that is safe
+    ///
+    /// Only `Stop` is retried: a request that failed in transit may still have been
+    /// carried out, and stopping again is harmless, whereas a second restart, kill or
+    /// removal is not.
+    async fn run_action_with_retry(
+        &self,
+        container_id: &str,
+        action: ContainerAction,
+        stop_timeout_secs: u32,
+    ) -> Result<()> {
+        if action != ContainerAction::Stop {
+            return self.run_action(container_id, action, stop_timeout_secs).await;
+        }
+
+        let what = format!("{:?} on {}", action, container_id);
+        with_retry(&what, move || {
+            self.run_action(container_id, action.clone(), stop_timeout_secs)
+        })
+        .await
+    }
+
+    /// Mark an action as in flight, or return None if a recent identical one still is
+    fn begin_action(&self, container_id: &str, action: &ContainerAction) -> Option<InFlightGuard> {
+        let key = (container_id.to_string(), action.clone());
+        let mut in_flight = self.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+
+        if in_flight
+            .get(&key)
+            .is_some_and(|started| started.elapsed() < ACTION_DEBOUNCE_WINDOW)
+        {
+            return None;
+        }
+        in_flight.insert(key.clone(), Instant::now());
+
+        Some(InFlightGuard {
+            in_flight: self.in_flight.clone(),
+            key,
+        })
+    }
+
+    async fn run_action(
+        &self,
+        container_id: &str,
+        action: ContainerAction,
+        stop_timeout_secs: u32,
+    ) -> Result<()> {
         match action {
-            ContainerAction::Stop => self.stop_container(container_id).await,
+            ContainerAction::Stop => self.stop_container(container_id, stop_timeout_secs).await,
             ContainerAction::Kill => self.kill_container(container_id).await,
             ContainerAction::Remove => self.remove_container(container_id, true).await,
             ContainerAction::Restart => {
@@ -199,19 +935,449 @@ impl DockerResolver {
     }
 }
 
+/// Clears an in-flight action when it completes, fails or is cancelled
+struct InFlightGuard {
+    in_flight: InFlightActions,
+    key: (String, ContainerAction),
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        let mut in_flight = self.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+        in_flight.remove(&self.key);
+    }
+}
+
 impl Default for DockerResolver {
     fn default() -> Self {
         // Create without async - client will be None
         Self {
             client: None,
             port_map: Arc::new(RwLock::new(HashMap::new())),
+            containers: Arc::new(RwLock::new(Vec::new())),
+            endpoint: None,
+            remote: false,
+            in_flight: Arc::new(Mutex::new(HashMap::new())),
+            last_removed: Arc::new(Mutex::new(None)),
+            unknown_ids: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+/// Capture what is needed to recreate a container from its inspect output
+///
+/// Host settings (port bindings, binds, restart policy, network mode) are kept as they
+/// were. Anonymous volumes and secondary networks cannot be brought back and are
+/// listed as warnings.
+fn removed_container(inspect: ContainerInspectResponse) -> Result<RemovedContainer> {
+    let name = inspect
+        .name
+        .as_deref()
+        .map(|name| name.trim_start_matches('/').to_string())
+        .filter(|name| !name.is_empty())
+        .ok_or_else(|| anyhow!("Container has no name"))?;
+    let container_config = inspect
+        .config
+        .ok_or_else(|| anyhow!("Container {} has no configuration", name))?;
+    let host_config = inspect.host_config.unwrap_or_default();
+    let mut warnings = Vec::new();
+
+    for mount in inspect.mounts.unwrap_or_default() {
+        let anonymous = mount
+            .name
+            .as_deref()
+            .is_some_and(|name| name.len() == 64 && name.chars().all(|c| c.is_ascii_hexdigit()));
+        if mount.typ == Some(MountPointTypeEnum::VOLUME) && anonymous {
+            warnings.push(format!(
+                "Anonymous volume at {} starts empty",
+                mount.destination.as_deref().unwrap_or("?")
+            ));
+        }
+    }
+
+    // "default" is the engine's name for the bridge network in HostConfig
+    let network_mode = match host_config.network_mode.as_deref() {
+        Some("default") => "bridge".to_string(),
+        mode => mode.unwrap_or_default().to_string(),
+    };
+    if network_mode.starts_with("container:") {
+        warnings.push(format!("Shares the network of {}, which may be gone", network_mode));
+    }
+    let mut extra_networks: Vec<String> = inspect
+        .network_settings
+        .and_then(|settings| settings.networks)
+        .unwrap_or_default()
+        .into_keys()
+        .filter(|network| *network != network_mode)
+        .collect();
+    extra_networks.sort();
+    if !network_mode.is_empty() && !extra_networks.is_empty() {
+        warnings.push(format!("Not reconnected to {}", extra_networks.join(", ")));
+    }
+
+    let mut config = Config::from(container_config);
+    config.host_config = Some(host_config);
+
+    Ok(RemovedContainer {
+        name,
+        config,
+        was_running: inspect.state.and_then(|state| state.running).unwrap_or(false),
+        warnings,
+        removed_at: Instant::now(),
+    })
+}
+
+/// Host ports a container's `HostConfig.PortBindings` request, sorted by host port
+///
+/// Keys look like `80/tcp`. Bindings without a fixed host port (empty or `0`) get a
+/// random one at start and are skipped, as are host port ranges.
+fn configured_port_bindings(inspect: &ContainerInspectResponse) -> Vec<ContainerPort> {
+    let Some(bindings) = inspect
+        .host_config
+        .as_ref()
+        .and_then(|host_config| host_config.port_bindings.as_ref())
+    else {
+        return Vec::new();
+    };
+
+    let mut ports = Vec::new();
+    for (spec, bindings) in bindings {
+        let (container_port, protocol) = spec.split_once('/').unwrap_or((spec, "tcp"));
+        let Ok(container_port) = container_port.parse::<u16>() else {
+            continue;
+        };
+        let protocol = match protocol {
+            "udp" => Protocol::UDP,
+            _ => Protocol::TCP,
+        };
+        for binding in bindings.iter().flatten() {
+            let Some(host_port) = binding
+                .host_port
+                .as_deref()
+                .and_then(|port| port.parse::<u16>().ok())
+                .filter(|&port| port != 0)
+            else {
+                continue;
+            };
+            ports.push(ContainerPort {
+                host_port,
+                container_port,
+                protocol,
+                host_ip: binding.host_ip.clone().filter(|ip| !ip.is_empty()),
+            });
+        }
+    }
+
+    ports.sort_by_key(|p| (p.host_port, p.container_port));
+    ports
+}
+
+/// Find the id of the container whose id or name equals `ident`
+///
+/// Errors when the name is shared by several containers, listing the candidates.
+fn match_container(containers: &[ContainerInfo], ident: &str) -> Result<Option<String>> {
+    if let Some(container) = containers.iter().find(|c| c.id == ident) {
+        return Ok(Some(container.id.clone()));
+    }
+
+    let named: Vec<&ContainerInfo> = containers.iter().filter(|c| c.name == ident).collect();
+    match named.as_slice() {
+        [] => Ok(None),
+        [container] => Ok(Some(container.id.clone())),
+        candidates => Err(anyhow!(
+            "Container name '{}' is ambiguous: {}",
+            ident,
+            candidates
+                .iter()
+                .map(|c| format!("{} ({})", c.name, short_id(&c.id)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+/// Reduce a Docker stats sample to the figures `docker stats` shows
+fn stats_to_container_stats(container_id: &str, stats: &Stats) -> ContainerStats {
+    let cpu = &stats.cpu_stats;
+    let precpu = &stats.precpu_stats;
+    let online_cpus = cpu
+        .online_cpus
+        .or_else(|| cpu.cpu_usage.percpu_usage.as_ref().map(|p| p.len() as u64))
+        .unwrap_or(1);
+
+    let cpu_percent = cpu_percent(
+        cpu.cpu_usage.total_usage.saturating_sub(precpu.cpu_usage.total_usage),
+        cpu.system_cpu_usage
+            .unwrap_or(0)
+            .saturating_sub(precpu.system_cpu_usage.unwrap_or(0)),
+        online_cpus,
+    );
+
+    // Page cache is reclaimable, so the CLI leaves it out of the usage figure
+    let cache = match &stats.memory_stats.stats {
+        Some(MemoryStatsStats::V1(v1)) => v1.total_inactive_file,
+        Some(MemoryStatsStats::V2(v2)) => v2.inactive_file,
+        None => 0,
+    };
+
+    ContainerStats {
+        container_id: container_id.to_string(),
+        cpu_percent,
+        memory_usage: stats.memory_stats.usage.unwrap_or(0).saturating_sub(cache),
+        memory_limit: stats.memory_stats.limit.unwrap_or(0),
+    }
+}
+
+/// CPU usage as a percentage of one core, from deltas between two samples
+fn cpu_percent(cpu_delta: u64, system_delta: u64, online_cpus: u64) -> f64 {
+    if cpu_delta == 0 || system_delta == 0 {
+        return 0.0;
+    }
+    cpu_delta as f64 / system_delta as f64 * online_cpus as f64 * 100.0
+}
+
+/// The 12-character id prefix shown by the Docker CLI
+fn short_id(id: &str) -> &str {
+    id.get(..12).unwrap_or(id)
+}
+
+/// Expand a leading `~/` to the user's home directory
+fn expand_home(path: &str) -> PathBuf {
+    let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"));
+    match (path.strip_prefix("~/"), home) {
+        (Some(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Convert an engine event into a `ContainerEvent`, if it is one we watch
+fn container_event(message: &EventMessage) -> Option<ContainerEvent> {
+    if message.typ != Some(EventMessageTypeEnum::CONTAINER) {
+        return None;
+    }
+    let action = message.action.as_deref()?;
+    let state = match action {
+        "start" => "running",
+        "stop" | "die" => "exited",
+        "destroy" => "removed",
+        _ => return None,
+    };
+    let actor = message.actor.as_ref()?;
+    let name = actor
+        .attributes
+        .as_ref()
+        .and_then(|attributes| attributes.get("name"))
+        .cloned();
+    let at = message
+        .time
+        .and_then(|secs| DateTime::from_timestamp(secs, 0))
+        .unwrap_or_else(Utc::now);
+
+    Some(ContainerEvent {
+        id: actor.id.clone()?,
+        name,
+        action: action.to_string(),
+        state: state.to_string(),
+        at,
+    })
+}
+
+/// Read the healthcheck verdict Docker appends to a status like `Up 2 hours (healthy)`
+fn parse_health(status: &str) -> Option<ContainerHealth> {
+    if status.contains("(unhealthy)") {
+        Some(ContainerHealth::Unhealthy)
+    } else if status.contains("(healthy)") {
+        Some(ContainerHealth::Healthy)
+    } else if status.contains("(health: starting)") {
+        Some(ContainerHealth::Starting)
+    } else {
+        None
+    }
+}
+
+/// Run an idempotent engine call, retrying with exponential backoff while the engine
+/// is unreachable
+///
+/// Errors the engine reports about the request itself (no such container, conflicts)
+/// are returned immediately, as is the last error once attempts run out. `what`
+/// describes the call in log messages.
+async fn with_retry<T, F, Fut>(what: &str, mut call: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T>>,
+{
+    let mut delay = RETRY_BACKOFF;
+    let mut attempt = 1;
+
+    loop {
+        match call().await {
+            Err(e) if attempt < RETRY_ATTEMPTS && is_retryable(&e) => {
+                log::warn!(
+                    "{} failed (attempt {}/{}), retrying in {:?}: {}",
+                    what,
+                    attempt,
+                    RETRY_ATTEMPTS,
+                    delay,
+                    e
+                );
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+                attempt += 1;
+            }
+            result => return result,
         }
     }
 }
 
+/// Whether an engine call failed for transient reasons worth retrying
+///
+/// Connection-level failures (daemon restarting, socket busy, timeouts) and gateway
+/// errors from a proxy in front of the engine qualify; any other status the engine
+/// answers with describes the request itself and would fail again.
+fn is_retryable(error: &anyhow::Error) -> bool {
+    use bollard::errors::Error as BollardError;
+    use std::io::ErrorKind;
+
+    match error.downcast_ref::<BollardError>() {
+        Some(BollardError::DockerResponseServerError { status_code, .. }) => {
+            matches!(status_code, 502..=504)
+        }
+        Some(BollardError::IOError { err }) => matches!(
+            err.kind(),
+            ErrorKind::ConnectionReset
+                | ErrorKind::ConnectionRefused
+                | ErrorKind::ConnectionAborted
+                | ErrorKind::BrokenPipe
+                | ErrorKind::TimedOut
+                | ErrorKind::Interrupted
+        ),
+        Some(BollardError::HyperResponseError { .. })
+        | Some(BollardError::HyperLegacyError { .. })
+        | Some(BollardError::RequestTimeoutError) => true,
+        _ => false,
+    }
+}
+
+/// Whether the engine refused a request over the API version the client speaks
+///
+/// Engines reply with e.g. "client version 1.47 is too new. Maximum supported API
+/// version is 1.41". Errors wrapped with extra context are recognised too.
+pub fn is_version_mismatch(error: &anyhow::Error) -> bool {
+    use bollard::errors::Error as BollardError;
+
+    error.chain().any(|cause| {
+        if let Some(BollardError::APIVersionParseError {}) = cause.downcast_ref::<BollardError>() {
+            return true;
+        }
+        let message = cause.to_string().to_lowercase();
+        message.contains("client version")
+            && (message.contains("too new") || message.contains("too old"))
+    })
+}
+
+/// Index containers by each published host port and protocol
+///
+/// A container appears once per key even when it binds the port on several host IPs.
+fn build_port_map(
+    containers: impl IntoIterator<Item = ContainerInfo>,
+) -> HashMap<(u16, Protocol), Vec<ContainerInfo>> {
+    let mut port_map: HashMap<(u16, Protocol), Vec<ContainerInfo>> = HashMap::new();
+
+    for container in containers {
+        for port in &container.ports {
+            let containers = port_map.entry((port.host_port, port.protocol)).or_default();
+            if !containers.iter().any(|c| c.id == container.id) {
+                containers.push(container.clone());
+            }
+        }
+    }
+
+    port_map
+}
+
+/// Pick the container whose binding of `port` is on `host_address`, else the first one
+fn select_container<'a>(
+    containers: &'a [ContainerInfo],
+    port: u16,
+    host_address: Option<&str>,
+) -> Option<&'a ContainerInfo> {
+    let wanted = host_address.and_then(|addr| addr.parse::<IpAddr>().ok());
+
+    wanted
+        .and_then(|wanted| {
+            containers.iter().find(|container| {
+                container.ports.iter().any(|binding| {
+                    binding.host_port == port
+                        && binding
+                            .host_ip
+                            .as_deref()
+                            .and_then(|ip| ip.parse::<IpAddr>().ok())
+                            .is_some_and(|ip| ip.to_canonical() == wanted.to_canonical())
+                })
+            })
+        })
+        .or_else(|| containers.first())
+}
+
+/// Containers listed from one engine by `DockerResolver::local_engines`
+#[derive(Debug, Clone)]
+pub struct EngineContainers {
+    pub endpoint: String,
+    pub runtime: ContainerRuntime,
+    pub containers: Vec<ContainerInfo>,
+}
+
+/// Engine sockets used by common Docker setups, checked by `diagnostics`
+#[cfg(unix)]
+fn candidate_sockets() -> Vec<PathBuf> {
+    let mut sockets: Vec<PathBuf> = [
+        "/var/run/docker.sock",
+        "~/.docker/run/docker.sock",
+        "~/.colima/default/docker.sock",
+        "~/.orbstack/run/docker.sock",
+        "/run/podman/podman.sock",
+    ]
+    .iter()
+    .map(|path| expand_home(path))
+    .collect();
+
+    if let Some(runtime_dir) = std::env::var_os("XDG_RUNTIME_DIR") {
+        sockets.push(PathBuf::from(runtime_dir).join("podman/podman.sock"));
+    }
+    sockets
+}
+
+/// Windows engines are reached through the default named pipe only
+#[cfg(windows)]
+fn candidate_sockets() -> Vec<PathBuf> {
+    Vec::new()
+}
+
+/// Tell Podman's Docker-compatible API apart from Docker by its version report
+fn detect_runtime(platform_name: Option<&str>, component_names: &[&str]) -> ContainerRuntime {
+    let is_podman = platform_name
+        .into_iter()
+        .chain(component_names.iter().copied())
+        .any(|name| name.to_lowercase().contains("podman"));
+
+    if is_podman {
+        ContainerRuntime::Podman
+    } else {
+        ContainerRuntime::Docker
+    }
+}
+
+/// Default location of Docker client certificates
+fn default_cert_path() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(|home| PathBuf::from(home).join(".docker"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fixtures::{self, published_port};
 
     #[tokio::test]
     async fn test_docker_connection() {
@@ -223,4 +1389,456 @@ mod tests {
             println!("Containers: {:?}", containers);
         }
     }
+
+    #[test]
+    fn test_docker_host_from_env() {
+        assert_eq!(DockerHost::from_env_values(None, None), DockerHost::LocalDefault);
+        assert_eq!(DockerHost::from_env_values(Some(""), None), DockerHost::LocalDefault);
+        assert_eq!(
+            DockerHost::from_env_values(Some("unix:///var/run/docker.sock"), None),
+            DockerHost::Socket("unix:///var/run/docker.sock".to_string())
+        );
+        assert_eq!(
+            DockerHost::from_env_values(Some("tcp://10.0.0.5:2375"), Some("0")),
+            DockerHost::Http("tcp://10.0.0.5:2375".to_string())
+        );
+        assert_eq!(
+            DockerHost::from_env_values(Some("tcp://10.0.0.5:2376"), Some("1")),
+            DockerHost::Tls("tcp://10.0.0.5:2376".to_string())
+        );
+        assert_eq!(
+            DockerHost::from_env_values(Some("ssh://me@build-box"), Some("1")),
+            DockerHost::Ssh("ssh://me@build-box".to_string())
+        );
+        assert!(DockerHost::Ssh("ssh://me@build-box:22".to_string()).is_remote());
+        assert!(!DockerHost::Http("tcp://localhost:2375".to_string()).is_remote());
+    }
+
+    #[test]
+    fn test_select_container_by_host_ip() {
+        let container = |id: &str, host_ip: &str| ContainerInfo {
+            ports: vec![ContainerPort {
+                host_ip: Some(host_ip.to_string()),
+                ..published_port(8080, 80)
+            }],
+            ..fixtures::container(id, id)
+        };
+        let containers = vec![container("public", "192.168.1.20"), container("local", "127.0.0.1")];
+
+        let pick = |address: Option<&str>| {
+            select_container(&containers, 8080, address).map(|c| c.id.as_str())
+        };
+        assert_eq!(pick(Some("127.0.0.1")), Some("local"));
+        assert_eq!(pick(Some("::ffff:192.168.1.20")), Some("public"));
+        // Unknown or unmatched addresses fall back to the first container
+        assert_eq!(pick(None), Some("public"));
+        assert_eq!(pick(Some("10.0.0.1")), Some("public"));
+        assert_eq!(select_container(&[], 8080, None), None);
+    }
+
+    #[test]
+    fn test_parse_health() {
+        assert_eq!(parse_health("Up 2 hours (healthy)"), Some(ContainerHealth::Healthy));
+        assert_eq!(parse_health("Up 3 minutes (unhealthy)"), Some(ContainerHealth::Unhealthy));
+        assert_eq!(
+            parse_health("Up 5 seconds (health: starting)"),
+            Some(ContainerHealth::Starting)
+        );
+        assert_eq!(parse_health("Up 2 hours"), None);
+        assert_eq!(parse_health("Exited (0) 3 days ago"), None);
+    }
+
+    #[test]
+    fn test_container_event() {
+        use bollard::models::EventActor;
+
+        let message = |typ, action: &str| EventMessage {
+            typ: Some(typ),
+            action: Some(action.to_string()),
+            actor: Some(EventActor {
+                id: Some("abc123".to_string()),
+                attributes: Some(HashMap::from([("name".to_string(), "web".to_string())])),
+            }),
+            time: Some(1_700_000_000),
+            ..Default::default()
+        };
+
+        let event = container_event(&message(EventMessageTypeEnum::CONTAINER, "die")).unwrap();
+        assert_eq!(event.id, "abc123");
+        assert_eq!(event.name.as_deref(), Some("web"));
+        assert_eq!(event.state, "exited");
+        assert_eq!(event.at.timestamp(), 1_700_000_000);
+
+        let started = container_event(&message(EventMessageTypeEnum::CONTAINER, "start"));
+        assert_eq!(started.unwrap().state, "running");
+        assert!(container_event(&message(EventMessageTypeEnum::CONTAINER, "pause")).is_none());
+        assert!(container_event(&message(EventMessageTypeEnum::IMAGE, "destroy")).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_container() {
+        let resolver = DockerResolver::default();
+        resolver
+            .port_map
+            .write()
+            .await
+            .extend([
+                (
+                    (80, Protocol::TCP),
+                    vec![fixtures::container("a", "a"), fixtures::container("b", "b")],
+                ),
+                ((443, Protocol::TCP), vec![fixtures::container("a", "a")]),
+            ]);
+
+        resolver.invalidate_container("a").await;
+        let port_map = resolver.port_map.read().await;
+        assert_eq!(port_map.len(), 1);
+        assert_eq!(port_map[&(80, Protocol::TCP)][0].id, "b");
+    }
+
+    #[tokio::test]
+    async fn test_containers_by_id_skips_remembered_misses() {
+        let resolver = DockerResolver::default();
+        *resolver.containers.write().await = vec![fixtures::container("a", "a")];
+        let now = Instant::now();
+        {
+            let mut unknown = resolver.unknown_ids.lock().unwrap();
+            unknown.insert("podman".to_string(), now);
+            // The clock may not reach back a full TTL on a freshly booted host
+            if let Some(expired) = now.checked_sub(UNKNOWN_ID_TTL) {
+                unknown.insert("gone".to_string(), expired);
+            }
+        }
+
+        let ids = HashSet::from(["a".to_string(), "podman".to_string()]);
+        let found = resolver.containers_by_id(&ids).await;
+        assert_eq!(found.keys().collect::<Vec<_>>(), vec!["a"]);
+        let unknown = resolver.unknown_ids.lock().unwrap();
+        assert!(unknown.contains_key("podman"));
+        assert!(!unknown.contains_key("gone"));
+    }
+
+    #[tokio::test]
+    async fn test_port_map_keeps_protocols_apart() {
+        let container = |id: &str, ports: &[(u16, Protocol)]| ContainerInfo {
+            image: "coredns".to_string(),
+            ports: ports
+                .iter()
+                .map(|&(host_port, protocol)| ContainerPort {
+                    protocol,
+                    ..published_port(host_port, host_port)
+                })
+                .collect(),
+            ..fixtures::container(id, id)
+        };
+        // "dns" publishes 53 over both protocols; "syslog" only UDP 514, "web" TCP 514
+        let resolver = DockerResolver::default();
+        *resolver.port_map.write().await = build_port_map([
+            container("dns", &[(53, Protocol::TCP), (53, Protocol::UDP)]),
+            container("web", &[(514, Protocol::TCP)]),
+            container("syslog", &[(514, Protocol::UDP)]),
+        ]);
+
+        let owner = |container: Option<ContainerInfo>| container.map(|c| c.id);
+        let tcp = Some(Protocol::TCP);
+        let udp = Some(Protocol::UDP);
+        assert_eq!(owner(resolver.get_container_for_port(53, tcp, None).await), Some("dns".into()));
+        assert_eq!(owner(resolver.get_container_for_port(53, udp, None).await), Some("dns".into()));
+        assert_eq!(
+            owner(resolver.get_container_for_port(514, tcp, None).await),
+            Some("web".into())
+        );
+        assert_eq!(
+            owner(resolver.get_container_for_port(514, udp, None).await),
+            Some("syslog".into())
+        );
+        // Without a protocol TCP publishers win
+        assert_eq!(
+            owner(resolver.get_container_for_port(514, None, None).await),
+            Some("web".into())
+        );
+        assert!(resolver.get_container_for_port(8080, None, None).await.is_none());
+
+        assert_eq!(resolver.containers_for_port(53).await.len(), 1);
+        assert_eq!(resolver.containers_for_port(514).await.len(), 2);
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        use bollard::errors::Error as BollardError;
+
+        let server_error = |status_code| {
+            anyhow::Error::from(BollardError::DockerResponseServerError {
+                status_code,
+                message: "error".to_string(),
+            })
+        };
+        let io_error = |kind| {
+            anyhow::Error::from(BollardError::IOError {
+                err: std::io::Error::from(kind),
+            })
+        };
+
+        assert!(is_retryable(&io_error(std::io::ErrorKind::ConnectionReset)));
+        assert!(is_retryable(&anyhow::Error::from(BollardError::RequestTimeoutError)));
+        assert!(is_retryable(&server_error(503)));
+        // No such container / conflicts won't change on retry
+        assert!(!is_retryable(&server_error(404)));
+        assert!(!is_retryable(&server_error(409)));
+        assert!(!is_retryable(&io_error(std::io::ErrorKind::PermissionDenied)));
+        assert!(!is_retryable(&anyhow!("Docker not available")));
+    }
+
+    #[tokio::test]
+    async fn test_with_retry_stops_at_request_errors() {
+        use bollard::errors::Error as BollardError;
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let calls = AtomicU32::new(0);
+        let timed_out: Result<()> = with_retry("Listing containers", || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(BollardError::RequestTimeoutError.into())
+        })
+        .await;
+        assert!(timed_out.is_err());
+        assert_eq!(calls.swap(0, Ordering::SeqCst), RETRY_ATTEMPTS);
+
+        let missing: Result<()> = with_retry("Inspecting a container", || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(anyhow!("No such container"))
+        })
+        .await;
+        assert!(missing.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_is_version_mismatch() {
+        use bollard::errors::Error as BollardError;
+
+        let too_new = anyhow::Error::from(BollardError::DockerResponseServerError {
+            status_code: 400,
+            message: "client version 1.47 is too new. Maximum supported API version is 1.41"
+                .to_string(),
+        });
+        assert!(is_version_mismatch(&too_new));
+        assert!(is_version_mismatch(&too_new.context("Failed to list containers")));
+        assert!(is_version_mismatch(&anyhow::Error::from(
+            BollardError::APIVersionParseError {}
+        )));
+
+        let missing = anyhow::Error::from(BollardError::DockerResponseServerError {
+            status_code: 404,
+            message: "No such container: web".to_string(),
+        });
+        assert!(!is_version_mismatch(&missing));
+    }
+
+    #[test]
+    fn test_detect_runtime() {
+        assert_eq!(
+            detect_runtime(Some("Docker Engine - Community"), &["Engine", "containerd"]),
+            ContainerRuntime::Docker
+        );
+        assert_eq!(
+            detect_runtime(None, &["Podman Engine", "Conmon"]),
+            ContainerRuntime::Podman
+        );
+        assert_eq!(detect_runtime(None, &[]), ContainerRuntime::Docker);
+    }
+
+    #[test]
+    fn test_begin_action_debounces_duplicates() {
+        let resolver = DockerResolver::default();
+
+        let guard = resolver.begin_action("abc123", &ContainerAction::Stop);
+        assert!(guard.is_some());
+        assert!(resolver.begin_action("abc123", &ContainerAction::Stop).is_none());
+        // Other actions and other containers are not blocked
+        assert!(resolver.begin_action("abc123", &ContainerAction::Kill).is_some());
+        assert!(resolver.begin_action("def456", &ContainerAction::Stop).is_some());
+
+        drop(guard);
+        assert!(resolver.begin_action("abc123", &ContainerAction::Stop).is_some());
+    }
+
+    #[test]
+    fn test_docker_host_is_remote() {
+        assert!(!DockerHost::LocalDefault.is_remote());
+        assert!(!DockerHost::Http("tcp://localhost:2375".to_string()).is_remote());
+        assert!(!DockerHost::Http("tcp://127.0.0.1:2375".to_string()).is_remote());
+        assert!(DockerHost::Http("tcp://build-box:2375".to_string()).is_remote());
+        assert!(DockerHost::Tls("tcp://[2001:db8::1]:2376".to_string()).is_remote());
+    }
+
+    #[test]
+    fn test_container_to_info_compose_labels() {
+        let resolver = DockerResolver::default();
+
+        let compose = bollard::models::ContainerSummary {
+            names: Some(vec!["/myapp-web-1".to_string()]),
+            labels: Some(HashMap::from([
+                (COMPOSE_PROJECT_LABEL.to_string(), "myapp".to_string()),
+                (COMPOSE_SERVICE_LABEL.to_string(), "web".to_string()),
+            ])),
+            ..Default::default()
+        };
+        let info = resolver.container_to_info(&compose);
+        assert_eq!(info.name, "myapp-web-1");
+        assert_eq!(info.compose_project.as_deref(), Some("myapp"));
+        assert_eq!(info.compose_service.as_deref(), Some("web"));
+
+        let standalone = bollard::models::ContainerSummary::default();
+        let info = resolver.container_to_info(&standalone);
+        assert_eq!(info.compose_project, None);
+        assert_eq!(info.compose_service, None);
+    }
+
+    #[test]
+    fn test_removed_container() {
+        use bollard::models::{
+            ContainerConfig, ContainerState, EndpointSettings, HostConfig, MountPoint,
+            NetworkSettings, PortBinding,
+        };
+
+        let volume = |name: &str, destination: &str| MountPoint {
+            typ: Some(MountPointTypeEnum::VOLUME),
+            name: Some(name.to_string()),
+            destination: Some(destination.to_string()),
+            ..Default::default()
+        };
+        let inspect = ContainerInspectResponse {
+            name: Some("/web".to_string()),
+            state: Some(ContainerState {
+                running: Some(true),
+                ..Default::default()
+            }),
+            config: Some(ContainerConfig {
+                image: Some("nginx:1.27".to_string()),
+                env: Some(vec!["MODE=dev".to_string()]),
+                ..Default::default()
+            }),
+            host_config: Some(HostConfig {
+                network_mode: Some("default".to_string()),
+                port_bindings: Some(HashMap::from([(
+                    "80/tcp".to_string(),
+                    Some(vec![PortBinding {
+                        host_ip: None,
+                        host_port: Some("8080".to_string()),
+                    }]),
+                )])),
+                ..Default::default()
+            }),
+            mounts: Some(vec![
+                volume(&"a".repeat(64), "/var/cache/nginx"),
+                volume("web-data", "/usr/share/nginx/html"),
+            ]),
+            network_settings: Some(NetworkSettings {
+                networks: Some(HashMap::from([
+                    ("bridge".to_string(), EndpointSettings::default()),
+                    ("backend".to_string(), EndpointSettings::default()),
+                ])),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let removed = removed_container(inspect).unwrap();
+        assert_eq!(removed.name, "web");
+        assert!(removed.was_running);
+        assert_eq!(removed.config.image.as_deref(), Some("nginx:1.27"));
+        assert_eq!(removed.config.env, Some(vec!["MODE=dev".to_string()]));
+        let host_config = removed.config.host_config.unwrap();
+        assert!(host_config.port_bindings.unwrap().contains_key("80/tcp"));
+        assert_eq!(
+            removed.warnings,
+            vec![
+                "Anonymous volume at /var/cache/nginx starts empty".to_string(),
+                "Not reconnected to backend".to_string(),
+            ]
+        );
+
+        assert!(removed_container(ContainerInspectResponse::default()).is_err());
+    }
+
+    #[test]
+    fn test_configured_port_bindings() {
+        use bollard::models::{HostConfig, PortBinding};
+
+        let binding = |host_ip: Option<&str>, host_port: &str| PortBinding {
+            host_ip: host_ip.map(str::to_string),
+            host_port: Some(host_port.to_string()),
+        };
+        let inspect = ContainerInspectResponse {
+            host_config: Some(HostConfig {
+                port_bindings: Some(HashMap::from([
+                    ("80/tcp".to_string(), Some(vec![binding(Some(""), "8080")])),
+                    ("53/udp".to_string(), Some(vec![binding(Some("127.0.0.1"), "5353")])),
+                    ("443/tcp".to_string(), Some(vec![binding(None, "")])),
+                    ("9000/tcp".to_string(), None),
+                ])),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let ports = configured_port_bindings(&inspect);
+        assert_eq!(ports.len(), 2);
+        assert_eq!(ports[0].host_port, 5353);
+        assert_eq!(ports[0].protocol, Protocol::UDP);
+        assert_eq!(ports[0].host_ip.as_deref(), Some("127.0.0.1"));
+        assert_eq!(ports[1].host_port, 8080);
+        assert_eq!(ports[1].container_port, 80);
+        assert_eq!(ports[1].host_ip, None);
+
+        assert!(configured_port_bindings(&ContainerInspectResponse::default()).is_empty());
+    }
+
+    #[test]
+    fn test_match_container_by_id_or_name() {
+        let containers = vec![
+            fixtures::container("aaaaaaaaaaaa1111", "web"),
+            fixtures::container("bbbbbbbbbbbb2222", "db"),
+            fixtures::container("cccccccccccc3333", "db"),
+        ];
+
+        assert_eq!(
+            match_container(&containers, "web").unwrap().as_deref(),
+            Some("aaaaaaaaaaaa1111")
+        );
+        assert_eq!(
+            match_container(&containers, "bbbbbbbbbbbb2222").unwrap().as_deref(),
+            Some("bbbbbbbbbbbb2222")
+        );
+        assert_eq!(match_container(&containers, "cache").unwrap(), None);
+
+        let err = match_container(&containers, "db").unwrap_err().to_string();
+        assert!(err.contains("bbbbbbbbbbbb"));
+        assert!(err.contains("cccccccccccc"));
+    }
+
+    #[tokio::test]
+    async fn test_connect_socket_rejects_missing_path() {
+        let result = DockerResolver::connect_socket("/nonexistent/docker.sock").await;
+        assert!(result.err().unwrap().to_string().contains("does not exist"));
+    }
+
+    #[test]
+    fn test_expand_home() {
+        assert_eq!(expand_home("/var/run/docker.sock"), PathBuf::from("/var/run/docker.sock"));
+        if let Some(home) = std::env::var_os("HOME") {
+            assert_eq!(
+                expand_home("~/.colima/default/docker.sock"),
+                PathBuf::from(home).join(".colima/default/docker.sock")
+            );
+        }
+    }
+
+    #[test]
+    fn test_cpu_percent() {
+        assert_eq!(cpu_percent(0, 1_000, 4), 0.0);
+        assert_eq!(cpu_percent(100, 0, 4), 0.0);
+        assert!((cpu_percent(250, 1_000, 4) - 100.0).abs() < f64::EPSILON);
+        assert!((cpu_percent(50, 1_000, 2) - 10.0).abs() < f64::EPSILON);
+    }
 }
@@ -0,0 +1,130 @@
+// Fixtures Module - Model values shared by unit tests
+//
+// Each builder returns an unremarkable value; tests override the fields they care
+// about with struct update syntax, e.g. `ProcessNode { cpu_usage: 40.0, ..node }`.
+use crate::discovery::AddressFlags;
+use crate::models::{
+    ContainerInfo, ContainerPort, ContainerRuntime, PortEntry, PortInfo, ProcessInfo,
+    ProcessNode, Protocol, SocketState,
+};
+
+/// A TCP listener on `address:port` held by `pid`
+pub fn listener(address: &str, port: u16, pid: u32) -> PortInfo {
+    let flags = AddressFlags::of(address.parse().unwrap());
+    PortInfo {
+        protocol: Protocol::TCP,
+        local_address: address.to_string(),
+        local_port: port,
+        remote_address: None,
+        remote_port: None,
+        state: SocketState::Listening,
+        pids: vec![pid],
+        is_loopback: flags.is_loopback,
+        is_wildcard: flags.is_wildcard,
+        is_ipv6: flags.is_ipv6,
+        rx_bytes: None,
+        tx_bytes: None,
+        netns: None,
+    }
+}
+
+/// The node-side entry of a TCP listener on `address:port`
+pub fn port_entry(address: &str, port: u16) -> PortEntry {
+    let flags = AddressFlags::of(address.parse().unwrap());
+    PortEntry {
+        protocol: Protocol::TCP,
+        local_address: address.to_string(),
+        local_port: port,
+        remote_address: None,
+        remote_port: None,
+        state: SocketState::Listening,
+        is_loopback: flags.is_loopback,
+        is_wildcard: flags.is_wildcard,
+        is_ipv6: flags.is_ipv6,
+        remote_hostname: None,
+        service_name: None,
+        rx_bytes: None,
+        tx_bytes: None,
+        remote_asn: None,
+        connection_count: 0,
+        shared_port: false,
+        inherited_socket: false,
+        port_key: format!("tcp:{}", port),
+        netns: None,
+    }
+}
+
+/// An idle process called `name`, run by `test` with no parent
+pub fn process_info(pid: u32, name: &str) -> ProcessInfo {
+    ProcessInfo {
+        pid,
+        name: name.to_string(),
+        exe_path: None,
+        command_line: None,
+        args: Vec::new(),
+        user: "test".to_string(),
+        memory_usage: 0,
+        cpu_usage: 0.0,
+        disk_read_bytes: 0,
+        disk_written_bytes: 0,
+        start_time: None,
+        parent_pid: None,
+    }
+}
+
+/// The node of an idle process called `name` that holds no ports
+pub fn process_node(pid: u32, name: &str) -> ProcessNode {
+    ProcessNode {
+        id: format!("{}-0", pid),
+        pid,
+        name: name.to_string(),
+        exe_path: None,
+        command_line: None,
+        args: Vec::new(),
+        user: "test".to_string(),
+        memory_usage: 0,
+        cpu_usage: 0.0,
+        disk_read_bytes: 0,
+        disk_written_bytes: 0,
+        start_time: None,
+        uptime_secs: None,
+        age: None,
+        ports: Vec::new(),
+        is_docker_proxy: false,
+        containerized: false,
+        is_tunnel: false,
+        tunnel_target: None,
+        container: None,
+        is_protected: false,
+        ignored: false,
+        is_orphaned: false,
+        is_suspended: false,
+        note: None,
+    }
+}
+
+/// A running Docker container that publishes no ports
+pub fn container(id: &str, name: &str) -> ContainerInfo {
+    ContainerInfo {
+        id: id.to_string(),
+        name: name.to_string(),
+        image: "nginx".to_string(),
+        status: "Up".to_string(),
+        state: "running".to_string(),
+        runtime: ContainerRuntime::Docker,
+        ports: Vec::new(),
+        compose_project: None,
+        compose_service: None,
+        health: None,
+    }
+}
+
+/// TCP `container_port` published on every host address at `host_port`
+pub fn published_port(host_port: u16, container_port: u16) -> ContainerPort {
+    ContainerPort {
+        host_port,
+        container_port,
+        protocol: Protocol::TCP,
+        host_ip: None,
+    }
+}
@@ -2,9 +2,18 @@
 // Main library entry point
 
 pub mod commands;
+pub mod config;
 pub mod discovery;
 pub mod docker;
+#[cfg(test)]
+mod fixtures;
+pub mod logging;
+pub mod metrics;
 pub mod models;
+pub mod proxy;
+pub mod report;
+pub mod schema;
+pub mod snapshot;
 pub mod surgery;
 
 use commands::*;
@@ -12,35 +21,146 @@ use tauri::Manager;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    // Initialize logger
-    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+    // Initialize logger, keeping recent entries for the debug panel
+    let log_buffer = logging::init(logging::LOG_BUFFER_CAPACITY);
 
     log::info!("Starting Process Surgeon...");
 
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
+        .manage(log_buffer)
         .setup(|app| {
             // Initialize app state asynchronously
             let handle = app.handle().clone();
             
             tauri::async_runtime::spawn(async move {
                 log::info!("Initializing application state...");
-                let state = AppStateManager::new().await;
+                let config_path = match handle.path().app_config_dir() {
+                    Ok(dir) => Some(dir.join(config::CONFIG_FILE_NAME)),
+                    Err(e) => {
+                        log::warn!("No config directory, settings will not persist: {}", e);
+                        None
+                    }
+                };
+                let state = AppStateManager::new(config_path).await;
                 handle.manage(state);
                 log::info!("Application state initialized");
             });
             
             Ok(())
         })
+        .on_window_event(|window, event| {
+            // A closed window never calls stop_monitoring, so drop its subscription here
+            if let tauri::WindowEvent::Destroyed = event {
+                let app = window.app_handle().clone();
+                let label = window.label().to_string();
+                tauri::async_runtime::spawn(async move {
+                    if let Some(state) = app.try_state::<AppStateManager>() {
+                        unsubscribe_monitoring(&state, &label).await;
+                    }
+                });
+            }
+        })
         .invoke_handler(tauri::generate_handler![
             get_processes,
+            get_processes_if_newer,
+            get_ports_only,
+            watch_pids,
+            set_scan_limit,
+            get_processes_grouped,
+            get_dashboard,
+            metrics_prometheus,
+            export_type_schema,
+            get_docker_ports,
+            get_processes_paginated,
+            get_processes_by_name,
+            get_all_processes,
+            get_process_detail,
+            format_process_report,
+            get_root_ancestor,
+            get_process_env,
+            get_systemd_unit,
+            get_windows_service,
+            reveal_process_exe,
+            reveal_process_cwd,
+            add_ignored_process,
+            remove_ignored_process,
+            set_protected_ports,
+            get_protected_ports,
+            set_protected_pid_ranges,
+            set_process_note,
+            start_monitoring,
+            stop_monitoring,
+            watch_port,
+            stop_watch_port,
+            diff_scans,
             find_port,
+            search_ports,
+            get_port_exposure,
+            generate_proxy_config,
+            scan_port_range,
+            is_port_available,
+            test_external_reachability,
+            resolve_listener_interfaces,
+            port_pressure,
+            clear_dns_cache,
+            set_geoip_database,
+            get_connections_to_org,
+            save_snapshot,
+            compare_to_snapshot,
+            list_snapshots,
+            capabilities,
+            set_safe_mode,
             kill_process,
+            kill_processes,
+            kill_by_name,
+            free_port,
+            kill_process_group,
+            kill_tree,
+            kill_process_escalating,
+            kill_and_watch,
+            drain_and_stop,
+            cancel_drain,
+            start_elevated_session,
+            end_elevated_session,
+            close_connection,
+            set_process_priority,
+            set_oom_score_adj,
+            capture_backtrace,
+            get_cpu_affinity,
+            set_cpu_affinity,
+            suspend_process,
+            resume_process,
+            reload_process,
+            simulate_kill,
+            assess_kill_risk,
+            explain_protection,
+            restart_process,
             container_action,
+            compose_action,
+            container_action_batch,
+            recreate_last_removed,
+            stop_container_by_port,
+            container_stats,
+            container_dependents,
+            container_host_port_conflicts,
             get_containers,
+            refresh_docker,
+            get_containers_filtered,
             is_docker_available,
+            docker_diagnostics,
+            list_runtime_conflicts,
+            set_docker_socket,
+            get_recent_logs,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            if let tauri::RunEvent::Exit = event {
+                if let Some(state) = app.try_state::<AppStateManager>() {
+                    state.cpu_sampler.stop();
+                }
+            }
+        });
 }
 
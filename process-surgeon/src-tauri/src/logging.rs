@@ -0,0 +1,159 @@
+// Logging Module - env_logger plus an in-memory ring buffer for the debug panel
+use crate::models::{LogEntry, LogLevel};
+use chrono::Utc;
+use log::{Log, Metadata, Record};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+/// Number of log entries kept for `get_recent_logs`
+pub const LOG_BUFFER_CAPACITY: usize = 500;
+
+/// Bounded, thread-safe buffer of the most recent log entries
+///
+/// Cloning shares the same buffer.
+#[derive(Clone)]
+pub struct LogBuffer {
+    entries: Arc<Mutex<VecDeque<LogEntry>>>,
+    capacity: usize,
+}
+
+impl LogBuffer {
+    /// A buffer keeping the last `capacity` entries; 0 is raised to 1
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            entries: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            capacity,
+        }
+    }
+
+    /// Append an entry, dropping the oldest once the buffer is full
+    pub fn push(&self, entry: LogEntry) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        while entries.len() >= self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+
+    /// Buffered entries at `min_level` or more severe, oldest first
+    pub fn recent(&self, min_level: LogLevel) -> Vec<LogEntry> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries
+            .iter()
+            .filter(|entry| entry.level <= min_level)
+            .cloned()
+            .collect()
+    }
+}
+
+/// Logger that forwards to env_logger and records whatever it lets through
+struct RingLogger {
+    inner: env_logger::Logger,
+    buffer: LogBuffer,
+}
+
+impl Log for RingLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.inner.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.inner.matches(record) {
+            return;
+        }
+
+        self.buffer.push(LogEntry {
+            timestamp: Utc::now(),
+            level: record.level().into(),
+            target: record.target().to_string(),
+            message: record.args().to_string(),
+        });
+        self.inner.log(record);
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+/// Install the global logger, honouring `RUST_LOG` and defaulting to `info`
+///
+/// Returns the buffer that receives a copy of every logged entry.
+pub fn init(capacity: usize) -> LogBuffer {
+    let env = env_logger::Env::default().default_filter_or("info");
+    let inner = env_logger::Builder::from_env(env).build();
+    let buffer = LogBuffer::new(capacity);
+    let max_level = inner.filter();
+
+    let logger = RingLogger {
+        inner,
+        buffer: buffer.clone(),
+    };
+    if log::set_boxed_logger(Box::new(logger)).is_ok() {
+        log::set_max_level(max_level);
+    }
+
+    buffer
+}
+
+impl From<log::Level> for LogLevel {
+    fn from(level: log::Level) -> Self {
+        match level {
+            log::Level::Error => LogLevel::Error,
+            log::Level::Warn => LogLevel::Warn,
+            log::Level::Info => LogLevel::Info,
+            log::Level::Debug => LogLevel::Debug,
+            log::Level::Trace => LogLevel::Trace,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(level: LogLevel, message: &str) -> LogEntry {
+        LogEntry {
+            timestamp: Utc::now(),
+            level,
+            target: "process_surgeon".to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_log_buffer_is_bounded() {
+        let buffer = LogBuffer::new(2);
+        buffer.push(entry(LogLevel::Info, "first"));
+        buffer.push(entry(LogLevel::Info, "second"));
+        buffer.push(entry(LogLevel::Info, "third"));
+
+        let messages: Vec<String> =
+            buffer.recent(LogLevel::Trace).into_iter().map(|e| e.message).collect();
+        assert_eq!(messages, vec!["second", "third"]);
+    }
+
+    #[test]
+    fn test_log_buffer_with_zero_capacity_keeps_the_latest() {
+        let buffer = LogBuffer::new(0);
+        buffer.push(entry(LogLevel::Info, "first"));
+        buffer.push(entry(LogLevel::Info, "second"));
+
+        let messages: Vec<String> =
+            buffer.recent(LogLevel::Trace).into_iter().map(|e| e.message).collect();
+        assert_eq!(messages, vec!["second"]);
+    }
+
+    #[test]
+    fn test_log_buffer_filters_by_level() {
+        let buffer = LogBuffer::new(10);
+        buffer.push(entry(LogLevel::Debug, "scan started"));
+        buffer.push(entry(LogLevel::Warn, "docker unavailable"));
+        buffer.push(entry(LogLevel::Error, "kill failed"));
+
+        assert_eq!(buffer.recent(LogLevel::Warn).len(), 2);
+        assert_eq!(buffer.recent(LogLevel::Error)[0].message, "kill failed");
+        assert_eq!(buffer.recent(LogLevel::Trace).len(), 3);
+    }
+}
@@ -0,0 +1,159 @@
+// Metrics Module - Prometheus text exposition of a scan
+use crate::models::{AppState, ProcessNode};
+use std::cmp::Ordering;
+use std::fmt::Write;
+
+/// Prefix shared by every exported metric
+const METRIC_PREFIX: &str = "process_surgeon";
+
+/// Processes exported per resource gauge when the caller gives no limit
+pub const DEFAULT_TOP_PROCESSES: usize = 10;
+
+/// Render a scan in the Prometheus text exposition format
+///
+/// Per-process CPU and memory gauges only cover the `top_n` heaviest processes for
+/// each resource, keeping label cardinality bounded however many processes exist.
+pub fn render_prometheus(state: &AppState, top_n: usize) -> String {
+    let mut out = String::new();
+
+    write_gauge_header(&mut out, "total_connections", "Sockets seen in the last scan");
+    write_sample(&mut out, "total_connections", &[], state.total_connections as f64);
+
+    write_gauge_header(&mut out, "listening_ports", "Processes with at least one listener");
+    write_sample(&mut out, "listening_ports", &[], state.listening_ports as f64);
+
+    write_gauge_header(&mut out, "sockets", "Sockets by protocol");
+    let counts = &state.port_counts;
+    write_sample(&mut out, "sockets", &[("protocol", "tcp")], counts.tcp_count as f64);
+    write_sample(&mut out, "sockets", &[("protocol", "udp")], counts.udp_count as f64);
+
+    write_gauge_header(&mut out, "established_connections", "Established sockets");
+    write_sample(&mut out, "established_connections", &[], counts.established_count as f64);
+
+    write_process_gauge(
+        &mut out,
+        "process_cpu_percent",
+        "CPU usage of the busiest processes, in percent of one core",
+        top_processes(&state.processes, top_n, |a, b| b.cpu_usage.total_cmp(&a.cpu_usage)),
+        |p| p.cpu_usage as f64,
+    );
+    write_process_gauge(
+        &mut out,
+        "process_memory_bytes",
+        "Resident memory of the largest processes",
+        top_processes(&state.processes, top_n, |a, b| b.memory_usage.cmp(&a.memory_usage)),
+        |p| p.memory_usage as f64,
+    );
+
+    out
+}
+
+/// The first `n` processes in `order`, ties broken by PID
+fn top_processes(
+    processes: &[ProcessNode],
+    n: usize,
+    order: impl Fn(&ProcessNode, &ProcessNode) -> Ordering,
+) -> Vec<&ProcessNode> {
+    let mut sorted: Vec<&ProcessNode> = processes.iter().collect();
+    sorted.sort_by(|a, b| order(a, b).then(a.pid.cmp(&b.pid)));
+    sorted.truncate(n);
+    sorted
+}
+
+fn write_process_gauge(
+    out: &mut String,
+    name: &str,
+    help: &str,
+    processes: Vec<&ProcessNode>,
+    value: impl Fn(&ProcessNode) -> f64,
+) {
+    write_gauge_header(out, name, help);
+    for process in processes {
+        let pid = process.pid.to_string();
+        write_sample(out, name, &[("pid", &pid), ("name", &process.name)], value(process));
+    }
+}
+
+fn write_gauge_header(out: &mut String, name: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {}_{} {}", METRIC_PREFIX, name, help);
+    let _ = writeln!(out, "# TYPE {}_{} gauge", METRIC_PREFIX, name);
+}
+
+fn write_sample(out: &mut String, name: &str, labels: &[(&str, &str)], value: f64) {
+    let _ = write!(out, "{}_{}", METRIC_PREFIX, name);
+    if !labels.is_empty() {
+        let labels: Vec<String> = labels
+            .iter()
+            .map(|(key, value)| format!("{}=\"{}\"", key, escape_label_value(value)))
+            .collect();
+        let _ = write!(out, "{{{}}}", labels.join(","));
+    }
+    let _ = writeln!(out, " {}", value);
+}
+
+/// Escape a label value as the text format requires: backslash, quote and newline
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::process_node;
+    use crate::models::PortCounts;
+    use chrono::Utc;
+    use std::collections::HashMap;
+
+    fn process(pid: u32, name: &str, cpu_usage: f32, memory_usage: u64) -> ProcessNode {
+        ProcessNode {
+            cpu_usage,
+            memory_usage,
+            ..process_node(pid, name)
+        }
+    }
+
+    #[test]
+    fn test_render_prometheus() {
+        let state = AppState {
+            processes: vec![
+                process(10, "postgres", 1.5, 4096),
+                process(20, "node", 40.0, 1024),
+                process(30, "my \"app\"", 0.0, 8192),
+            ],
+            total_connections: 12,
+            listening_ports: 3,
+            port_counts: PortCounts {
+                tcp_count: 9,
+                udp_count: 3,
+                established_count: 6,
+                ipv6_count: 2,
+            },
+            docker_available: false,
+            last_updated: Utc::now(),
+            warnings: Vec::new(),
+            unix_sockets: Vec::new(),
+            limited_privileges: false,
+            truncated: false,
+            total_processes: 3,
+            state_histogram: HashMap::new(),
+        };
+
+        let text = render_prometheus(&state, 2);
+
+        assert!(text.contains("# TYPE process_surgeon_total_connections gauge\n"));
+        assert!(text.contains("process_surgeon_total_connections 12\n"));
+        assert!(text.contains("process_surgeon_sockets{protocol=\"udp\"} 3\n"));
+        assert!(
+            text.contains("process_surgeon_process_cpu_percent{pid=\"20\",name=\"node\"} 40\n")
+        );
+        assert!(text.contains(
+            "process_surgeon_process_memory_bytes{pid=\"30\",name=\"my \\\"app\\\"\"} 8192\n"
+        ));
+        // Only the top two per gauge are exported
+        assert_eq!(text.matches("process_surgeon_process_cpu_percent{").count(), 2);
+        assert!(!text.contains("process_memory_bytes{pid=\"20\""));
+    }
+}
@@ -1,9 +1,11 @@
 // Models module - Core data structures
 use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Network protocol type
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum Protocol {
     TCP,
@@ -11,7 +13,7 @@ pub enum Protocol {
 }
 
 /// Socket connection state
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum SocketState {
     Listening,
@@ -39,6 +41,101 @@ pub struct PortInfo {
     pub remote_port: Option<u16>,
     pub state: SocketState,
     pub pids: Vec<u32>,
+    /// Local address is loopback, including IPv4-mapped forms such as `::ffff:127.0.0.1`
+    pub is_loopback: bool,
+    /// Local address is `0.0.0.0`, `::` or an IPv4-mapped equivalent
+    pub is_wildcard: bool,
+    /// Socket belongs to the IPv6 address family
+    pub is_ipv6: bool,
+    /// Cumulative bytes received, where the OS exposes per-socket counters
+    pub rx_bytes: Option<u64>,
+    /// Cumulative bytes sent, where the OS exposes per-socket counters
+    pub tx_bytes: Option<u64>,
+    /// Network namespace (e.g. `net:[4026532281]`) for sockets outside the host's (Linux)
+    pub netns: Option<String>,
+}
+
+/// Whether a port can currently be bound
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PortStatus {
+    Free,
+    InUse,
+    /// Binding was refused, typically a privileged port (< 1024) without elevation
+    PermissionDenied,
+}
+
+/// Result of a port availability check
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortAvailability {
+    pub port: u16,
+    pub protocol: Protocol,
+    pub status: PortStatus,
+}
+
+/// How much of the ephemeral port range TCP sockets are holding
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortPressure {
+    /// First port of the OS's ephemeral range
+    pub range_start: u16,
+    /// Last port of the ephemeral range, inclusive
+    pub range_end: u16,
+    /// Ephemeral ports with an established connection
+    pub established: usize,
+    /// Ephemeral ports held by a connection in TIME_WAIT
+    pub time_wait: usize,
+    /// Ephemeral ports held in any non-listening state
+    pub in_use: usize,
+    pub utilization_percent: f64,
+    /// Utilization reached the warning threshold
+    pub warning: bool,
+}
+
+/// Outcome of a TCP connect attempt
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Reachability {
+    /// Something accepted the connection
+    Connected,
+    /// The host answered but nothing accepts connections on the port
+    Refused,
+    /// No answer before the time limit, typically a firewall dropping the packets
+    Timeout,
+}
+
+/// Result of connecting to a local port through the primary network interface
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReachabilityCheck {
+    pub port: u16,
+    /// Interface address the connection was made to
+    pub address: String,
+    pub status: Reachability,
+}
+
+/// A concrete address a listener serves, with wildcard binds expanded per interface
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListenerInterface {
+    pub protocol: Protocol,
+    /// Interface the address is assigned to, when known
+    pub interface: Option<String>,
+    pub address: String,
+    pub port: u16,
+}
+
+/// Where a port is reachable from, based on the addresses its listeners bind
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "addresses", rename_all = "camelCase")]
+pub enum Exposure {
+    /// Bound on a wildcard address (`0.0.0.0` or `::`), so every interface accepts it
+    External,
+    /// Only bound on loopback addresses
+    LoopbackOnly,
+    /// Bound on specific non-loopback addresses
+    SpecificInterfaces(Vec<String>),
 }
 
 /// Process information
@@ -49,15 +146,33 @@ pub struct ProcessInfo {
     pub name: String,
     pub exe_path: Option<String>,
     pub command_line: Option<String>,
+    /// Arguments as the OS reports them, including the program; empty when unreadable
+    pub args: Vec<String>,
     pub user: String,
     pub memory_usage: u64,
     pub cpu_usage: f32,
+    /// Total bytes read from disk since the process started
+    pub disk_read_bytes: u64,
+    /// Total bytes written to disk since the process started
+    pub disk_written_bytes: u64,
     pub start_time: Option<DateTime<Utc>>,
     pub parent_pid: Option<u32>,
 }
 
+/// Full details for a single process, whether or not it owns any sockets
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessDetail {
+    #[serde(flatten)]
+    pub info: ProcessInfo,
+    pub is_protected: bool,
+    /// Why the process is protected, when it is
+    pub protection_reason: Option<String>,
+    pub is_docker_proxy: bool,
+}
+
 /// Container type enum
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum ContainerRuntime {
     Docker,
@@ -66,9 +181,36 @@ pub enum ContainerRuntime {
     Unknown,
 }
 
-/// Docker container information
+/// One endpoint tried while diagnosing the Docker connection
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+pub struct DockerEndpointAttempt {
+    pub endpoint: String,
+    /// Why connecting or pinging failed; None if the engine responded
+    pub error: Option<String>,
+}
+
+/// Why Docker is or isn't available, for turning "unavailable" into something actionable
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DockerDiagnostics {
+    /// Whether the app currently has a working connection
+    pub available: bool,
+    /// Endpoint of the current connection
+    pub endpoint: Option<String>,
+    pub remote: bool,
+    /// The `DOCKER_HOST` endpoint followed by each well-known socket found on disk
+    pub attempts: Vec<DockerEndpointAttempt>,
+    pub runtime: Option<ContainerRuntime>,
+    pub server_version: Option<String>,
+    pub api_version: Option<String>,
+    /// Error from the version query when an engine responded to ping
+    pub version_error: Option<String>,
+}
+
+/// Docker container information
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct ContainerInfo {
     pub id: String,
     pub name: String,
@@ -77,11 +219,83 @@ pub struct ContainerInfo {
     pub state: String,
     pub runtime: ContainerRuntime,
     pub ports: Vec<ContainerPort>,
+    /// Compose project from the `com.docker.compose.project` label
+    pub compose_project: Option<String>,
+    /// Compose service from the `com.docker.compose.service` label
+    pub compose_service: Option<String>,
+    /// HEALTHCHECK result; None when the container defines no healthcheck
+    pub health: Option<ContainerHealth>,
 }
 
-/// Container port mapping
+/// What two engines both claim in a `RuntimeConflict`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictKind {
+    /// The same container name, so name-based actions are ambiguous
+    Name,
+    /// The same published host port
+    Port,
+}
+
+/// A container on one side of a `RuntimeConflict`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConflictingContainer {
+    pub runtime: ContainerRuntime,
+    /// Engine socket the container was listed from
+    pub endpoint: String,
+    pub id: String,
+    pub name: String,
+}
+
+/// A container name or host port claimed on more than one local engine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeConflict {
+    pub kind: ConflictKind,
+    /// The shared name, or the host port as a string
+    pub value: String,
+    pub containers: Vec<ConflictingContainer>,
+}
+
+/// A published container port that a host process also listens on
+///
+/// Usually behind "port is already allocated" when the container (re)starts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HostPortConflict {
+    pub port: u16,
+    pub protocol: Protocol,
+    pub containers: Vec<ContainerInfo>,
+    /// Host processes, each with only the listeners that collide
+    pub processes: Vec<ProcessNode>,
+}
+
+/// Health reported by a container's HEALTHCHECK
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ContainerHealth {
+    /// Still within the start period, no verdict yet
+    Starting,
+    Healthy,
+    Unhealthy,
+}
+
+/// One-shot resource usage sample for a container
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+pub struct ContainerStats {
+    pub container_id: String,
+    /// CPU usage in percent of a single core, like `docker stats`
+    pub cpu_percent: f64,
+    /// Memory in use, excluding page cache
+    pub memory_usage: u64,
+    pub memory_limit: u64,
+}
+
+/// Container port mapping
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct ContainerPort {
     pub host_port: u16,
     pub container_port: u16,
@@ -90,7 +304,7 @@ pub struct ContainerPort {
 }
 
 /// Unified process node combining port, process, and container info
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ProcessNode {
     pub id: String, // Unique identifier (PID-Port combination)
@@ -98,19 +312,53 @@ pub struct ProcessNode {
     pub name: String,
     pub exe_path: Option<String>,
     pub command_line: Option<String>,
+    /// Arguments as the OS reports them, unlike `command_line` never re-split on spaces
+    pub args: Vec<String>,
     pub user: String,
     pub memory_usage: u64,
     pub cpu_usage: f32,
+    /// Total bytes read from disk since the process started, not the per-refresh delta
+    pub disk_read_bytes: u64,
+    /// Total bytes written to disk since the process started, not the per-refresh delta
+    pub disk_written_bytes: u64,
     pub start_time: Option<DateTime<Utc>>,
+    /// Seconds since the process started, computed at scan time
+    pub uptime_secs: Option<u64>,
+    /// Human-readable form of `uptime_secs`, e.g. "3h 5m"
+    pub age: Option<String>,
     pub ports: Vec<PortEntry>,
     pub is_docker_proxy: bool,
+    /// Runs inside a container, per its cgroup (Linux only); `container` is filled in
+    /// when the local engine knows it
+    pub containerized: bool,
+    /// ssh or sshd holding listening ports, most likely for a port forward
+    pub is_tunnel: bool,
+    /// Where the forward relays to, e.g. "→ remote:3306", when the command line tells
+    pub tunnel_target: Option<String>,
     pub container: Option<ContainerInfo>,
     pub is_protected: bool,
+    /// Name is on the user's ignore list; the UI collapses these rather than hiding them
+    pub ignored: bool,
+    /// Parent exited and the process was not adopted by init, e.g. a leaked dev server
+    pub is_orphaned: bool,
+    /// Paused with `suspend_process` and not yet resumed
+    pub is_suspended: bool,
+    /// User note attached to the executable path with `set_process_note`
+    pub note: Option<String>,
 }
 
-/// Port entry within a process node
+/// Process node merged from every process sharing the same executable path
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+pub struct ProcessGroupNode {
+    #[serde(flatten)]
+    pub node: ProcessNode,
+    pub worker_pids: Vec<u32>,
+}
+
+/// Port entry within a process node
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct PortEntry {
     pub protocol: Protocol,
     pub local_address: String,
@@ -118,6 +366,37 @@ pub struct PortEntry {
     pub remote_address: Option<String>,
     pub remote_port: Option<u16>,
     pub state: SocketState,
+    pub is_loopback: bool,
+    pub is_wildcard: bool,
+    pub is_ipv6: bool,
+    /// Reverse DNS name of the remote address (only when resolution is requested)
+    pub remote_hostname: Option<String>,
+    /// Well-known service name for the remote port, or the local port for listeners
+    pub service_name: Option<String>,
+    pub rx_bytes: Option<u64>,
+    pub tx_bytes: Option<u64>,
+    /// Autonomous system of a public remote address (only when a GeoIP database is configured)
+    pub remote_asn: Option<AsnInfo>,
+    /// For listeners: established connections on the same PID and local port
+    pub connection_count: usize,
+    /// For listeners: other PIDs also listen on this protocol and port (e.g. SO_REUSEPORT)
+    pub shared_port: bool,
+    /// Socket came with a fork from an ancestor that still holds it; terminating this
+    /// process alone will not free the port
+    pub inherited_socket: bool,
+    /// Key unique within the owning node for frontend keying, e.g. `tcp:53`
+    pub port_key: String,
+    /// Network namespace for sockets outside the host's, e.g. inside a container (Linux)
+    pub netns: Option<String>,
+}
+
+/// Autonomous system details for a remote address
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AsnInfo {
+    pub asn: u32,
+    pub org: String,
+    pub country: Option<String>,
 }
 
 /// Kill request from frontend
@@ -129,7 +408,7 @@ pub struct KillRequest {
 }
 
 /// Kill result response
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct KillResult {
     pub success: bool,
@@ -137,16 +416,283 @@ pub struct KillResult {
     pub required_elevation: bool,
 }
 
+/// Outcome of `drain_and_stop`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DrainResult {
+    #[serde(flatten)]
+    pub result: KillResult,
+    /// Ports the process was listening on when the drain started
+    pub listening_ports: Vec<u16>,
+    /// Established connections on those ports when the process was terminated
+    pub open_connections: usize,
+    /// Milliseconds spent waiting for connections to close
+    pub waited_ms: u64,
+    /// False where connections can't be attributed to processes, so nothing was awaited
+    pub drain_supported: bool,
+    /// Ended early by `cancel_drain`; the process was not stopped
+    pub cancelled: bool,
+}
+
+/// How `free_port` went about releasing a port
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FreePortMethod {
+    /// Nothing held the port, so nothing was done
+    AlreadyFree,
+    /// The container(s) publishing the port were stopped
+    Container,
+    /// The processes owning the port were terminated
+    Process,
+}
+
+/// Outcome of `free_port`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FreePortResult {
+    pub port: u16,
+    pub method: FreePortMethod,
+    /// Every action succeeded (also true when the port was already free)
+    pub success: bool,
+    /// Containers acted on, for `Container`
+    pub containers: Vec<ContainerInfo>,
+    /// Processes signalled, for `Process`
+    pub pids: Vec<u32>,
+    /// One result per container or PID, in the same order
+    pub results: Vec<KillResult>,
+}
+
+/// One kill performed by `kill_and_watch`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchAction {
+    pub pid: u32,
+    pub name: String,
+    /// Why the process was targeted, e.g. it took over a watched port
+    pub reason: String,
+    pub result: KillResult,
+    pub at: DateTime<Utc>,
+}
+
+/// Result of signalling a whole process group
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupKillResult {
+    #[serde(flatten)]
+    pub result: KillResult,
+    pub signaled_count: usize,
+}
+
+/// Result of asking a process to reload its configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReloadResult {
+    #[serde(flatten)]
+    pub result: KillResult,
+    /// The process was still alive shortly after SIGHUP
+    pub still_running: bool,
+}
+
+/// Debugger used to capture a backtrace
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BacktraceTool {
+    Gdb,
+    Lldb,
+    /// Python stacks rather than the interpreter's native ones
+    #[serde(rename = "py-spy")]
+    PySpy,
+}
+
+/// Every thread's stack of a process, as printed by a debugger
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessBacktrace {
+    pub pid: u32,
+    pub tool: BacktraceTool,
+    pub output: String,
+}
+
+/// Result of changing a process's OOM score adjustment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OomScoreChange {
+    pub pid: u32,
+    /// `oom_score_adj` before the change
+    pub previous: i32,
+    /// `oom_score_adj` as read back afterwards
+    pub current: i32,
+}
+
+/// Signals usable in a termination escalation ladder
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TerminationSignal {
+    /// SIGINT, as sent by Ctrl+C
+    Int,
+    /// SIGTERM
+    Term,
+    /// SIGQUIT
+    Quit,
+    /// SIGKILL, which cannot be caught
+    Kill,
+}
+
+/// Result of an escalating termination
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EscalationResult {
+    #[serde(flatten)]
+    pub result: KillResult,
+    /// Zero-based index of the ladder step after which the process exited
+    pub step: Option<usize>,
+    /// Signal of that step
+    pub signal: Option<TerminationSignal>,
+}
+
+/// Advisory risk level for terminating a process
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RiskLevel {
+    Low,
+    Medium,
+    High,
+}
+
+/// Termination features available on this platform, so the UI only offers working actions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Capabilities {
+    /// Signals usable in `kill_process_escalating` ladders
+    pub supports_signals: Vec<TerminationSignal>,
+    pub supports_suspend: bool,
+    pub supports_renice: bool,
+    /// `kill_process_group` kills the process group (Unix) or process tree (Windows)
+    pub supports_killpg: bool,
+    /// How elevated kills obtain privileges, e.g. "pkexec"; None when they can't
+    pub elevation_method: Option<String>,
+    pub docker_available: bool,
+}
+
+/// Kill risk assessment with human-readable reasons
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KillRisk {
+    pub pid: u32,
+    pub level: RiskLevel,
+    pub reasons: Vec<String>,
+}
+
+/// Which kind of safety rule protects a process
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ProtectionKind {
+    /// Process name is on the protected list
+    Name,
+    /// PID is fixed as protected or in a protected range
+    Pid,
+    /// Holds a protected port
+    Port,
+    /// Process Surgeon itself or its elevated helper
+    SelfProcess,
+}
+
+/// Why a process is or is not protected from termination
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProtectionExplanation {
+    pub pid: u32,
+    pub protected: bool,
+    pub kind: Option<ProtectionKind>,
+    /// The matched rule, e.g. a name pattern like `systemd-*`, a PID range like `0-2`,
+    /// or a port
+    pub rule: Option<String>,
+    pub reason: Option<String>,
+}
+
+/// Resource thresholds checked on every monitoring sample
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceThresholds {
+    /// CPU usage in percent
+    pub cpu_percent: Option<f32>,
+    /// Resident memory in bytes
+    pub memory_bytes: Option<u64>,
+    /// Consecutive samples over a threshold before alerting (defaults to 3)
+    pub consecutive_samples: Option<u32>,
+}
+
+/// Which resource threshold was crossed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThresholdKind {
+    Cpu,
+    Memory,
+}
+
+/// Payload of the `resource-alert` event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceAlert {
+    pub process: ProcessNode,
+    pub threshold: ThresholdKind,
+    /// Number of consecutive samples over the threshold
+    pub samples: u32,
+}
+
+/// Payload of the `port-state-changed` event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortStateChange {
+    pub port: u16,
+    /// Owning PIDs before the change, sorted; empty when the port was free
+    pub previous_pids: Vec<u32>,
+    /// Owning PIDs now, sorted; empty when the port was released
+    pub pids: Vec<u32>,
+    pub at: DateTime<Utc>,
+}
+
+/// Payload of the `container-event` event, from the engine's event stream
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerEvent {
+    pub id: String,
+    /// Container name, when the engine includes it
+    pub name: Option<String>,
+    /// Engine action: start, stop, die or destroy
+    pub action: String,
+    /// State after the action: running, exited or removed
+    pub state: String,
+    pub at: DateTime<Utc>,
+}
+
 /// Container action request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ContainerActionRequest {
     pub container_id: String,
     pub action: ContainerAction,
+    /// For `Stop`: seconds Docker waits after SIGTERM before sending SIGKILL
+    /// (default 10, 0 to kill immediately)
+    #[serde(default)]
+    pub timeout_secs: Option<u32>,
 }
 
-/// Available container actions
+/// Result of recreating a removed container
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecreateResult {
+    /// Id of the new container
+    pub id: String,
+    pub name: String,
+    /// Started again because the original was running when removed
+    pub started: bool,
+    /// What could not be restored, e.g. the contents of anonymous volumes
+    pub warnings: Vec<String>,
+}
+
+/// Available container actions
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ContainerAction {
     Stop,
@@ -155,38 +701,284 @@ pub enum ContainerAction {
     Restart,
 }
 
-/// Application state for the frontend
+/// A PID followed with `watch_pids`
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+pub struct WatchedProcess {
+    pub pid: u32,
+    /// False once the process has exited, in which case `process` is `None`
+    pub alive: bool,
+    pub process: Option<ProcessNode>,
+}
+
+/// Application state for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct AppState {
     pub processes: Vec<ProcessNode>,
     pub total_connections: usize,
     pub listening_ports: usize,
+    #[serde(flatten)]
+    pub port_counts: PortCounts,
     pub docker_available: bool,
     pub last_updated: DateTime<Utc>,
+    /// Non-fatal scan problems, e.g. one address family failing to enumerate
+    pub warnings: Vec<String>,
+    /// Named Unix domain sockets; empty unless requested with `include_unix_sockets`
+    pub unix_sockets: Vec<UnixSocket>,
+    /// Many sockets had no visible owner, so relaunching elevated would show more (Windows)
+    pub limited_privileges: bool,
+    /// `processes` was cut to the configured result cap; `total_processes` has the full count
+    pub truncated: bool,
+    pub total_processes: usize,
+    /// Sockets per state across the whole scan, including ones with no visible owner
+    /// (such as TIME_WAIT) and ones hidden by filters
+    pub state_histogram: HashMap<SocketState, usize>,
 }
 
-/// Error types for IPC communication
+/// Reply to `get_processes_if_newer`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum ScanUpdate {
+    /// Nothing newer than the client's generation has been scanned
+    NotModified { generation: u64 },
+    Updated { generation: u64, state: AppState },
+}
+
+/// State of a Unix domain socket
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum UnixSocketState {
+    Listening,
+    Connected,
+    Unconnected,
+    /// The platform does not report socket state (macOS)
+    Unknown,
+}
+
+/// A Unix domain socket bound to a filesystem path (or an abstract name, shown with `@`)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UnixSocket {
+    pub path: String,
+    pub pids: Vec<u32>,
+    pub state: UnixSocketState,
+}
+
+/// Everything the frontend needs at startup, fetched in one call
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Dashboard {
+    pub app_state: AppState,
+    pub containers: Vec<ContainerInfo>,
+    pub docker_available: bool,
+}
+
+/// Ports a surviving process opened or closed between two scans
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+pub struct PortChange {
+    pub pid: u32,
+    pub name: String,
+    pub added: Vec<PortEntry>,
+    pub removed: Vec<PortEntry>,
+}
+
+/// What changed between two scans, matching processes by PID
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanDiff {
+    pub added: Vec<ProcessNode>,
+    pub removed: Vec<ProcessNode>,
+    pub port_changes: Vec<PortChange>,
+}
+
+impl ScanDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.port_changes.is_empty()
+    }
+}
+
+/// A listener recorded in a named snapshot
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotListener {
+    pub pid: u32,
+    pub process_name: String,
+    pub protocol: Protocol,
+    pub local_address: String,
+    pub local_port: u16,
+}
+
+/// Listeners that appeared or disappeared since a saved snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotComparison {
+    pub snapshot: String,
+    pub taken_at: DateTime<Utc>,
+    pub added: Vec<SnapshotListener>,
+    pub removed: Vec<SnapshotListener>,
+}
+
+/// Socket breakdown for the summary bar
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PortCounts {
+    pub tcp_count: usize,
+    pub udp_count: usize,
+    pub established_count: usize,
+    pub ipv6_count: usize,
+}
+
+/// Application state with processes grouped by executable path
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupedAppState {
+    pub groups: Vec<ProcessGroupNode>,
+    pub total_connections: usize,
+    pub listening_ports: usize,
+    pub docker_available: bool,
+    pub last_updated: DateTime<Utc>,
+    pub warnings: Vec<String>,
+}
+
+/// Severity of a log entry, most severe first so `<=` means "at least as severe"
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+/// A backend log record kept for the debug panel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogEntry {
+    pub timestamp: DateTime<Utc>,
+    pub level: LogLevel,
+    /// Module that logged the entry
+    pub target: String,
+    pub message: String,
+}
+
+/// Stable error codes reported to the frontend
+///
+/// Serialized as the SCREAMING_SNAKE_CASE strings used before this enum existed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    ScanError,
+    ScanCancelled,
+    KillError,
+    DockerUnavailable,
+    DockerError,
+    AccessDenied,
+    NotFound,
+    SafetyViolation,
+    InvalidRange,
+    RestartUnavailable,
+    RestartTimeout,
+    SpawnError,
+    GeoipError,
+    ConfigError,
+    NotSupported,
+    OpenError,
+    InvalidInput,
+    PriorityError,
+    SafeModeBlocked,
+    AffinityError,
+    AmbiguousTarget,
+    /// The Docker engine rejected the API version the client speaks
+    DockerVersionMismatch,
+    /// A PID to kill now belongs to a process with a different name
+    PidReused,
+    /// Reading or writing `/proc/<pid>/oom_score_adj` failed
+    OomScoreError,
+    /// None of the supported debuggers is installed
+    DebuggerNotFound,
+    /// The debugger failed without printing a backtrace, or did not finish in time
+    BacktraceError,
+}
+
+/// Server-side sort order for paginated process listings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortKey {
+    /// Ascending PID
+    Pid,
+    /// Case-insensitive process name
+    Name,
+    /// Highest memory usage first
+    Memory,
+    /// Highest CPU usage first
+    Cpu,
+    /// Lowest bound port first
+    Port,
+}
+
+/// Sort order for `get_containers_filtered`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ContainerSortKey {
+    /// Case-insensitive container name
+    Name,
+    /// Engine state (running, exited, ...), then name
+    State,
+    /// Most published host ports first
+    PortCount,
+}
+
+/// Reverse-proxy flavour emitted by `generate_proxy_config`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProxyFormat {
+    /// An nginx `upstream` block plus a `location` that proxies to it
+    Nginx,
+    /// A Caddy `reverse_proxy` directive
+    Caddy,
+}
+
+/// One page of process nodes plus totals for the full result set
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessPage {
+    pub processes: Vec<ProcessNode>,
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+    pub total_connections: usize,
+    pub listening_ports: usize,
+    pub docker_available: bool,
+    pub last_updated: DateTime<Utc>,
+    pub warnings: Vec<String>,
+}
+
+/// Error types for IPC communication
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct AppError {
-    pub code: String,
+    pub code: ErrorCode,
     pub message: String,
     pub details: Option<String>,
 }
 
 impl AppError {
-    pub fn new(code: &str, message: &str) -> Self {
+    pub fn new(code: ErrorCode, message: &str) -> Self {
         Self {
-            code: code.to_string(),
+            code,
             message: message.to_string(),
             details: None,
         }
     }
 
-    pub fn with_details(code: &str, message: &str, details: &str) -> Self {
+    pub fn with_details(code: ErrorCode, message: &str, details: &str) -> Self {
         Self {
-            code: code.to_string(),
+            code,
             message: message.to_string(),
             details: Some(details.to_string()),
         }
@@ -194,7 +986,7 @@ impl AppError {
 
     pub fn safety_violation(process_name: &str) -> Self {
         Self::with_details(
-            "SAFETY_VIOLATION",
+            ErrorCode::SafetyViolation,
             "Operation Forbidden: Critical System Process",
             &format!("Cannot terminate protected process: {}", process_name),
         )
@@ -202,13 +994,48 @@ impl AppError {
 
     pub fn access_denied(pid: u32) -> Self {
         Self::with_details(
-            "ACCESS_DENIED",
+            ErrorCode::AccessDenied,
             "Insufficient privileges",
             &format!("Elevated privileges required to terminate PID {}", pid),
         )
     }
 
     pub fn process_not_found(pid: u32) -> Self {
-        Self::new("NOT_FOUND", &format!("Process {} not found", pid))
+        Self::new(ErrorCode::NotFound, &format!("Process {} not found", pid))
+    }
+
+    pub fn safe_mode_blocked(operation: &str) -> Self {
+        Self::with_details(
+            ErrorCode::SafeModeBlocked,
+            "Blocked by safe mode",
+            &format!("{} is not allowed while safe mode is on", operation),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_code_serializes_to_legacy_strings() {
+        let codes = [
+            (ErrorCode::ScanError, "\"SCAN_ERROR\""),
+            (ErrorCode::DockerUnavailable, "\"DOCKER_UNAVAILABLE\""),
+            (ErrorCode::NotFound, "\"NOT_FOUND\""),
+            (ErrorCode::SafetyViolation, "\"SAFETY_VIOLATION\""),
+            (ErrorCode::SafeModeBlocked, "\"SAFE_MODE_BLOCKED\""),
+        ];
+
+        for (code, expected) in codes {
+            assert_eq!(serde_json::to_string(&code).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_app_error_shape() {
+        let json = serde_json::to_value(AppError::process_not_found(42)).unwrap();
+        assert_eq!(json["code"], "NOT_FOUND");
+        assert_eq!(json["message"], "Process 42 not found");
     }
 }
@@ -0,0 +1,109 @@
+// Proxy Module - Reverse-proxy config snippets for a process's listeners
+use crate::models::{PortInfo, Protocol, ProxyFormat, SocketState};
+use std::collections::BTreeMap;
+use std::net::{IpAddr, Ipv6Addr};
+
+/// Local endpoints a reverse proxy on this host can reach `pid` on, sorted by port
+///
+/// Only listening TCP sockets bound to loopback or a wildcard address are used. Each
+/// port appears once, as `127.0.0.1:port` unless it is only bound on `::1`.
+pub fn proxy_endpoints(ports: &[PortInfo], pid: u32) -> Vec<String> {
+    let mut hosts: BTreeMap<u16, &str> = BTreeMap::new();
+
+    for port in ports {
+        if port.protocol != Protocol::TCP
+            || port.state != SocketState::Listening
+            || !(port.is_loopback || port.is_wildcard)
+            || !port.pids.contains(&pid)
+        {
+            continue;
+        }
+
+        let ipv6_only = port.local_address.parse::<IpAddr>().ok()
+            == Some(IpAddr::V6(Ipv6Addr::LOCALHOST));
+        let host = if ipv6_only { "[::1]" } else { "127.0.0.1" };
+        hosts
+            .entry(port.local_port)
+            .and_modify(|existing| {
+                if !ipv6_only {
+                    *existing = host;
+                }
+            })
+            .or_insert(host);
+    }
+
+    hosts
+        .into_iter()
+        .map(|(port, host)| format!("{}:{}", host, port))
+        .collect()
+}
+
+/// Render `endpoints` as a config snippet, naming the nginx upstream after `name`
+pub fn render_proxy_config(name: &str, endpoints: &[String], format: ProxyFormat) -> String {
+    match format {
+        ProxyFormat::Nginx => {
+            let upstream = upstream_name(name);
+            let mut out = format!("upstream {} {{\n", upstream);
+            for endpoint in endpoints {
+                out.push_str(&format!("    server {};\n", endpoint));
+            }
+            out.push_str("}\n\n");
+            out.push_str("location / {\n");
+            out.push_str(&format!("    proxy_pass http://{};\n", upstream));
+            out.push_str("    proxy_set_header Host $host;\n");
+            out.push_str("    proxy_set_header X-Real-IP $remote_addr;\n");
+            out.push_str("    proxy_set_header X-Forwarded-For $proxy_add_x_forwarded_for;\n");
+            out.push_str("    proxy_set_header X-Forwarded-Proto $scheme;\n");
+            out.push_str("}\n");
+            out
+        }
+        ProxyFormat::Caddy => format!("reverse_proxy {}\n", endpoints.join(" ")),
+    }
+}
+
+/// Reduce a process name to characters nginx accepts in an upstream name
+fn upstream_name(name: &str) -> String {
+    let sanitized: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' })
+        .collect();
+    if sanitized.is_empty() {
+        "backend".to_string()
+    } else {
+        sanitized
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::listener;
+
+    #[test]
+    fn test_proxy_endpoints() {
+        let mut udp = listener("0.0.0.0", 5353, 10);
+        udp.protocol = Protocol::UDP;
+        let ports = vec![
+            listener("0.0.0.0", 8080, 10),
+            listener("::", 8080, 10),
+            listener("::1", 3000, 10),
+            listener("192.168.1.5", 9000, 10),
+            listener("127.0.0.1", 4000, 20),
+            udp,
+        ];
+
+        assert_eq!(proxy_endpoints(&ports, 10), vec!["[::1]:3000", "127.0.0.1:8080"]);
+    }
+
+    #[test]
+    fn test_render_proxy_config() {
+        let endpoints = vec!["127.0.0.1:3000".to_string(), "127.0.0.1:3001".to_string()];
+
+        let nginx = render_proxy_config("my app.js", &endpoints, ProxyFormat::Nginx);
+        assert!(nginx.starts_with("upstream my_app_js {\n    server 127.0.0.1:3000;\n"));
+        assert!(nginx.contains("    proxy_pass http://my_app_js;\n"));
+
+        let caddy = render_proxy_config("my app.js", &endpoints, ProxyFormat::Caddy);
+        assert_eq!(caddy, "reverse_proxy 127.0.0.1:3000 127.0.0.1:3001\n");
+    }
+}
@@ -0,0 +1,208 @@
+// Report Module - Plain-text process summaries for pasting into chats and tickets
+use crate::discovery::{format_age, service_name, uptime_secs};
+use crate::models::{ContainerInfo, PortInfo, ProcessInfo, Protocol, SocketState};
+use chrono::{DateTime, Utc};
+use std::fmt::Write;
+
+/// Width labels are padded to so values line up
+const LABEL_WIDTH: usize = 11;
+
+/// Placeholder for fields the OS did not expose
+const UNKNOWN: &str = "unknown";
+
+/// Render a multi-line, human-readable summary of one process
+///
+/// `ports` may hold every scanned socket; only listeners owned by the process are
+/// listed. The layout is stable so reports from different runs diff cleanly.
+pub fn render_process_report(
+    info: &ProcessInfo,
+    ports: &[PortInfo],
+    container: Option<&ContainerInfo>,
+    now: DateTime<Utc>,
+) -> String {
+    let mut out = String::new();
+
+    write_field(&mut out, "Process", &format!("{} (PID {})", info.name, info.pid));
+    write_field(&mut out, "User", or_unknown(Some(info.user.as_str())));
+    write_field(&mut out, "Exe", or_unknown(info.exe_path.as_deref()));
+    write_field(&mut out, "Command", or_unknown(info.command_line.as_deref()));
+
+    let uptime = match (info.start_time, uptime_secs(info.start_time, now)) {
+        (Some(started), Some(secs)) => format!(
+            "{} (since {})",
+            format_age(secs),
+            started.format("%Y-%m-%d %H:%M:%S UTC")
+        ),
+        _ => UNKNOWN.to_string(),
+    };
+    write_field(&mut out, "Uptime", &uptime);
+    write_field(&mut out, "Memory", &format_bytes(info.memory_usage));
+    write_field(&mut out, "CPU", &format!("{:.1}%", info.cpu_usage));
+
+    let listeners = listener_lines(ports, info.pid);
+    if listeners.is_empty() {
+        write_field(&mut out, "Listening", "none");
+    } else {
+        write_field(&mut out, "Listening", &listeners[0]);
+        for line in &listeners[1..] {
+            write_field(&mut out, "", line);
+        }
+    }
+
+    if let Some(container) = container {
+        let short_id: String = container.id.chars().take(12).collect();
+        write_field(
+            &mut out,
+            "Container",
+            &format!(
+                "{} ({}, {}, {})",
+                container.name, container.image, container.state, short_id
+            ),
+        );
+    }
+
+    out
+}
+
+fn write_field(out: &mut String, label: &str, value: &str) {
+    let label = if label.is_empty() {
+        String::new()
+    } else {
+        format!("{}:", label)
+    };
+    let _ = writeln!(out, "{:<width$}{}", label, value, width = LABEL_WIDTH);
+}
+
+fn or_unknown(value: Option<&str>) -> &str {
+    value
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .unwrap_or(UNKNOWN)
+}
+
+/// One line per distinct listener of `pid`, e.g. "TCP 0.0.0.0:443 (https)"
+fn listener_lines(ports: &[PortInfo], pid: u32) -> Vec<String> {
+    let mut listeners: Vec<(u16, &str, Protocol)> = ports
+        .iter()
+        .filter(|p| p.state == SocketState::Listening && p.pids.contains(&pid))
+        .map(|p| (p.local_port, p.local_address.as_str(), p.protocol))
+        .collect();
+    listeners.sort_by(|a, b| {
+        a.0.cmp(&b.0)
+            .then(protocol_label(a.2).cmp(protocol_label(b.2)))
+            .then(a.1.cmp(b.1))
+    });
+    listeners.dedup();
+
+    listeners
+        .into_iter()
+        .map(|(port, address, protocol)| {
+            let host = if address.contains(':') {
+                format!("[{}]", address)
+            } else {
+                address.to_string()
+            };
+            let mut line = format!("{} {}:{}", protocol_label(protocol), host, port);
+            if let Some(service) = service_name(port, protocol) {
+                let _ = write!(line, " ({})", service);
+            }
+            line
+        })
+        .collect()
+}
+
+fn protocol_label(protocol: Protocol) -> &'static str {
+    match protocol {
+        Protocol::TCP => "TCP",
+        Protocol::UDP => "UDP",
+    }
+}
+
+/// Format a byte count with binary units, e.g. "512 B", "1.5 MiB"
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{container, listener, process_info};
+    use chrono::TimeZone;
+
+    fn process(pid: u32) -> ProcessInfo {
+        ProcessInfo {
+            exe_path: Some("/usr/sbin/nginx".to_string()),
+            command_line: Some("nginx -g daemon off;".to_string()),
+            args: ["nginx", "-g", "daemon off;"].map(String::from).to_vec(),
+            user: "www-data".to_string(),
+            memory_usage: 3 * 1024 * 1024 / 2,
+            cpu_usage: 2.5,
+            start_time: Some(Utc.with_ymd_and_hms(2026, 1, 2, 3, 0, 0).unwrap()),
+            parent_pid: Some(1),
+            ..process_info(pid, "nginx")
+        }
+    }
+
+    #[test]
+    fn test_render_process_report() {
+        let now = Utc.with_ymd_and_hms(2026, 1, 2, 6, 5, 0).unwrap();
+        let ports = vec![
+            listener("0.0.0.0", 443, 10),
+            listener("::", 80, 10),
+            listener("0.0.0.0", 443, 10),
+            listener("0.0.0.0", 8080, 99),
+        ];
+        let container = ContainerInfo {
+            image: "nginx:1.27".to_string(),
+            status: "Up 3 hours".to_string(),
+            ..container("0123456789abcdef", "web")
+        };
+
+        let report = render_process_report(&process(10), &ports, Some(&container), now);
+        let expected = "\
+Process:   nginx (PID 10)
+User:      www-data
+Exe:       /usr/sbin/nginx
+Command:   nginx -g daemon off;
+Uptime:    3h 5m (since 2026-01-02 03:00:00 UTC)
+Memory:    1.5 MiB
+CPU:       2.5%
+Listening: TCP [::]:80 (http)
+           TCP 0.0.0.0:443 (https)
+Container: web (nginx:1.27, running, 0123456789ab)
+";
+        assert_eq!(report, expected);
+    }
+
+    #[test]
+    fn test_render_process_report_unknown_fields() {
+        let info = ProcessInfo {
+            exe_path: None,
+            command_line: Some("  ".to_string()),
+            user: String::new(),
+            start_time: None,
+            memory_usage: 512,
+            ..process(20)
+        };
+
+        let report = render_process_report(&info, &[], None, Utc::now());
+        assert!(report.contains("User:      unknown\n"));
+        assert!(report.contains("Exe:       unknown\n"));
+        assert!(report.contains("Command:   unknown\n"));
+        assert!(report.contains("Uptime:    unknown\n"));
+        assert!(report.contains("Memory:    512 B\n"));
+        assert!(report.contains("Listening: none\n"));
+        assert!(!report.contains("Container"));
+    }
+}
@@ -0,0 +1,49 @@
+// Schema Module - JSON Schema of the models the frontend consumes
+use crate::models::{AppError, AppState, ContainerInfo, KillResult, ProcessNode};
+use schemars::r#gen::SchemaSettings;
+use serde_json::{json, Value};
+
+/// Draft 7 schema document with one definition per model sent to the frontend
+///
+/// Types nested in the root models (port entries, container ports, enums) get their
+/// own definitions too. Field names and enum values follow the serde renames, so the
+/// document describes the wire format rather than the Rust structs.
+pub fn model_schema() -> Value {
+    let settings = SchemaSettings::draft07();
+    let meta_schema = settings.meta_schema.clone();
+    let mut generator = settings.into_generator();
+
+    generator.subschema_for::<AppState>();
+    generator.subschema_for::<ProcessNode>();
+    generator.subschema_for::<ContainerInfo>();
+    generator.subschema_for::<KillResult>();
+    generator.subschema_for::<AppError>();
+
+    json!({
+        "$schema": meta_schema,
+        "title": "Process Surgeon models",
+        "definitions": generator.take_definitions(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_model_schema_uses_wire_names() {
+        let schema = model_schema();
+        let definitions = &schema["definitions"];
+
+        for name in ["AppState", "ProcessNode", "ContainerInfo", "KillResult", "AppError"] {
+            assert!(definitions[name].is_object(), "missing definition for {}", name);
+        }
+        assert!(definitions["PortEntry"]["properties"]["localPort"].is_object());
+        // Flattened port counts appear on AppState itself
+        assert!(definitions["AppState"]["properties"]["tcpCount"].is_object());
+        assert!(definitions["ErrorCode"]["enum"]
+            .as_array()
+            .unwrap()
+            .contains(&json!("DOCKER_VERSION_MISMATCH")));
+    }
+}
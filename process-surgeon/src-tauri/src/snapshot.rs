@@ -0,0 +1,258 @@
+// Snapshot Module - Named baselines of listening ports, persisted for later comparison
+use crate::discovery::{normalize_listen_address, AddressClass};
+use crate::models::{AppState, Protocol, SnapshotComparison, SnapshotListener, SocketState};
+use anyhow::{anyhow, bail, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+/// Directory next to the settings file that holds one JSON file per snapshot
+pub const SNAPSHOT_DIR_NAME: &str = "snapshots";
+
+/// Longest accepted snapshot name
+const MAX_NAME_LEN: usize = 64;
+
+/// Listeners captured under a name, as saved on disk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Snapshot {
+    pub name: String,
+    pub taken_at: DateTime<Utc>,
+    pub listeners: Vec<SnapshotListener>,
+}
+
+impl Snapshot {
+    /// Record every listener in `state`, sorted by port
+    pub fn capture(name: &str, state: &AppState) -> Self {
+        Self {
+            name: name.to_string(),
+            taken_at: state.last_updated,
+            listeners: listeners_of(state),
+        }
+    }
+
+    /// Listeners in `current` that are new since the snapshot, and ones that went away
+    ///
+    /// Listeners are matched by process name, protocol, port and address class rather
+    /// than PID, so a service restarted since the baseline is not reported.
+    pub fn compare(&self, current: &[SnapshotListener]) -> SnapshotComparison {
+        let before: HashSet<_> = self.listeners.iter().map(listener_key).collect();
+        let after: HashSet<_> = current.iter().map(listener_key).collect();
+
+        SnapshotComparison {
+            snapshot: self.name.clone(),
+            taken_at: self.taken_at,
+            added: current
+                .iter()
+                .filter(|listener| !before.contains(&listener_key(listener)))
+                .cloned()
+                .collect(),
+            removed: self
+                .listeners
+                .iter()
+                .filter(|listener| !after.contains(&listener_key(listener)))
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+/// Every listening socket in a scan, one entry per process, protocol, address and port
+pub fn listeners_of(state: &AppState) -> Vec<SnapshotListener> {
+    let mut listeners: Vec<SnapshotListener> = state
+        .processes
+        .iter()
+        .flat_map(|node| {
+            node.ports
+                .iter()
+                .filter(|port| port.state == SocketState::Listening)
+                .map(|port| SnapshotListener {
+                    pid: node.pid,
+                    process_name: node.name.clone(),
+                    protocol: port.protocol,
+                    local_address: port.local_address.clone(),
+                    local_port: port.local_port,
+                })
+        })
+        .collect();
+
+    // Grouped nodes can repeat a listener shared by their workers
+    let mut seen = HashSet::new();
+    listeners.retain(|l| seen.insert((l.pid, l.protocol, l.local_port, l.local_address.clone())));
+    listeners.sort_by(|a, b| {
+        a.local_port
+            .cmp(&b.local_port)
+            .then(a.pid.cmp(&b.pid))
+            .then(a.local_address.cmp(&b.local_address))
+    });
+    listeners
+}
+
+fn listener_key(listener: &SnapshotListener) -> (String, Protocol, u16, AddressClass) {
+    (
+        listener.process_name.to_lowercase(),
+        listener.protocol,
+        listener.local_port,
+        normalize_listen_address(&listener.local_address),
+    )
+}
+
+/// Check a snapshot name is safe to use as a file name
+///
+/// Letters, digits, spaces, `-`, `_` and `.` are allowed, up to 64 characters, and the
+/// name may not start with a dot.
+pub fn is_valid_snapshot_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= MAX_NAME_LEN
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, ' ' | '-' | '_' | '.'))
+}
+
+/// Snapshots saved as JSON files in one directory
+///
+/// Without a directory (the config directory could not be resolved) nothing can be
+/// saved and no snapshots are listed.
+#[derive(Debug, Default)]
+pub struct SnapshotStore {
+    dir: Option<PathBuf>,
+}
+
+impl SnapshotStore {
+    pub fn new(dir: Option<PathBuf>) -> Self {
+        Self { dir }
+    }
+
+    /// Write a snapshot, replacing any earlier one with the same name
+    pub fn save(&self, snapshot: &Snapshot) -> Result<()> {
+        let path = self.path(&snapshot.name)?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(snapshot)?)?;
+        Ok(())
+    }
+
+    /// Read a snapshot by name, or `None` if there is none with that name
+    pub fn load(&self, name: &str) -> Result<Option<Snapshot>> {
+        if self.dir.is_none() {
+            return Ok(None);
+        }
+        let path = self.path(name)?;
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => Ok(Some(serde_json::from_str(&contents)?)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Names of all saved snapshots, sorted
+    pub fn list(&self) -> Result<Vec<String>> {
+        let Some(dir) = &self.dir else {
+            return Ok(Vec::new());
+        };
+        let entries = match std::fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut names = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                if let Some(stem) = path.file_stem() {
+                    names.push(stem.to_string_lossy().into_owned());
+                }
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    fn path(&self, name: &str) -> Result<PathBuf> {
+        if !is_valid_snapshot_name(name) {
+            bail!("Invalid snapshot name '{}'", name);
+        }
+        let dir = self
+            .dir
+            .as_ref()
+            .ok_or_else(|| anyhow!("No config directory, snapshots cannot be saved"))?;
+        Ok(dir.join(format!("{}.json", name)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn listener(pid: u32, name: &str, address: &str, port: u16) -> SnapshotListener {
+        SnapshotListener {
+            pid,
+            process_name: name.to_string(),
+            protocol: Protocol::TCP,
+            local_address: address.to_string(),
+            local_port: port,
+        }
+    }
+
+    fn snapshot(listeners: Vec<SnapshotListener>) -> Snapshot {
+        Snapshot {
+            name: "baseline".to_string(),
+            taken_at: Utc::now(),
+            listeners,
+        }
+    }
+
+    #[test]
+    fn test_compare_ignores_restarts() {
+        let baseline = snapshot(vec![
+            listener(10, "postgres", "127.0.0.1", 5432),
+            listener(20, "nginx", "0.0.0.0", 80),
+        ]);
+        let current = vec![
+            // Restarted with a new PID, now bound through an IPv4-mapped address
+            listener(11, "postgres", "::ffff:127.0.0.1", 5432),
+            listener(30, "node", "0.0.0.0", 3000),
+        ];
+
+        let comparison = baseline.compare(&current);
+        assert_eq!(comparison.snapshot, "baseline");
+        assert_eq!(comparison.added.len(), 1);
+        assert_eq!(comparison.added[0].local_port, 3000);
+        assert_eq!(comparison.removed.len(), 1);
+        assert_eq!(comparison.removed[0].process_name, "nginx");
+    }
+
+    #[test]
+    fn test_is_valid_snapshot_name() {
+        assert!(is_valid_snapshot_name("before upgrade"));
+        assert!(is_valid_snapshot_name("dev-2026.01_a"));
+        assert!(!is_valid_snapshot_name(""));
+        assert!(!is_valid_snapshot_name("../settings"));
+        assert!(!is_valid_snapshot_name(".hidden"));
+        assert!(!is_valid_snapshot_name("a/b"));
+        assert!(!is_valid_snapshot_name(&"x".repeat(MAX_NAME_LEN + 1)));
+    }
+
+    #[test]
+    fn test_snapshot_store_round_trip() {
+        let dir = std::env::temp_dir()
+            .join(format!("process-surgeon-snapshots-{}", std::process::id()));
+        let store = SnapshotStore::new(Some(dir.clone()));
+        assert!(store.list().unwrap().is_empty());
+        assert!(store.load("baseline").unwrap().is_none());
+
+        store
+            .save(&snapshot(vec![listener(10, "postgres", "127.0.0.1", 5432)]))
+            .unwrap();
+        assert_eq!(store.list().unwrap(), vec!["baseline"]);
+        let loaded = store.load("baseline").unwrap().unwrap();
+        assert_eq!(loaded.listeners, vec![listener(10, "postgres", "127.0.0.1", 5432)]);
+        assert!(store.load("../baseline").is_err());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,199 @@
+// Affinity Module - Pinning processes to CPU cores
+use std::io;
+
+/// Cores a process may run on, as ascending core indices
+#[cfg(target_os = "linux")]
+pub fn get_cpu_affinity(pid: u32) -> io::Result<Vec<usize>> {
+    // SAFETY: an all-zero cpu_set_t is a valid empty set
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    // SAFETY: set is a live cpu_set_t and its exact size is passed alongside it
+    let result = unsafe {
+        libc::sched_getaffinity(
+            pid as libc::pid_t,
+            std::mem::size_of::<libc::cpu_set_t>(),
+            &mut set,
+        )
+    };
+    if result != 0 {
+        return Err(affinity_error(pid));
+    }
+
+    // SAFETY: every index is below CPU_SETSIZE, the capacity of the set
+    Ok((0..libc::CPU_SETSIZE as usize)
+        .filter(|&core| unsafe { libc::CPU_ISSET(core, &set) })
+        .collect())
+}
+
+/// Restrict a process to `cores`, returning the affinity the kernel actually applied
+///
+/// Cores that are offline are dropped by the kernel; if none of them are usable the
+/// call fails with `InvalidInput`.
+#[cfg(target_os = "linux")]
+pub fn set_cpu_affinity(pid: u32, cores: &[usize]) -> io::Result<Vec<usize>> {
+    validate_cores(cores, libc::CPU_SETSIZE as usize)?;
+
+    // SAFETY: an all-zero cpu_set_t is a valid empty set
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    for &core in cores {
+        // SAFETY: validate_cores keeps every index below CPU_SETSIZE
+        unsafe { libc::CPU_SET(core, &mut set) };
+    }
+    // SAFETY: set is a live cpu_set_t and its exact size is passed alongside it
+    let result = unsafe {
+        libc::sched_setaffinity(
+            pid as libc::pid_t,
+            std::mem::size_of::<libc::cpu_set_t>(),
+            &set,
+        )
+    };
+    if result != 0 {
+        return Err(affinity_error(pid));
+    }
+
+    get_cpu_affinity(pid)
+}
+
+/// Turn the current errno into an error naming the process
+#[cfg(target_os = "linux")]
+fn affinity_error(pid: u32) -> io::Error {
+    let error = io::Error::last_os_error();
+    match error.raw_os_error() {
+        Some(libc::ESRCH) => {
+            io::Error::new(io::ErrorKind::NotFound, format!("Process {} not found", pid))
+        }
+        Some(libc::EPERM) | Some(libc::EACCES) => io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!("Not allowed to change the CPU affinity of process {}", pid),
+        ),
+        Some(libc::EINVAL) => io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "None of the requested cores are online",
+        ),
+        _ => error,
+    }
+}
+
+/// Cores a process may run on, as ascending core indices
+#[cfg(windows)]
+pub fn get_cpu_affinity(pid: u32) -> io::Result<Vec<usize>> {
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Threading::{
+        GetProcessAffinityMask, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+
+    let mut process_mask = 0usize;
+    let mut system_mask = 0usize;
+    // SAFETY: the handle is checked by OpenProcess and closed before returning
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid)
+            .map_err(io::Error::from)?;
+        let result = GetProcessAffinityMask(handle, &mut process_mask, &mut system_mask);
+        let _ = CloseHandle(handle);
+        result.map_err(io::Error::from)?;
+    }
+
+    Ok(mask_to_cores(process_mask))
+}
+
+/// Restrict a process to `cores`, returning the affinity Windows actually applied
+///
+/// Only the first processor group (64 cores) can be addressed.
+#[cfg(windows)]
+pub fn set_cpu_affinity(pid: u32, cores: &[usize]) -> io::Result<Vec<usize>> {
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Threading::{
+        OpenProcess, SetProcessAffinityMask, PROCESS_QUERY_LIMITED_INFORMATION,
+        PROCESS_SET_INFORMATION,
+    };
+
+    validate_cores(cores, usize::BITS as usize)?;
+    let mask = cores.iter().fold(0usize, |mask, &core| mask | (1 << core));
+
+    // SAFETY: the handle is checked by OpenProcess and closed before returning
+    unsafe {
+        let handle = OpenProcess(
+            PROCESS_SET_INFORMATION | PROCESS_QUERY_LIMITED_INFORMATION,
+            false,
+            pid,
+        )
+        .map_err(io::Error::from)?;
+        let result = SetProcessAffinityMask(handle, mask);
+        let _ = CloseHandle(handle);
+        result.map_err(io::Error::from)?;
+    }
+
+    get_cpu_affinity(pid)
+}
+
+#[cfg(windows)]
+fn mask_to_cores(mask: usize) -> Vec<usize> {
+    (0..usize::BITS as usize)
+        .filter(|&core| mask & (1 << core) != 0)
+        .collect()
+}
+
+/// macOS only offers per-thread affinity hints, so there is nothing to report or set
+#[cfg(not(any(target_os = "linux", windows)))]
+pub fn get_cpu_affinity(_pid: u32) -> io::Result<Vec<usize>> {
+    Err(unsupported())
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+pub fn set_cpu_affinity(_pid: u32, _cores: &[usize]) -> io::Result<Vec<usize>> {
+    Err(unsupported())
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+fn unsupported() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        "CPU affinity is not supported on this platform",
+    )
+}
+
+/// Reject an empty core list or indices the platform's mask cannot hold
+#[cfg(any(target_os = "linux", windows))]
+fn validate_cores(cores: &[usize], limit: usize) -> io::Result<()> {
+    if cores.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "At least one core is required",
+        ));
+    }
+    if let Some(core) = cores.iter().find(|&&core| core >= limit) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Core {} is out of range (at most {} cores)", core, limit),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_cpu_affinity() {
+        let mut child = std::process::Command::new("sleep").arg("30").spawn().unwrap();
+        let pid = child.id();
+
+        let cores = get_cpu_affinity(pid).unwrap();
+        assert!(!cores.is_empty());
+        assert_eq!(set_cpu_affinity(pid, &cores[..1]).unwrap(), vec![cores[0]]);
+
+        let error = set_cpu_affinity(pid, &[]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        let error = set_cpu_affinity(pid, &[usize::MAX]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+
+        child.kill().unwrap();
+        child.wait().unwrap();
+    }
+
+    #[test]
+    fn test_get_cpu_affinity_missing_process() {
+        let error = get_cpu_affinity(u32::MAX - 1).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+    }
+}
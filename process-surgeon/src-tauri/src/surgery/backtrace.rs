@@ -0,0 +1,244 @@
+// Backtrace Module - Capturing what a stuck process is doing before it is killed
+use crate::models::BacktraceTool;
+use std::io;
+use std::time::Duration;
+
+/// Longest a debugger may take to attach and dump every thread
+pub const BACKTRACE_TIMEOUT: Duration = Duration::from_secs(30);
+
+impl BacktraceTool {
+    /// Executable looked up on `PATH`
+    pub fn program(self) -> &'static str {
+        match self {
+            BacktraceTool::Gdb => "gdb",
+            BacktraceTool::Lldb => "lldb",
+            BacktraceTool::PySpy => "py-spy",
+        }
+    }
+
+    /// Arguments that attach to `pid`, print every thread's stack and detach
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    fn args(self, pid: u32) -> Vec<String> {
+        let pid = pid.to_string();
+        match self {
+            BacktraceTool::Gdb => vec![
+                "-p".to_string(),
+                pid,
+                "-batch".to_string(),
+                "-ex".to_string(),
+                "thread apply all bt".to_string(),
+                "-ex".to_string(),
+                "detach".to_string(),
+            ],
+            BacktraceTool::Lldb => vec![
+                "-p".to_string(),
+                pid,
+                "--batch".to_string(),
+                "-o".to_string(),
+                "thread backtrace all".to_string(),
+                "-o".to_string(),
+                "process detach".to_string(),
+            ],
+            BacktraceTool::PySpy => vec!["dump".to_string(), "--pid".to_string(), pid],
+        }
+    }
+}
+
+/// Pick the tool to use for a process, given which ones are installed
+///
+/// Python processes get py-spy's interpreter-level stacks when it is available; native
+/// debuggers follow in the platform's preferred order.
+pub fn choose_backtrace_tool(
+    process_name: &str,
+    is_installed: impl Fn(BacktraceTool) -> bool,
+) -> Option<BacktraceTool> {
+    let native = if cfg!(target_os = "macos") {
+        [BacktraceTool::Lldb, BacktraceTool::Gdb]
+    } else {
+        [BacktraceTool::Gdb, BacktraceTool::Lldb]
+    };
+    let is_python = process_name.to_lowercase().starts_with("python");
+
+    is_python
+        .then_some(BacktraceTool::PySpy)
+        .into_iter()
+        .chain(native)
+        .find(|&tool| is_installed(tool))
+}
+
+/// Full path of `program` in one of the `PATH` directories
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn find_on_path(program: &str) -> Option<std::path::PathBuf> {
+    let paths = std::env::var_os("PATH")?;
+    std::env::split_paths(&paths)
+        .map(|dir| dir.join(program))
+        .find(|candidate| is_executable(candidate))
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn is_executable(path: &std::path::Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    path.metadata()
+        .map(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+/// Attach a debugger to `pid` and return its dump of every thread's stack
+///
+/// Uses py-spy for Python processes when installed, otherwise gdb or lldb. The process
+/// is paused while the debugger is attached. Fails with `NotFound` when no tool is
+/// installed, `PermissionDenied` when attaching is not allowed (e.g. Yama's
+/// `ptrace_scope`, or macOS without developer mode), and `TimedOut` after
+/// `BACKTRACE_TIMEOUT`. A debugger that times out is killed, which can leave the
+/// process stopped, so it is then sent SIGCONT unless it was stopped beforehand.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+pub async fn capture_backtrace(
+    pid: u32,
+    process_name: &str,
+) -> io::Result<(BacktraceTool, String)> {
+    let Some(tool) = choose_backtrace_tool(process_name, |tool| {
+        find_on_path(tool.program()).is_some()
+    }) else {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "No debugger found; install gdb or lldb (or py-spy for Python processes)",
+        ));
+    };
+
+    let was_stopped = is_stopped(pid);
+    let mut debugger = tokio::process::Command::new(tool.program())
+        .args(tool.args(pid))
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()?;
+    let (stdout_pipe, stderr_pipe) = (debugger.stdout.take(), debugger.stderr.take());
+    let finished = async {
+        tokio::try_join!(debugger.wait(), read_pipe(stdout_pipe), read_pipe(stderr_pipe))
+    };
+    let (status, stdout, stderr) = match tokio::time::timeout(BACKTRACE_TIMEOUT, finished).await {
+        Ok(output) => output?,
+        Err(_) => {
+            // Waiting for the debugger to exit means the kernel has detached it
+            let _ = debugger.kill().await;
+            if !was_stopped {
+                resume(pid);
+            }
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                format!("{} did not finish within {:?}", tool.program(), BACKTRACE_TIMEOUT),
+            ));
+        }
+    };
+
+    let stdout = String::from_utf8_lossy(&stdout);
+    let stderr = String::from_utf8_lossy(&stderr);
+    // gdb warns about unreadable debug files on stderr even when the dump succeeds
+    let failed = !status.success() || stdout.trim().is_empty();
+    if failed && is_attach_denied(&stderr) {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!(
+                "Not allowed to attach {} to process {}: {}",
+                tool.program(),
+                pid,
+                stderr.trim()
+            ),
+        ));
+    }
+    if failed && stdout.trim().is_empty() {
+        return Err(io::Error::other(format!(
+            "{} failed: {}",
+            tool.program(),
+            stderr.trim()
+        )));
+    }
+
+    Ok((tool, stdout.into_owned()))
+}
+
+/// Everything written to a child's output pipe
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+async fn read_pipe(pipe: Option<impl tokio::io::AsyncRead + Unpin>) -> io::Result<Vec<u8>> {
+    use tokio::io::AsyncReadExt;
+
+    let mut buffer = Vec::new();
+    if let Some(mut pipe) = pipe {
+        pipe.read_to_end(&mut buffer).await?;
+    }
+    Ok(buffer)
+}
+
+/// Whether `pid` is stopped by a signal, e.g. suspended by the user
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn is_stopped(pid: u32) -> bool {
+    use sysinfo::{Pid, ProcessRefreshKind, ProcessStatus, ProcessesToUpdate, System};
+
+    let pid = Pid::from_u32(pid);
+    let mut system = System::new();
+    system.refresh_processes_specifics(ProcessesToUpdate::Some(&[pid]), ProcessRefreshKind::new());
+    system
+        .process(pid)
+        .is_some_and(|process| process.status() == ProcessStatus::Stop)
+}
+
+/// Continue a process a killed debugger may have left stopped
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn resume(pid: u32) {
+    if unsafe { libc::kill(pid as libc::pid_t, libc::SIGCONT) } != 0 {
+        log::warn!(
+            "Failed to resume process {} after the debugger timed out: {}",
+            pid,
+            io::Error::last_os_error()
+        );
+    }
+}
+
+/// Attaching debuggers is only supported on Linux and macOS
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub async fn capture_backtrace(
+    _pid: u32,
+    _process_name: &str,
+) -> io::Result<(BacktraceTool, String)> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "Capturing backtraces is only supported on Linux and macOS",
+    ))
+}
+
+/// Whether debugger output says the kernel refused to let it attach
+#[cfg(any(target_os = "linux", target_os = "macos", test))]
+fn is_attach_denied(stderr: &str) -> bool {
+    stderr.contains("Operation not permitted")
+        || stderr.contains("Permission denied")
+        || stderr.contains("attach failed")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_choose_backtrace_tool() {
+        let all = |_: BacktraceTool| true;
+        let only = |wanted: BacktraceTool| move |tool: BacktraceTool| tool == wanted;
+
+        assert_eq!(choose_backtrace_tool("python3.12", all), Some(BacktraceTool::PySpy));
+        assert_ne!(choose_backtrace_tool("node", all), Some(BacktraceTool::PySpy));
+        assert_eq!(
+            choose_backtrace_tool("python3", only(BacktraceTool::Gdb)),
+            Some(BacktraceTool::Gdb)
+        );
+        // py-spy only understands Python processes
+        assert_eq!(choose_backtrace_tool("node", only(BacktraceTool::PySpy)), None);
+        assert_eq!(choose_backtrace_tool("node", |_| false), None);
+    }
+
+    #[test]
+    fn test_attach_denied() {
+        assert!(is_attach_denied("ptrace: Operation not permitted.\nNo stack.\n"));
+        assert!(!is_attach_denied("warning: could not find .gnu_debugaltlink file\n"));
+    }
+}
@@ -0,0 +1,137 @@
+// Connection Module - Closing individual sockets instead of whole processes
+use crate::models::KillResult;
+use std::io;
+#[cfg(target_os = "linux")]
+use std::net::IpAddr;
+
+/// Close one established TCP connection identified by its 4-tuple
+///
+/// Uses `ss --kill`, which needs CAP_NET_ADMIN; without it nothing is closed and the
+/// result asks for elevation.
+#[cfg(target_os = "linux")]
+pub fn close_connection(
+    local: &str,
+    local_port: u16,
+    remote: &str,
+    remote_port: u16,
+) -> io::Result<KillResult> {
+    use std::process::Command;
+
+    let local_ip = parse_ip(local)?;
+    let remote_ip = parse_ip(remote)?;
+    let description = format!("{}:{} -> {}:{}", local, local_port, remote, remote_port);
+
+    let output = Command::new("ss")
+        .args(["--tcp", "--kill"])
+        .args(ss_filter(local_ip, local_port, remote_ip, remote_port))
+        .output()?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() || stderr.contains("Operation not permitted") {
+        return Ok(KillResult {
+            success: false,
+            message: format!(
+                "Failed to close connection {}. Closing sockets requires CAP_NET_ADMIN: {}",
+                description,
+                stderr.trim()
+            ),
+            required_elevation: true,
+        });
+    }
+
+    // ss prints a header line followed by one line per socket it killed
+    let closed = String::from_utf8_lossy(&output.stdout).lines().skip(1).count();
+    if closed == 0 {
+        return Ok(KillResult {
+            success: false,
+            message: format!("No established connection matches {}", description),
+            required_elevation: false,
+        });
+    }
+
+    Ok(KillResult {
+        success: true,
+        message: format!("Closed connection {}", description),
+        required_elevation: false,
+    })
+}
+
+/// Closing individual sockets is not supported on this platform
+#[cfg(not(target_os = "linux"))]
+pub fn close_connection(
+    _local: &str,
+    _local_port: u16,
+    _remote: &str,
+    _remote_port: u16,
+) -> io::Result<KillResult> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "Closing individual connections is only available on Linux",
+    ))
+}
+
+/// Parse an address so nothing but an IP ends up in the ss filter
+#[cfg(target_os = "linux")]
+fn parse_ip(address: &str) -> io::Result<IpAddr> {
+    address.trim().parse().map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} is not an IP address", address),
+        )
+    })
+}
+
+/// Build the ss filter expression matching exactly one 4-tuple
+#[cfg(target_os = "linux")]
+fn ss_filter(local: IpAddr, local_port: u16, remote: IpAddr, remote_port: u16) -> Vec<String> {
+    vec![
+        "src".to_string(),
+        ss_address(local),
+        "sport".to_string(),
+        "=".to_string(),
+        format!(":{}", local_port),
+        "dst".to_string(),
+        ss_address(remote),
+        "dport".to_string(),
+        "=".to_string(),
+        format!(":{}", remote_port),
+    ]
+}
+
+/// ss expects IPv6 addresses in brackets
+#[cfg(target_os = "linux")]
+fn ss_address(ip: IpAddr) -> String {
+    match ip {
+        IpAddr::V4(v4) => v4.to_string(),
+        IpAddr::V6(v6) => format!("[{}]", v6),
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ss_filter() {
+        let filter = ss_filter(
+            "192.168.1.10".parse().unwrap(),
+            51234,
+            "2001:db8::1".parse().unwrap(),
+            443,
+        );
+        assert_eq!(
+            filter.join(" "),
+            "src 192.168.1.10 sport = :51234 dst [2001:db8::1] dport = :443"
+        );
+    }
+
+    #[test]
+    fn test_parse_ip_rejects_filter_syntax() {
+        assert!(parse_ip("10.0.0.1").is_ok());
+        assert!(parse_ip("::1").is_ok());
+        assert_eq!(
+            parse_ip("10.0.0.1 or dport = :22").unwrap_err().kind(),
+            io::ErrorKind::InvalidInput
+        );
+    }
+}
@@ -0,0 +1,533 @@
+// Elevated Session Module - A long-lived privileged helper for repeated kills
+//
+// One-shot elevation prompts for credentials on every kill. A session instead
+// authenticates a root shell once; it reads `TERM|KILL <pid>` lines and answers each
+// with `OK` or `ERR <reason>`. The shell exits when its request stream closes, which
+// happens when the session ends, idles out, or the app quits.
+use crate::models::KillResult;
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+use crate::surgery::safety::ElevatedHelperGuard;
+use anyhow::Result;
+use std::time::Duration;
+
+/// A session ends after this long without a kill request
+pub const ELEVATED_SESSION_IDLE_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// How long to wait for the user to authenticate the helper
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+const AUTH_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// How long one kill request may take before the helper is considered broken
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How often the watchdog looks for an idle or dead session
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+const WATCHDOG_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Root shell loop; with two arguments it talks over those FIFOs instead of stdio
+///
+/// Only `TERM`/`KILL` and all-digit PIDs are accepted, so a malformed line can't turn
+/// into anything but a refused request.
+#[cfg(any(target_os = "linux", target_os = "macos", test))]
+const HELPER_SCRIPT: &str = r#"
+exec 2>/dev/null
+if [ $# -eq 2 ]; then exec <"$1" >"$2"; fi
+echo "READY $$"
+while read -r sig pid; do
+  case "$sig" in TERM|KILL) ;; *) echo "ERR invalid signal"; continue ;; esac
+  case "$pid" in ''|*[!0-9]*) echo "ERR invalid pid"; continue ;; esac
+  if out=$(kill -"$sig" "$pid" 2>&1); then echo OK; else echo "ERR $out" | head -n 1; fi
+done
+"#;
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+mod session {
+    use super::*;
+    use anyhow::{anyhow, bail};
+    use once_cell::sync::Lazy;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::path::PathBuf;
+    use std::process::Child;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+    use std::sync::Mutex;
+    use std::time::Instant;
+
+    /// The running session, if any
+    static SESSION: Lazy<Mutex<Option<ElevatedSession>>> = Lazy::new(|| Mutex::new(None));
+
+    /// Held for the whole of `start`, so concurrent starts share one prompt
+    static STARTING: Mutex<()> = Mutex::new(());
+
+    /// Source of session ids, letting a watchdog tell its session from a newer one
+    static NEXT_SESSION_ID: AtomicU64 = AtomicU64::new(1);
+
+    /// Opens the helper's response stream; may block until the helper connects
+    pub(super) type ResponseOpener = Box<dyn FnOnce() -> std::io::Result<Box<dyn Read>> + Send>;
+
+    /// A spawned helper before it has confirmed authentication
+    pub(super) struct SpawnedHelper {
+        pub child: Child,
+        pub requests: Box<dyn Write + Send>,
+        pub open_responses: ResponseOpener,
+        /// Directory holding the FIFOs, removed when the session ends
+        pub fifo_dir: Option<PathBuf>,
+    }
+
+    struct ElevatedSession {
+        id: u64,
+        child: Child,
+        requests: Box<dyn Write + Send>,
+        responses: Receiver<String>,
+        last_used: Instant,
+        fifo_dir: Option<PathBuf>,
+        /// Keeps the root shell's PID protected for the session's lifetime
+        _guard: ElevatedHelperGuard,
+    }
+
+    impl ElevatedSession {
+        /// Send one kill request and wait for the helper's answer
+        fn kill(&mut self, pid: u32, force: bool) -> Result<KillResult> {
+            let signal = if force { "KILL" } else { "TERM" };
+            writeln!(self.requests, "{} {}", signal, pid)?;
+            self.requests.flush()?;
+
+            let reply = self
+                .responses
+                .recv_timeout(REQUEST_TIMEOUT)
+                .map_err(|_| anyhow!("Elevated helper stopped responding"))?;
+            self.last_used = Instant::now();
+            Ok(parse_reply(pid, &reply))
+        }
+
+        fn is_alive(&mut self) -> bool {
+            matches!(self.child.try_wait(), Ok(None))
+        }
+    }
+
+    impl Drop for ElevatedSession {
+        fn drop(&mut self) {
+            // Closing the request stream ends the helper's read loop
+            self.requests = Box::new(std::io::sink());
+            if let Some(dir) = self.fifo_dir.take() {
+                super::platform::release_fifos(&dir);
+            }
+
+            let deadline = Instant::now() + Duration::from_secs(2);
+            while matches!(self.child.try_wait(), Ok(None)) {
+                if Instant::now() >= deadline {
+                    // Still waiting on the credentials prompt
+                    let _ = self.child.kill();
+                    let _ = self.child.wait();
+                    break;
+                }
+                std::thread::sleep(Duration::from_millis(50));
+            }
+            log::info!("Elevated session ended");
+        }
+    }
+
+    pub fn start() -> Result<()> {
+        let _starting = STARTING.lock().unwrap_or_else(|e| e.into_inner());
+        if is_active() {
+            return Ok(());
+        }
+
+        let SpawnedHelper {
+            child,
+            requests,
+            open_responses,
+            fifo_dir,
+        } = super::platform::spawn_helper()?;
+        let guard = ElevatedHelperGuard::register(child.id());
+        let responses = spawn_reader(open_responses);
+
+        let mut session = ElevatedSession {
+            id: NEXT_SESSION_ID.fetch_add(1, Ordering::Relaxed),
+            child,
+            requests,
+            responses,
+            last_used: Instant::now(),
+            fifo_dir,
+            _guard: guard,
+        };
+        let shell_pid = wait_until_ready(&mut session)?;
+        // The root shell may be a child of the launcher (osascript); protect it instead
+        session._guard = ElevatedHelperGuard::register(shell_pid);
+
+        let id = session.id;
+        *SESSION.lock().unwrap_or_else(|e| e.into_inner()) = Some(session);
+
+        spawn_watchdog(id);
+        log::info!("Elevated session started (helper PID {})", shell_pid);
+        Ok(())
+    }
+
+    pub fn end() -> bool {
+        let session = SESSION.lock().unwrap_or_else(|e| e.into_inner()).take();
+        session.is_some()
+    }
+
+    pub fn is_active() -> bool {
+        let mut slot = SESSION.lock().unwrap_or_else(|e| e.into_inner());
+        match slot.as_mut() {
+            Some(session) if session.is_alive() => true,
+            Some(_) => {
+                *slot = None;
+                false
+            }
+            None => false,
+        }
+    }
+
+    pub fn kill(pid: u32, force: bool) -> Option<KillResult> {
+        let mut slot = SESSION.lock().unwrap_or_else(|e| e.into_inner());
+        let session = slot.as_mut()?;
+
+        match session.kill(pid, force) {
+            Ok(result) => Some(result),
+            Err(e) => {
+                log::warn!("Elevated session failed, falling back to a prompt: {}", e);
+                *slot = None;
+                None
+            }
+        }
+    }
+
+    /// Forward response lines to a channel so reads can time out
+    fn spawn_reader(open_responses: ResponseOpener) -> Receiver<String> {
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            let Ok(stream) = open_responses() else {
+                return;
+            };
+            for line in BufReader::new(stream).lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        receiver
+    }
+
+    /// Wait for the helper's `READY <pid>` line, i.e. for the user to authenticate
+    fn wait_until_ready(session: &mut ElevatedSession) -> Result<u32> {
+        let deadline = Instant::now() + AUTH_TIMEOUT;
+
+        loop {
+            match session.responses.recv_timeout(Duration::from_millis(200)) {
+                Ok(line) => {
+                    return parse_ready(&line)
+                        .ok_or_else(|| anyhow!("Unexpected reply from elevated helper: {}", line));
+                }
+                Err(RecvTimeoutError::Disconnected) => bail!("Authentication was cancelled"),
+                Err(RecvTimeoutError::Timeout) => {}
+            }
+
+            if !session.is_alive() {
+                bail!("Authentication was cancelled");
+            }
+            if Instant::now() >= deadline {
+                bail!("Timed out waiting for authentication");
+            }
+        }
+    }
+
+    /// End the session once it has been idle too long or its helper died
+    fn spawn_watchdog(id: u64) {
+        std::thread::spawn(move || loop {
+            std::thread::sleep(WATCHDOG_INTERVAL);
+
+            let mut slot = SESSION.lock().unwrap_or_else(|e| e.into_inner());
+            // Gone, or replaced by a newer session that has its own watchdog
+            let Some(session) = slot.as_mut().filter(|session| session.id == id) else {
+                return;
+            };
+
+            if !session.is_alive() {
+                *slot = None;
+                return;
+            }
+            if session.last_used.elapsed() >= ELEVATED_SESSION_IDLE_TIMEOUT {
+                log::info!(
+                    "Elevated session idle for {:?}, ending it",
+                    ELEVATED_SESSION_IDLE_TIMEOUT
+                );
+                *slot = None;
+                return;
+            }
+        });
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use super::session::SpawnedHelper;
+    use super::HELPER_SCRIPT;
+    use anyhow::{anyhow, Result};
+    use std::io::Read;
+    use std::path::Path;
+    use std::process::{Command, Stdio};
+
+    /// Run the helper loop under pkexec, talking over its stdin and stdout
+    pub(super) fn spawn_helper() -> Result<SpawnedHelper> {
+        let mut child = Command::new("pkexec")
+            .args(["/bin/sh", "-c", HELPER_SCRIPT, "sh"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+
+        let requests = child.stdin.take().ok_or_else(|| anyhow!("Helper has no stdin"))?;
+        let responses = child.stdout.take().ok_or_else(|| anyhow!("Helper has no stdout"))?;
+
+        Ok(SpawnedHelper {
+            child,
+            requests: Box::new(requests),
+            open_responses: Box::new(move || Ok(Box::new(responses) as Box<dyn Read>)),
+            fifo_dir: None,
+        })
+    }
+
+    pub(super) fn release_fifos(_dir: &Path) {}
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use super::session::SpawnedHelper;
+    use super::{applescript_string, shell_quote, HELPER_SCRIPT};
+    use anyhow::Result;
+    use std::ffi::CString;
+    use std::fs::{DirBuilder, OpenOptions};
+    use std::io::Read;
+    use std::os::unix::ffi::OsStrExt;
+    use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt};
+    use std::path::{Path, PathBuf};
+    use std::process::{Command, Stdio};
+
+    const REQUESTS_FIFO: &str = "requests";
+    const RESPONSES_FIFO: &str = "responses";
+
+    /// Run the helper loop through osascript, talking over a pair of FIFOs
+    ///
+    /// `do shell script` gives the root shell no usable stdio, so requests and replies
+    /// go through FIFOs in a private (0700) temporary directory.
+    pub(super) fn spawn_helper() -> Result<SpawnedHelper> {
+        let dir = std::env::temp_dir().join(format!(
+            "process-surgeon-elevated-{}-{}",
+            std::process::id(),
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        DirBuilder::new().mode(0o700).create(&dir)?;
+
+        let spawned = spawn_in(&dir);
+        if spawned.is_err() {
+            release_fifos(&dir);
+        }
+        spawned
+    }
+
+    fn spawn_in(dir: &Path) -> Result<SpawnedHelper> {
+        let requests_path = dir.join(REQUESTS_FIFO);
+        let responses_path = dir.join(RESPONSES_FIFO);
+        make_fifo(&requests_path)?;
+        make_fifo(&responses_path)?;
+
+        // Opening read-write never blocks, and keeps the FIFO open until we drop it
+        let requests = OpenOptions::new().read(true).write(true).open(&requests_path)?;
+
+        let command = format!(
+            "/bin/sh -c {} sh {} {}",
+            shell_quote(HELPER_SCRIPT),
+            shell_quote(&requests_path.to_string_lossy()),
+            shell_quote(&responses_path.to_string_lossy())
+        );
+        let script = format!(
+            "do shell script {} with administrator privileges",
+            applescript_string(&command)
+        );
+        let child = Command::new("osascript")
+            .arg("-e")
+            .arg(script)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()?;
+
+        let open_path: PathBuf = responses_path;
+        Ok(SpawnedHelper {
+            child,
+            requests: Box::new(requests),
+            // Blocks until the root shell opens its end
+            open_responses: Box::new(move || {
+                Ok(Box::new(std::fs::File::open(open_path)?) as Box<dyn Read>)
+            }),
+            fifo_dir: Some(dir.to_path_buf()),
+        })
+    }
+
+    fn make_fifo(path: &Path) -> std::io::Result<()> {
+        let path = CString::new(path.as_os_str().as_bytes())?;
+        // SAFETY: path is a valid NUL-terminated string
+        if unsafe { libc::mkfifo(path.as_ptr(), 0o600) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Unblock a reader still waiting for the helper to connect, then remove the FIFOs
+    pub(super) fn release_fifos(dir: &Path) {
+        let _ = OpenOptions::new()
+            .write(true)
+            .custom_flags(libc::O_NONBLOCK)
+            .open(dir.join(RESPONSES_FIFO));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
+
+/// Start a privileged helper, prompting for credentials once
+///
+/// While the session lasts, `kill_via_elevated_session` signals processes without
+/// further prompts. It ends after `ELEVATED_SESSION_IDLE_TIMEOUT` without requests.
+/// Starting while a session is active keeps the existing one.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+pub fn start_elevated_session() -> Result<()> {
+    session::start()
+}
+
+/// Elevated sessions rely on a Unix root shell
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub fn start_elevated_session() -> Result<()> {
+    anyhow::bail!("Elevated sessions are only supported on Linux and macOS")
+}
+
+/// End the elevated session, returning whether one was running
+pub fn end_elevated_session() -> bool {
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    return session::end();
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    return false;
+}
+
+/// Whether an elevated session is running
+pub fn is_elevated_session_active() -> bool {
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    return session::is_active();
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    return false;
+}
+
+/// Signal a process through the elevated session
+///
+/// Returns None when no session is running or the helper broke, in which case the
+/// session is dropped and the caller should fall back to a one-shot prompt.
+pub fn kill_via_elevated_session(pid: u32, force: bool) -> Option<KillResult> {
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    return session::kill(pid, force);
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    {
+        let _ = (pid, force);
+        None
+    }
+}
+
+/// Parse the helper's `READY <pid>` greeting
+#[cfg(any(target_os = "linux", target_os = "macos", test))]
+fn parse_ready(line: &str) -> Option<u32> {
+    line.trim().strip_prefix("READY ")?.parse().ok()
+}
+
+/// Turn a helper reply into a kill result
+#[cfg(any(target_os = "linux", target_os = "macos", test))]
+fn parse_reply(pid: u32, reply: &str) -> KillResult {
+    let reply = reply.trim();
+    if reply == "OK" {
+        KillResult {
+            success: true,
+            message: format!("Process {} terminated with elevated privileges", pid),
+            required_elevation: true,
+        }
+    } else {
+        let reason = reply.strip_prefix("ERR").unwrap_or(reply).trim();
+        KillResult {
+            success: false,
+            message: format!("Elevated termination failed: {}", reason),
+            required_elevation: true,
+        }
+    }
+}
+
+/// Quote a string for a POSIX shell
+#[cfg(any(target_os = "macos", test))]
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// Quote a string as an AppleScript string literal
+#[cfg(any(target_os = "macos", test))]
+fn applescript_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ready() {
+        assert_eq!(parse_ready("READY 4321\n"), Some(4321));
+        assert_eq!(parse_ready("OK"), None);
+        assert_eq!(parse_ready("READY"), None);
+    }
+
+    #[test]
+    fn test_parse_reply() {
+        let ok = parse_reply(42, "OK\n");
+        assert!(ok.success);
+        assert!(ok.required_elevation);
+
+        let failed = parse_reply(42, "ERR kill: (42) - No such process");
+        assert!(!failed.success);
+        assert_eq!(failed.message, "Elevated termination failed: kill: (42) - No such process");
+    }
+
+    #[test]
+    fn test_quoting() {
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+        assert_eq!(applescript_string(r#"say "hi" \ bye"#), r#""say \"hi\" \\ bye""#);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_helper_script() {
+        use std::io::Write;
+        use std::process::{Command, Stdio};
+
+        let mut child = Command::new("/bin/sh")
+            .args(["-c", HELPER_SCRIPT, "sh"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let mut sleeper = Command::new("sleep").arg("30").spawn().unwrap();
+
+        let mut stdin = child.stdin.take().unwrap();
+        writeln!(stdin, "TERM {}", sleeper.id()).unwrap();
+        writeln!(stdin, "HUP {}", sleeper.id()).unwrap();
+        writeln!(stdin, "KILL 12;reboot").unwrap();
+        drop(stdin);
+
+        let output = child.wait_with_output().unwrap();
+        let lines: Vec<String> =
+            String::from_utf8_lossy(&output.stdout).lines().map(str::to_string).collect();
+        assert!(parse_ready(&lines[0]).is_some());
+        assert_eq!(&lines[1..], ["OK", "ERR invalid signal", "ERR invalid pid"]);
+
+        let _ = sleeper.kill();
+        sleeper.wait().unwrap();
+    }
+}
@@ -1,6 +1,20 @@
 // Surgery module - Process termination and safety controls
+pub mod affinity;
+pub mod backtrace;
+pub mod connection;
+pub mod elevated_session;
+pub mod oom;
+pub mod priority;
+pub mod risk;
 pub mod safety;
 pub mod terminator;
 
+pub use affinity::*;
+pub use backtrace::*;
+pub use connection::*;
+pub use elevated_session::*;
+pub use oom::*;
+pub use priority::*;
+pub use risk::*;
 pub use safety::*;
 pub use terminator::*;
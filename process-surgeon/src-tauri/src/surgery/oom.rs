@@ -0,0 +1,112 @@
+// OOM Module - Steering the Linux OOM killer instead of killing processes
+use std::io;
+
+/// Lowest `oom_score_adj`: the OOM killer never picks the process
+pub const MIN_OOM_SCORE_ADJ: i32 = -1000;
+
+/// Highest `oom_score_adj`: the process is the first one reaped under memory pressure
+pub const MAX_OOM_SCORE_ADJ: i32 = 1000;
+
+/// Set a process's `oom_score_adj`, returning the previous and the new value
+///
+/// `score` must be within -1000..=1000. Unprivileged users may raise the value of
+/// their own processes; lowering it below what it was set to needs CAP_SYS_RESOURCE
+/// and fails with `PermissionDenied`.
+#[cfg(target_os = "linux")]
+pub fn set_oom_score_adj(pid: u32, score: i32) -> io::Result<(i32, i32)> {
+    if !(MIN_OOM_SCORE_ADJ..=MAX_OOM_SCORE_ADJ).contains(&score) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "OOM score adjustment {} is outside {}..={}",
+                score, MIN_OOM_SCORE_ADJ, MAX_OOM_SCORE_ADJ
+            ),
+        ));
+    }
+
+    let previous = read_oom_score_adj(pid)?;
+    std::fs::write(oom_score_adj_path(pid), score.to_string())
+        .map_err(|e| oom_error(pid, e))?;
+    Ok((previous, read_oom_score_adj(pid)?))
+}
+
+/// The OOM killer's per-process knob only exists on Linux
+#[cfg(not(target_os = "linux"))]
+pub fn set_oom_score_adj(_pid: u32, _score: i32) -> io::Result<(i32, i32)> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "Adjusting the OOM score is only supported on Linux",
+    ))
+}
+
+/// Current `oom_score_adj` of a process
+#[cfg(target_os = "linux")]
+pub fn read_oom_score_adj(pid: u32) -> io::Result<i32> {
+    let contents =
+        std::fs::read_to_string(oom_score_adj_path(pid)).map_err(|e| oom_error(pid, e))?;
+    parse_oom_score_adj(&contents)
+}
+
+#[cfg(target_os = "linux")]
+fn oom_score_adj_path(pid: u32) -> String {
+    format!("/proc/{}/oom_score_adj", pid)
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn parse_oom_score_adj(contents: &str) -> io::Result<i32> {
+    contents.trim().parse().map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Unexpected oom_score_adj contents: {:?}", contents.trim()),
+        )
+    })
+}
+
+/// Name the process in errors from `/proc/<pid>/oom_score_adj`
+#[cfg(target_os = "linux")]
+fn oom_error(pid: u32, error: io::Error) -> io::Error {
+    match error.kind() {
+        io::ErrorKind::NotFound => {
+            io::Error::new(io::ErrorKind::NotFound, format!("Process {} not found", pid))
+        }
+        io::ErrorKind::PermissionDenied => io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!(
+                "Not allowed to change the OOM score of process {}; lowering it needs root \
+                 or CAP_SYS_RESOURCE",
+                pid
+            ),
+        ),
+        _ => error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_oom_score_adj() {
+        assert_eq!(parse_oom_score_adj("0\n").unwrap(), 0);
+        assert_eq!(parse_oom_score_adj("-1000\n").unwrap(), -1000);
+        assert_eq!(
+            parse_oom_score_adj("abc").unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_set_oom_score_adj_rejects_out_of_range() {
+        let own = std::process::id();
+        assert!(read_oom_score_adj(own).is_ok());
+        assert_eq!(
+            set_oom_score_adj(own, 1001).unwrap_err().kind(),
+            io::ErrorKind::InvalidInput
+        );
+        assert_eq!(
+            read_oom_score_adj(u32::MAX).unwrap_err().kind(),
+            io::ErrorKind::NotFound
+        );
+    }
+}
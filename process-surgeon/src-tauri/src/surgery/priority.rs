@@ -0,0 +1,145 @@
+// Priority Module - Deprioritizing processes instead of killing them
+use std::io;
+
+/// Lowest niceness (highest priority) accepted on Unix
+pub const MIN_NICENESS: i32 = -20;
+
+/// Highest niceness (lowest priority) accepted on Unix
+pub const MAX_NICENESS: i32 = 19;
+
+/// Set a process's scheduling priority, returning the effective niceness
+///
+/// `niceness` follows the Unix convention and is clamped to -20..=19. Unprivileged
+/// users may only raise niceness of their own processes; going below the current
+/// value (and in particular below 0) needs root or CAP_SYS_NICE, and fails with
+/// `PermissionDenied`. Setpriority also covers I/O priority on Linux, which derives
+/// the default I/O class from the niceness.
+#[cfg(unix)]
+pub fn set_process_priority(pid: u32, niceness: i32) -> io::Result<i32> {
+    let niceness = niceness.clamp(MIN_NICENESS, MAX_NICENESS);
+
+    // SAFETY: setpriority only reads its integer arguments
+    let result = unsafe { libc::setpriority(libc::PRIO_PROCESS, pid as libc::id_t, niceness) };
+    if result != 0 {
+        return Err(priority_error(pid));
+    }
+
+    // getpriority can legitimately return -1, so errno has to be cleared and checked
+    // SAFETY: errno is thread-local and getpriority only reads its integer arguments
+    let effective = unsafe {
+        clear_errno();
+        libc::getpriority(libc::PRIO_PROCESS, pid as libc::id_t)
+    };
+    if effective == -1 && io::Error::last_os_error().raw_os_error().unwrap_or(0) != 0 {
+        return Err(priority_error(pid));
+    }
+
+    Ok(effective)
+}
+
+/// Turn the current errno into an error naming the process
+#[cfg(unix)]
+fn priority_error(pid: u32) -> io::Error {
+    let error = io::Error::last_os_error();
+    match error.raw_os_error() {
+        Some(libc::ESRCH) => {
+            io::Error::new(io::ErrorKind::NotFound, format!("Process {} not found", pid))
+        }
+        Some(libc::EPERM) | Some(libc::EACCES) => io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!(
+                "Not allowed to change the priority of process {}; raising priority (lowering \
+                 niceness) needs root or CAP_SYS_NICE",
+                pid
+            ),
+        ),
+        _ => error,
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+unsafe fn clear_errno() {
+    *libc::__errno_location() = 0;
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+unsafe fn clear_errno() {
+    *libc::__error() = 0;
+}
+
+/// Set a process's priority class from a Unix-style niceness, returning the effective one
+///
+/// Windows has no niceness, so the value is bucketed: <= -15 high, -14..=-5 above
+/// normal, -4..=4 normal, 5..=14 below normal, >= 15 idle. The realtime class is never
+/// requested. The returned niceness is a representative value for the resulting class.
+#[cfg(windows)]
+pub fn set_process_priority(pid: u32, niceness: i32) -> io::Result<i32> {
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Threading::{
+        GetPriorityClass, OpenProcess, SetPriorityClass, ABOVE_NORMAL_PRIORITY_CLASS,
+        BELOW_NORMAL_PRIORITY_CLASS, HIGH_PRIORITY_CLASS, IDLE_PRIORITY_CLASS,
+        NORMAL_PRIORITY_CLASS, PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_SET_INFORMATION,
+        REALTIME_PRIORITY_CLASS,
+    };
+
+    let class = match niceness {
+        n if n <= -15 => HIGH_PRIORITY_CLASS,
+        -14..=-5 => ABOVE_NORMAL_PRIORITY_CLASS,
+        -4..=4 => NORMAL_PRIORITY_CLASS,
+        5..=14 => BELOW_NORMAL_PRIORITY_CLASS,
+        _ => IDLE_PRIORITY_CLASS,
+    };
+
+    // SAFETY: the handle is checked by OpenProcess and closed before returning
+    unsafe {
+        let handle = OpenProcess(
+            PROCESS_SET_INFORMATION | PROCESS_QUERY_LIMITED_INFORMATION,
+            false,
+            pid,
+        )
+        .map_err(io::Error::from)?;
+        let result = SetPriorityClass(handle, class);
+        let effective = GetPriorityClass(handle);
+        let _ = CloseHandle(handle);
+        result.map_err(io::Error::from)?;
+
+        Ok(match effective {
+            c if c == REALTIME_PRIORITY_CLASS.0 => MIN_NICENESS,
+            c if c == HIGH_PRIORITY_CLASS.0 => -15,
+            c if c == ABOVE_NORMAL_PRIORITY_CLASS.0 => -5,
+            c if c == BELOW_NORMAL_PRIORITY_CLASS.0 => 10,
+            c if c == IDLE_PRIORITY_CLASS.0 => MAX_NICENESS,
+            _ => 0,
+        })
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_process_priority() {
+        let mut child = std::process::Command::new("sleep").arg("30").spawn().unwrap();
+        let pid = child.id();
+
+        assert_eq!(set_process_priority(pid, 10).unwrap(), 10);
+        // Out-of-range values are clamped
+        assert_eq!(set_process_priority(pid, 40).unwrap(), MAX_NICENESS);
+
+        // SAFETY: geteuid has no preconditions
+        if unsafe { libc::geteuid() } != 0 {
+            let error = set_process_priority(pid, -5).unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::PermissionDenied);
+        }
+
+        child.kill().unwrap();
+        child.wait().unwrap();
+    }
+
+    #[test]
+    fn test_set_process_priority_missing_process() {
+        let error = set_process_priority(u32::MAX - 1, 5).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+    }
+}
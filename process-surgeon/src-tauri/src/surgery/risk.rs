@@ -0,0 +1,124 @@
+// Risk Module - Advisory risk assessment for high-impact terminations
+use crate::models::{KillRisk, RiskLevel};
+
+/// Child process count at which killing the parent is considered high risk
+const HIGH_RISK_CHILD_COUNT: usize = 10;
+
+/// Ports below this are privileged on Unix systems
+const PRIVILEGED_PORT_LIMIT: u16 = 1024;
+
+/// Facts about a process gathered before assessing kill risk
+#[derive(Debug, Clone, Default)]
+pub struct RiskFactors {
+    pub user: String,
+    pub owned_by_current_user: bool,
+    /// Direct children only; grandchildren are not counted
+    pub child_count: usize,
+    pub listening_ports: Vec<u16>,
+}
+
+/// Whether a user name is the superuser / system account
+pub fn is_superuser(user: &str) -> bool {
+    matches!(
+        user.to_lowercase().as_str(),
+        "root" | "system" | "nt authority\\system"
+    )
+}
+
+/// Assess how disruptive terminating a process is likely to be
+///
+/// This is advisory only: it never blocks a kill, it just tells the caller
+/// when an extra confirmation is warranted.
+pub fn assess_risk(pid: u32, factors: &RiskFactors) -> KillRisk {
+    let mut level = RiskLevel::Low;
+    let mut reasons = Vec::new();
+
+    if is_superuser(&factors.user) {
+        level = level.max(RiskLevel::High);
+        reasons.push(format!("runs as {}", factors.user));
+    } else if !factors.owned_by_current_user {
+        level = level.max(RiskLevel::Medium);
+        reasons.push(format!("owned by another user ({})", factors.user));
+    }
+
+    if factors.child_count >= HIGH_RISK_CHILD_COUNT {
+        level = level.max(RiskLevel::High);
+        reasons.push(format!("has {} child processes", factors.child_count));
+    } else if factors.child_count > 0 {
+        level = level.max(RiskLevel::Medium);
+        reasons.push(format!("has {} child processes", factors.child_count));
+    }
+
+    let mut privileged: Vec<u16> = factors
+        .listening_ports
+        .iter()
+        .copied()
+        .filter(|&port| port < PRIVILEGED_PORT_LIMIT)
+        .collect();
+    privileged.sort();
+    privileged.dedup();
+
+    for port in privileged {
+        level = level.max(RiskLevel::Medium);
+        reasons.push(format!("listens on privileged port {}", port));
+    }
+
+    KillRisk {
+        pid,
+        level,
+        reasons,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_low_risk() {
+        let factors = RiskFactors {
+            user: "dev".to_string(),
+            owned_by_current_user: true,
+            child_count: 0,
+            listening_ports: vec![3000],
+        };
+
+        let risk = assess_risk(1234, &factors);
+        assert_eq!(risk.level, RiskLevel::Low);
+        assert!(risk.reasons.is_empty());
+    }
+
+    #[test]
+    fn test_root_is_high_risk() {
+        let factors = RiskFactors {
+            user: "root".to_string(),
+            owned_by_current_user: false,
+            child_count: 2,
+            listening_ports: vec![80, 443, 80],
+        };
+
+        let risk = assess_risk(1234, &factors);
+        assert_eq!(risk.level, RiskLevel::High);
+        assert_eq!(
+            risk.reasons,
+            vec![
+                "runs as root",
+                "has 2 child processes",
+                "listens on privileged port 80",
+                "listens on privileged port 443",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_many_children_is_high_risk() {
+        let factors = RiskFactors {
+            user: "dev".to_string(),
+            owned_by_current_user: true,
+            child_count: 12,
+            listening_ports: vec![],
+        };
+
+        assert_eq!(assess_risk(1234, &factors).level, RiskLevel::High);
+    }
+}
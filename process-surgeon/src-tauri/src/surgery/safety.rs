@@ -1,9 +1,14 @@
 // Safety Module - "Do No Harm" registry and protection logic
-use std::collections::HashSet;
+use crate::models::{ProtectionExplanation, ProtectionKind};
+use std::collections::{HashMap, HashSet};
+use std::ops::RangeInclusive;
+use std::sync::{Mutex, RwLock};
 use once_cell::sync::Lazy;
 
 /// Protected process names that should never be terminated
 /// These are critical system processes that could cause system instability if killed
+///
+/// Entries may be glob patterns: `*` matches any run of characters and `?` a single one.
 
 #[cfg(target_os = "windows")]
 static PROTECTED_PROCESSES: Lazy<HashSet<&'static str>> = Lazy::new(|| {
@@ -32,18 +37,16 @@ static PROTECTED_PROCESSES: Lazy<HashSet<&'static str>> = Lazy::new(|| {
     set.insert("init");
     set.insert("systemd");
     set.insert("kthreadd");
-    set.insert("ksoftirqd");
-    set.insert("kworker");
-    set.insert("rcu_sched");
-    set.insert("migration");
-    set.insert("watchdog");
-    set.insert("cpuhp");
+    set.insert("ksoftirqd*");
+    set.insert("kworker*");
+    set.insert("rcu_*");
+    set.insert("migration*");
+    set.insert("watchdog*");
+    set.insert("cpuhp*");
     set.insert("netns");
     set.insert("dbus-daemon");
     set.insert("NetworkManager");
-    set.insert("systemd-journald");
-    set.insert("systemd-logind");
-    set.insert("systemd-udevd");
+    set.insert("systemd-*");
     // Self-protection
     set.insert("process-surgeon");
     set.insert("ps-surgeon-proxy");
@@ -78,6 +81,149 @@ static PROTECTED_PROCESSES: Lazy<HashSet<&'static str>> = Lazy::new(|| {
     set
 });
 
+/// Protected names compiled once: exact names in a set, globs checked in order
+static PROTECTED_NAMES: Lazy<NameMatcher> =
+    Lazy::new(|| NameMatcher::new(PROTECTED_PROCESSES.iter().copied()));
+
+/// Case-insensitive process name matcher supporting `*` and `?` globs
+///
+/// Names and rules are compared without a trailing `.exe`, so Windows rules match
+/// with or without the extension.
+pub struct NameMatcher {
+    exact: HashSet<String>,
+    globs: Vec<String>,
+}
+
+impl NameMatcher {
+    pub fn new<'a>(rules: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut exact = HashSet::new();
+        let mut globs = Vec::new();
+
+        for rule in rules {
+            let rule = normalize_name(rule);
+            if rule.contains(['*', '?']) {
+                globs.push(rule);
+            } else {
+                exact.insert(rule);
+            }
+        }
+
+        Self { exact, globs }
+    }
+
+    pub fn matches(&self, process_name: &str) -> bool {
+        self.matching_rule(process_name).is_some()
+    }
+
+    /// The rule `process_name` matches, normalized; exact names win over globs
+    pub fn matching_rule(&self, process_name: &str) -> Option<&str> {
+        let name = normalize_name(process_name);
+        if let Some(rule) = self.exact.get(&name) {
+            return Some(rule.as_str());
+        }
+        self.globs
+            .iter()
+            .find(|glob| glob_match(glob, &name))
+            .map(String::as_str)
+    }
+}
+
+/// Fewest literal (non-wildcard) characters a kill-by-name glob or substring may have
+pub const MIN_PATTERN_LITERALS: usize = 3;
+
+/// User-supplied pattern selecting processes to kill by name
+///
+/// Patterns containing `*` or `?` are anchored globs; anything else matches as a
+/// case-insensitive substring, or as the whole name when it is shorter than
+/// `MIN_PATTERN_LITERALS`, so `sh` stops at `sh` rather than every `ssh` and `bash`.
+/// All ignore a trailing `.exe`.
+#[derive(Debug, Clone)]
+pub struct NamePattern {
+    pattern: String,
+    kind: PatternKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PatternKind {
+    Exact,
+    Substring,
+    Glob,
+}
+
+impl NamePattern {
+    /// Parse a pattern, rejecting ones so broad they would match almost everything
+    pub fn parse(pattern: &str) -> anyhow::Result<Self> {
+        let pattern = normalize_name(pattern.trim());
+        let literals = pattern.chars().filter(|c| !matches!(c, '*' | '?')).count();
+        let is_glob = pattern.contains(['*', '?']);
+        if literals == 0 || (is_glob && literals < MIN_PATTERN_LITERALS) {
+            anyhow::bail!(
+                "Pattern '{}' is too broad; use at least {} characters besides * and ?, \
+                 or a full process name",
+                pattern,
+                MIN_PATTERN_LITERALS
+            );
+        }
+
+        let kind = if is_glob {
+            PatternKind::Glob
+        } else if literals < MIN_PATTERN_LITERALS {
+            PatternKind::Exact
+        } else {
+            PatternKind::Substring
+        };
+        Ok(Self { pattern, kind })
+    }
+
+    pub fn matches(&self, process_name: &str) -> bool {
+        let name = normalize_name(process_name);
+        match self.kind {
+            PatternKind::Exact => name == self.pattern,
+            PatternKind::Substring => name.contains(&self.pattern),
+            PatternKind::Glob => glob_match(&self.pattern, &name),
+        }
+    }
+}
+
+/// Lowercase a process name and drop a trailing `.exe`
+fn normalize_name(name: &str) -> String {
+    let lower = name.to_lowercase();
+    lower.strip_suffix(".exe").unwrap_or(&lower).to_string()
+}
+
+/// Match `text` against a glob anchored at both ends
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // Position of the last `*` and the text index it is currently absorbing up to
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                // Let the last `*` swallow one more character and retry
+                Some((star, absorbed)) => {
+                    backtrack = Some((star, absorbed + 1));
+                    p = star + 1;
+                    t = absorbed + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
 /// Protected PIDs that should never be terminated
 static PROTECTED_PIDS: Lazy<HashSet<u32>> = Lazy::new(|| {
     let mut set = HashSet::new();
@@ -86,12 +232,135 @@ static PROTECTED_PIDS: Lazy<HashSet<u32>> = Lazy::new(|| {
     set
 });
 
+/// Highest PID in the default protected range: kthreadd (2) on Linux
+#[cfg(target_os = "linux")]
+pub const DEFAULT_PROTECTED_PID_CEILING: u32 = 2;
+
+/// Highest PID in the default protected range: System (4) on Windows
+#[cfg(target_os = "windows")]
+pub const DEFAULT_PROTECTED_PID_CEILING: u32 = 4;
+
+/// Highest PID in the default protected range: launchd (1) elsewhere
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+pub const DEFAULT_PROTECTED_PID_CEILING: u32 = 1;
+
+/// PID ranges refused on top of `PROTECTED_PIDS`, which always stay protected
+static PROTECTED_PID_RANGES: Lazy<RwLock<Vec<RangeInclusive<u32>>>> =
+    Lazy::new(|| RwLock::new(vec![0..=DEFAULT_PROTECTED_PID_CEILING]));
+
+/// Held by tests that change or depend on the protected PID ranges and ports
+#[cfg(test)]
+pub(crate) static PROTECTION_SETTINGS_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Replace the protected PID ranges; PIDs 0 and 1 stay protected regardless
+pub fn set_protected_pid_ranges(ranges: impl IntoIterator<Item = RangeInclusive<u32>>) {
+    let mut protected = PROTECTED_PID_RANGES.write().unwrap_or_else(|e| e.into_inner());
+    *protected = ranges.into_iter().collect();
+}
+
+/// Protect every PID up to and including `ceiling`, replacing any other ranges
+pub fn set_protected_pid_ceiling(ceiling: u32) {
+    set_protected_pid_ranges([0..=ceiling]);
+}
+
+/// Currently protected PID ranges
+pub fn protected_pid_ranges() -> Vec<RangeInclusive<u32>> {
+    PROTECTED_PID_RANGES
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+/// Whether `pid` is a fixed protected PID or falls in a protected range
+fn is_protected_pid(pid: u32) -> bool {
+    PROTECTED_PIDS.contains(&pid) || pid_in_ranges(pid, &protected_pid_ranges())
+}
+
+fn pid_in_ranges(pid: u32, ranges: &[RangeInclusive<u32>]) -> bool {
+    ranges.iter().any(|range| range.contains(&pid))
+}
+
+/// The fixed PID or `start-end` range that protects `pid`
+fn protected_pid_rule(pid: u32) -> Option<String> {
+    if PROTECTED_PIDS.contains(&pid) {
+        return Some(pid.to_string());
+    }
+    protected_pid_ranges()
+        .into_iter()
+        .find(|range| range.contains(&pid))
+        .map(|range| format!("{}-{}", range.start(), range.end()))
+}
+
+/// Ports protected unless configured otherwise: SSH, DNS and CUPS
+///
+/// Losing these can lock a user out or break name resolution and printing.
+pub const DEFAULT_PROTECTED_PORTS: [u16; 3] = [22, 53, 631];
+
+/// Ports whose holders are refused a graceful kill
+static PROTECTED_PORTS: Lazy<RwLock<HashSet<u16>>> =
+    Lazy::new(|| RwLock::new(HashSet::from(DEFAULT_PROTECTED_PORTS)));
+
+/// Replace the set of protected ports
+pub fn set_protected_ports(ports: impl IntoIterator<Item = u16>) {
+    let mut protected = PROTECTED_PORTS.write().unwrap_or_else(|e| e.into_inner());
+    *protected = ports.into_iter().collect();
+}
+
+/// Currently protected ports, sorted
+pub fn protected_ports() -> Vec<u16> {
+    let protected = PROTECTED_PORTS.read().unwrap_or_else(|e| e.into_inner());
+    let mut ports: Vec<u16> = protected.iter().copied().collect();
+    ports.sort_unstable();
+    ports
+}
+
+/// PIDs of the elevated helpers running on our behalf, with how many guards hold each
+///
+/// Counted because a session re-registers its helper once it is ready, and the PID can
+/// be the same when the launcher execs the shell in place.
+static ELEVATED_HELPER_PIDS: Lazy<Mutex<HashMap<u32, usize>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Marks a spawned elevated helper as protected until dropped
+pub struct ElevatedHelperGuard {
+    pid: u32,
+}
+
+impl ElevatedHelperGuard {
+    pub fn register(pid: u32) -> Self {
+        let mut helpers = ELEVATED_HELPER_PIDS.lock().unwrap_or_else(|e| e.into_inner());
+        *helpers.entry(pid).or_insert(0) += 1;
+        Self { pid }
+    }
+}
+
+impl Drop for ElevatedHelperGuard {
+    fn drop(&mut self) {
+        let mut helpers = ELEVATED_HELPER_PIDS.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(count) = helpers.get_mut(&self.pid) {
+            *count -= 1;
+            if *count == 0 {
+                helpers.remove(&self.pid);
+            }
+        }
+    }
+}
+
+/// Whether `pid` is an elevated helper that is currently running
+pub fn is_elevated_helper(pid: u32) -> bool {
+    ELEVATED_HELPER_PIDS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .contains_key(&pid)
+}
+
 /// Safety check result
 #[derive(Debug, Clone)]
 pub enum SafetyCheckResult {
     Safe,
     ProtectedProcess(String),
     ProtectedPid(u32),
+    ProtectedPort(u16),
     SelfTermination,
 }
 
@@ -99,33 +368,84 @@ impl SafetyCheckResult {
     pub fn is_safe(&self) -> bool {
         matches!(self, SafetyCheckResult::Safe)
     }
+
+    /// Human-readable reason the process is protected, or `None` when safe
+    pub fn reason(&self) -> Option<String> {
+        match self {
+            SafetyCheckResult::Safe => None,
+            SafetyCheckResult::ProtectedProcess(name) => {
+                Some(format!("{} is a protected system process", name))
+            }
+            SafetyCheckResult::ProtectedPid(pid) => Some(format!("PID {} is protected", pid)),
+            SafetyCheckResult::ProtectedPort(port) => {
+                Some(format!("Holds protected port {}", port))
+            }
+            SafetyCheckResult::SelfTermination => {
+                Some("This is Process Surgeon itself".to_string())
+            }
+        }
+    }
+
+    /// The result for `pid` with the kind of rule that matched and the rule itself
+    pub fn explanation(&self, pid: u32) -> ProtectionExplanation {
+        let (kind, rule) = match self {
+            SafetyCheckResult::Safe => (None, None),
+            SafetyCheckResult::ProtectedProcess(name) => (
+                Some(ProtectionKind::Name),
+                PROTECTED_NAMES.matching_rule(name).map(str::to_string),
+            ),
+            SafetyCheckResult::ProtectedPid(pid) => {
+                (Some(ProtectionKind::Pid), protected_pid_rule(*pid))
+            }
+            SafetyCheckResult::ProtectedPort(port) => {
+                (Some(ProtectionKind::Port), Some(port.to_string()))
+            }
+            SafetyCheckResult::SelfTermination => (Some(ProtectionKind::SelfProcess), None),
+        };
+
+        ProtectionExplanation {
+            pid,
+            protected: !self.is_safe(),
+            kind,
+            rule,
+            reason: self.reason(),
+        }
+    }
 }
 
 /// Check if a process is protected based on PID and name
 pub fn check_process_safety(pid: u32, process_name: &str) -> SafetyCheckResult {
-    // Check for self-termination
-    let current_pid = std::process::id();
-    if pid == current_pid {
+    check_process_safety_with_ports(pid, process_name, &[])
+}
+
+/// Check if a process is protected based on PID, name and the ports it holds
+///
+/// Protected ports are a softer rule than the name and PID lists: callers skip the
+/// port check (pass no ports) when the user explicitly forces a kill.
+pub fn check_process_safety_with_ports(
+    pid: u32,
+    process_name: &str,
+    ports: &[u16],
+) -> SafetyCheckResult {
+    // Check for self-termination, including our own elevated helper
+    if is_self_process(pid) {
         return SafetyCheckResult::SelfTermination;
     }
 
-    // Check protected PIDs
-    if PROTECTED_PIDS.contains(&pid) {
+    // Check protected PIDs and PID ranges
+    if is_protected_pid(pid) {
         return SafetyCheckResult::ProtectedPid(pid);
     }
 
-    // Normalize process name for comparison
-    let name_lower = process_name.to_lowercase();
-    let name_without_ext = name_lower.trim_end_matches(".exe");
+    // Check against protected process names and patterns
+    if PROTECTED_NAMES.matches(process_name) {
+        return SafetyCheckResult::ProtectedProcess(process_name.to_string());
+    }
 
-    // Check against protected process names
-    for protected in PROTECTED_PROCESSES.iter() {
-        let protected_lower = protected.to_lowercase();
-        let protected_without_ext = protected_lower.trim_end_matches(".exe");
-        
-        if name_without_ext == protected_without_ext || name_lower == protected_lower {
-            return SafetyCheckResult::ProtectedProcess(process_name.to_string());
-        }
+    // Check ports held by the process
+    let protected_ports = PROTECTED_PORTS.read().unwrap_or_else(|e| e.into_inner());
+    if let Some(&port) = ports.iter().find(|port| protected_ports.contains(port)) {
+        return SafetyCheckResult::ProtectedPort(port);
     }
 
     SafetyCheckResult::Safe
@@ -136,9 +456,9 @@ pub fn is_pid_one(pid: u32) -> bool {
     pid == 1
 }
 
-/// Check if the process is the application itself
+/// Check if the process is the application itself or its running elevated helper
 pub fn is_self_process(pid: u32) -> bool {
-    pid == std::process::id()
+    pid == std::process::id() || is_elevated_helper(pid)
 }
 
 #[cfg(test)]
@@ -151,6 +471,25 @@ mod tests {
         assert!(!check_process_safety(1, "init").is_safe());
     }
 
+    #[test]
+    fn test_protected_pid_ranges() {
+        let _settings = PROTECTION_SETTINGS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        assert_eq!(protected_pid_ranges(), vec![0..=DEFAULT_PROTECTED_PID_CEILING]);
+        assert!(!check_process_safety(DEFAULT_PROTECTED_PID_CEILING, "worker").is_safe());
+        assert!(matches!(
+            check_process_safety(DEFAULT_PROTECTED_PID_CEILING, "worker"),
+            SafetyCheckResult::ProtectedPid(_)
+        ));
+        assert!(check_process_safety(DEFAULT_PROTECTED_PID_CEILING + 1, "worker").is_safe());
+        assert!(check_process_safety(4242, "worker").is_safe());
+
+        let ranges = [0..=2, 300..=399];
+        assert!(pid_in_ranges(2, &ranges));
+        assert!(pid_in_ranges(300, &ranges));
+        assert!(!pid_in_ranges(3, &ranges));
+        assert!(!pid_in_ranges(400, &ranges));
+    }
+
     #[test]
     fn test_protected_process_names() {
         #[cfg(target_os = "macos")]
@@ -164,6 +503,9 @@ mod tests {
         {
             assert!(!check_process_safety(100, "systemd").is_safe());
             assert!(!check_process_safety(100, "init").is_safe());
+            assert!(!check_process_safety(100, "kworker/3:1").is_safe());
+            assert!(!check_process_safety(100, "systemd-oomd").is_safe());
+            assert!(check_process_safety(100, "my-systemd-helper").is_safe());
         }
 
         #[cfg(target_os = "windows")]
@@ -173,6 +515,56 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_name_matcher_globs() {
+        let matcher = NameMatcher::new(["kworker*", "systemd-*", "svc?.exe", "Exact"]);
+
+        assert!(matcher.matches("kworker/3:1"));
+        assert!(matcher.matches("kworker"));
+        assert!(matcher.matches("systemd-resolved"));
+        // Patterns are anchored, so a mid-name occurrence doesn't count
+        assert!(!matcher.matches("my-systemd-helper"));
+        assert!(!matcher.matches("systemd"));
+
+        assert!(matcher.matches("SVC1.exe"));
+        assert!(matcher.matches("svc2"));
+        assert!(!matcher.matches("svc10"));
+        assert!(matcher.matches("exact.exe"));
+    }
+
+    #[test]
+    fn test_name_pattern() {
+        let substring = NamePattern::parse("Node").unwrap();
+        assert!(substring.matches("node"));
+        assert!(substring.matches("nodemon"));
+        assert!(substring.matches("NODE.EXE"));
+        assert!(!substring.matches("deno"));
+
+        let glob = NamePattern::parse("node*").unwrap();
+        assert!(glob.matches("nodemon"));
+        assert!(!glob.matches("my-node"));
+
+        // Short patterns only match the whole name
+        let short = NamePattern::parse("sh").unwrap();
+        assert!(short.matches("sh"));
+        assert!(short.matches("SH.exe"));
+        assert!(!short.matches("ssh"));
+        assert!(!short.matches("bash"));
+
+        // Overly broad patterns are refused
+        for broad in ["", "  ", "*", "**", "?*", "a*", "*.exe", "go*", "*sh"] {
+            assert!(NamePattern::parse(broad).is_err(), "{:?} was accepted", broad);
+        }
+    }
+
+    #[test]
+    fn test_glob_match_backtracking() {
+        assert!(glob_match("a*b*c", "axxbyybc"));
+        assert!(!glob_match("a*b*c", "axxbyyb"));
+        assert!(glob_match("*", ""));
+        assert!(!glob_match("?", ""));
+    }
+
     #[test]
     fn test_safe_process() {
         assert!(check_process_safety(12345, "node").is_safe());
@@ -185,4 +577,74 @@ mod tests {
         let current_pid = std::process::id();
         assert!(!check_process_safety(current_pid, "test").is_safe());
     }
+
+    #[test]
+    fn test_protected_ports() {
+        let _settings = PROTECTION_SETTINGS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        assert!(check_process_safety_with_ports(12345, "sshd-session", &[8080]).is_safe());
+        assert!(matches!(
+            check_process_safety_with_ports(12345, "cupsd", &[8080, 631]),
+            SafetyCheckResult::ProtectedPort(631)
+        ));
+        // The back-compat wrapper considers no ports
+        assert!(check_process_safety(12345, "cupsd").is_safe());
+        assert_eq!(protected_ports(), vec![22, 53, 631]);
+    }
+
+    #[test]
+    fn test_safety_reason() {
+        assert_eq!(check_process_safety(12345, "node").reason(), None);
+        assert_eq!(
+            check_process_safety(1, "init").reason().as_deref(),
+            Some("PID 1 is protected")
+        );
+    }
+
+    #[test]
+    fn test_protection_explanation() {
+        let safe = check_process_safety(12345, "node").explanation(12345);
+        assert!(!safe.protected);
+        assert_eq!(safe.kind, None);
+        assert_eq!(safe.rule, None);
+
+        let pid_one = check_process_safety(1, "init").explanation(1);
+        assert_eq!(pid_one.kind, Some(ProtectionKind::Pid));
+        assert_eq!(pid_one.rule.as_deref(), Some("1"));
+
+        let port = check_process_safety_with_ports(12345, "cupsd", &[631]).explanation(12345);
+        assert_eq!(port.kind, Some(ProtectionKind::Port));
+        assert_eq!(port.rule.as_deref(), Some("631"));
+
+        let own = std::process::id();
+        let own = check_process_safety(own, "test").explanation(own);
+        assert_eq!(own.kind, Some(ProtectionKind::SelfProcess));
+
+        #[cfg(target_os = "linux")]
+        {
+            let glob = check_process_safety(100, "kworker/0:1").explanation(100);
+            assert_eq!(glob.kind, Some(ProtectionKind::Name));
+            assert_eq!(glob.rule.as_deref(), Some("kworker*"));
+        }
+    }
+
+    #[test]
+    fn test_elevated_helper_protected_while_running() {
+        let helper_pid = 424242;
+        {
+            let _guard = ElevatedHelperGuard::register(helper_pid);
+            assert!(!check_process_safety(helper_pid, "ps-surgeon-helper").is_safe());
+        }
+        assert!(check_process_safety(helper_pid, "ps-surgeon-helper").is_safe());
+    }
+
+    #[test]
+    fn test_elevated_helper_reregistered_under_same_pid() {
+        let helper_pid = 424243;
+        let first = ElevatedHelperGuard::register(helper_pid);
+        let second = ElevatedHelperGuard::register(helper_pid);
+        drop(first);
+        assert!(is_elevated_helper(helper_pid));
+        drop(second);
+        assert!(!is_elevated_helper(helper_pid));
+    }
 }
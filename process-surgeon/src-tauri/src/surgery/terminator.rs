@@ -1,8 +1,84 @@
 // Terminator Module - Process termination implementation
-use crate::models::KillResult;
-use crate::surgery::safety::{check_process_safety, SafetyCheckResult};
-use anyhow::Result;
-use sysinfo::{Pid, Signal, System};
+use crate::models::{
+    EscalationResult, GroupKillResult, KillResult, PortInfo, ReloadResult, TerminationSignal,
+};
+use crate::discovery::{live_pids, scan_listening_ports};
+use crate::surgery::elevated_session::kill_via_elevated_session;
+use crate::surgery::safety::{
+    check_process_safety, check_process_safety_with_ports, is_self_process, ElevatedHelperGuard,
+    SafetyCheckResult,
+};
+use anyhow::{anyhow, Result};
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use sysinfo::{
+    Pid, ProcessRefreshKind, ProcessesToUpdate, Signal, System, UpdateKind, SUPPORTED_SIGNALS,
+};
+use tokio::sync::RwLock;
+
+/// Everything needed to start a process again the way it was originally launched
+#[derive(Debug, Clone)]
+pub struct LaunchSpec {
+    pub name: String,
+    pub exe: Option<PathBuf>,
+    /// Arguments, excluding argv[0]
+    pub args: Vec<OsString>,
+    pub cwd: Option<PathBuf>,
+}
+
+impl LaunchSpec {
+    /// Spawn a new process from this spec, returning its PID
+    pub fn spawn(&self) -> Result<u32> {
+        use std::process::{Command, Stdio};
+
+        let exe = self
+            .exe
+            .as_ref()
+            .ok_or_else(|| anyhow!("Executable path for {} is unknown", self.name))?;
+
+        let mut command = Command::new(exe);
+        command
+            .args(&self.args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        if let Some(cwd) = &self.cwd {
+            command.current_dir(cwd);
+        }
+
+        let mut child = command.spawn()?;
+        let pid = child.id();
+
+        // Reap the child when it exits so it doesn't linger as a zombie
+        std::thread::spawn(move || {
+            let _ = child.wait();
+        });
+
+        Ok(pid)
+    }
+}
+
+/// A kill target whose name changed since the caller looked it up, most likely
+/// because the PID was recycled for an unrelated process
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PidReused {
+    pub pid: u32,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl std::fmt::Display for PidReused {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "PID reuse detected: PID {} is now {}, not {}",
+            self.pid, self.actual, self.expected
+        )
+    }
+}
+
+impl std::error::Error for PidReused {}
 
 /// Process terminator with safety checks
 pub struct ProcessTerminator {
@@ -31,8 +107,452 @@ impl ProcessTerminator {
     /// * `Ok(KillResult)` - Result of the termination attempt
     /// * `Err` - On system errors
     pub fn terminate(&mut self, pid: u32, force: bool) -> Result<KillResult> {
+        self.terminate_expecting(pid, force, None)
+    }
+
+    /// Terminate a process, first checking it still has the name the caller saw
+    ///
+    /// With `expected_name`, a PID now running under another name fails with
+    /// `PidReused` and no signal is sent. A graceful kill also fails when the PID's
+    /// listeners can't be scanned, as its protected ports can't be checked.
+    pub fn terminate_expecting(
+        &mut self,
+        pid: u32,
+        force: bool,
+        expected_name: Option<&str>,
+    ) -> Result<KillResult> {
+        self.refresh();
+        let ports = if force { Vec::new() } else { listening_ports_of(pid)? };
+        self.terminate_refreshed(pid, force, expected_name, &ports)
+    }
+
+    /// Refresh the process table and scan listeners once before killing several PIDs
+    ///
+    /// Pass the returned listeners to `terminate_in_batch` for each PID. Forced kills
+    /// don't check protected ports, so nothing is scanned for them; otherwise a failed
+    /// scan fails the batch, since protected ports couldn't be checked.
+    pub fn prepare_batch(&mut self, force: bool) -> Result<Vec<PortInfo>> {
+        self.refresh();
+        if force {
+            return Ok(Vec::new());
+        }
+        scan_listening_ports().map_err(protected_port_check_failed)
+    }
+
+    /// `terminate_expecting` against the table and listeners from `prepare_batch`
+    pub fn terminate_in_batch(
+        &mut self,
+        pid: u32,
+        force: bool,
+        expected_name: Option<&str>,
+        listeners: &[PortInfo],
+    ) -> Result<KillResult> {
+        let ports = if force { Vec::new() } else { ports_of(listeners, pid) };
+        self.terminate_refreshed(pid, force, expected_name, &ports)
+    }
+
+    /// Check and signal a PID using the current process table, without refreshing it
+    fn terminate_refreshed(
+        &mut self,
+        pid: u32,
+        force: bool,
+        expected_name: Option<&str>,
+        ports: &[u16],
+    ) -> Result<KillResult> {
+        let process_name = match self.check_target(pid, ports) {
+            Ok(name) => name,
+            Err(refusal) => return Ok(refusal),
+        };
+        if let Some(expected) = expected_name {
+            if process_name != expected {
+                return Err(PidReused {
+                    pid,
+                    expected: expected.to_string(),
+                    actual: process_name,
+                }
+                .into());
+            }
+        }
+
+        // Attempt termination
+        let signal = if force { Signal::Kill } else { Signal::Term };
+        let killed = self
+            .system
+            .process(Pid::from_u32(pid))
+            .map(|process| process.kill_with(signal).unwrap_or(false))
+            .unwrap_or(false);
+
+        if killed {
+            Ok(KillResult {
+                success: true,
+                message: format!(
+                    "Process {} ({}) terminated successfully",
+                    pid, process_name
+                ),
+                required_elevation: false,
+            })
+        } else {
+            // Kill failed - might need elevation
+            Ok(KillResult {
+                success: false,
+                message: format!(
+                    "Failed to terminate process {} ({}). May require elevated privileges.",
+                    pid, process_name
+                ),
+                required_elevation: true,
+            })
+        }
+    }
+
+    /// Pause a process with SIGSTOP, applying the same safety checks as `terminate`
+    pub fn suspend(&mut self, pid: u32) -> Result<KillResult> {
+        let process_name = match self.preflight(pid, false) {
+            Ok(name) => name,
+            Err(refusal) => return Ok(refusal),
+        };
+
+        Ok(self.send_control_signal(pid, &process_name, Signal::Stop, "suspend", "suspended"))
+    }
+
+    /// Continue a process paused with `suspend`
+    ///
+    /// Protected ports are not checked, so a paused port holder can always be resumed.
+    pub fn resume(&mut self, pid: u32) -> Result<KillResult> {
+        let process_name = match self.preflight(pid, true) {
+            Ok(name) => name,
+            Err(refusal) => return Ok(refusal),
+        };
+
+        Ok(self.send_control_signal(pid, &process_name, Signal::Continue, "resume", "resumed"))
+    }
+
+    /// Ask a daemon to reload its configuration with SIGHUP
+    ///
+    /// Protected ports are not checked since a reload keeps them bound. Processes that
+    /// don't handle SIGHUP exit on it, so `still_running` reports whether the process
+    /// was alive `RELOAD_SETTLE` after the signal. The terminator is only locked while
+    /// signalling, not while watching the process.
+    pub async fn reload(terminator: &RwLock<Self>, pid: u32) -> Result<ReloadResult> {
+        let (mut result, process_name) = {
+            let mut terminator = terminator.write().await;
+            let process_name = match terminator.preflight(pid, true) {
+                Ok(name) => name,
+                Err(refusal) => {
+                    let still_running = terminator.system.process(Pid::from_u32(pid)).is_some();
+                    return Ok(ReloadResult {
+                        result: refusal,
+                        still_running,
+                    });
+                }
+            };
+            let result = terminator.send_control_signal(
+                pid,
+                &process_name,
+                Signal::Hangup,
+                "reload",
+                "sent SIGHUP",
+            );
+            (result, process_name)
+        };
+        if !result.success {
+            return Ok(ReloadResult {
+                result,
+                still_running: true,
+            });
+        }
+
+        let still_running = !wait_for_pid_exit(pid, RELOAD_SETTLE).await;
+        if !still_running {
+            result.success = false;
+            result.message = format!(
+                "Process {} ({}) exited on SIGHUP instead of reloading",
+                pid, process_name
+            );
+        }
+        Ok(ReloadResult {
+            result,
+            still_running,
+        })
+    }
+
+    fn send_control_signal(
+        &self,
+        pid: u32,
+        process_name: &str,
+        signal: Signal,
+        verb: &str,
+        past_tense: &str,
+    ) -> KillResult {
+        let sent = self
+            .system
+            .process(Pid::from_u32(pid))
+            .and_then(|process| process.kill_with(signal))
+            .unwrap_or(false);
+
+        if sent {
+            KillResult {
+                success: true,
+                message: format!("Process {} ({}) {}", pid, process_name, past_tense),
+                required_elevation: false,
+            }
+        } else {
+            KillResult {
+                success: false,
+                message: format!(
+                    "Failed to {} process {} ({}). May require elevated privileges.",
+                    verb, pid, process_name
+                ),
+                required_elevation: true,
+            }
+        }
+    }
+
+    /// Terminate the whole process group that `pid` belongs to
+    ///
+    /// On Unix the signal is delivered to the group with `killpg`; on Windows the
+    /// process and its descendants are terminated individually. Safety checks are
+    /// applied to `pid` itself.
+    pub fn terminate_group(&mut self, pid: u32, force: bool) -> Result<GroupKillResult> {
+        let process_name = match self.preflight(pid, force) {
+            Ok(name) => name,
+            Err(refusal) => {
+                return Ok(GroupKillResult {
+                    result: refusal,
+                    signaled_count: 0,
+                })
+            }
+        };
+
+        self.signal_group(pid, &process_name, force)
+    }
+
+    #[cfg(unix)]
+    fn signal_group(
+        &mut self,
+        pid: u32,
+        process_name: &str,
+        force: bool,
+    ) -> Result<GroupKillResult> {
+        let pgid = unsafe { libc::getpgid(pid as libc::pid_t) };
+        if pgid < 0 {
+            return Err(std::io::Error::last_os_error().into());
+        }
+
+        // Never signal our own group or the init/kernel groups
+        let own_pgid = unsafe { libc::getpgrp() };
+        if pgid == own_pgid || pgid <= 1 {
+            return Ok(GroupKillResult {
+                result: KillResult {
+                    success: false,
+                    message: format!("Cannot terminate protected process group: {}", pgid),
+                    required_elevation: false,
+                },
+                signaled_count: 0,
+            });
+        }
+
+        let members = self
+            .system
+            .processes()
+            .iter()
+            .filter(|(_, p)| p.thread_kind().is_none())
+            .filter(|(member, _)| unsafe { libc::getpgid(member.as_u32() as libc::pid_t) } == pgid)
+            .count();
+
+        let signal = if force { libc::SIGKILL } else { libc::SIGTERM };
+
+        if unsafe { libc::killpg(pgid, signal) } == 0 {
+            Ok(GroupKillResult {
+                result: KillResult {
+                    success: true,
+                    message: format!(
+                        "Process group {} of {} ({}) terminated: {} processes signaled",
+                        pgid, pid, process_name, members
+                    ),
+                    required_elevation: false,
+                },
+                signaled_count: members,
+            })
+        } else {
+            let error = std::io::Error::last_os_error();
+            let required_elevation = error.raw_os_error() == Some(libc::EPERM);
+            Ok(GroupKillResult {
+                result: KillResult {
+                    success: false,
+                    message: format!(
+                        "Failed to terminate process group {} of {} ({}): {}",
+                        pgid, pid, process_name, error
+                    ),
+                    required_elevation,
+                },
+                signaled_count: 0,
+            })
+        }
+    }
+
+    #[cfg(windows)]
+    fn signal_group(
+        &mut self,
+        pid: u32,
+        process_name: &str,
+        force: bool,
+    ) -> Result<GroupKillResult> {
+        // Windows has no killpg equivalent for arbitrary processes, so walk the tree
+        let mut targets = self.descendants_of(pid);
+        targets.push(pid);
+
+        let signal = if force { Signal::Kill } else { Signal::Term };
+        let mut signaled_count = 0;
+
+        // Descendants come before their parents, so children are terminated first
+        for target in targets {
+            let Some(process) = self.system.process(Pid::from_u32(target)) else {
+                continue;
+            };
+
+            let name = process.name().to_string_lossy().to_string();
+            if !check_process_safety(target, &name).is_safe() {
+                log::warn!("Skipping protected process {} ({}) in group kill", target, name);
+                continue;
+            }
+
+            if process.kill_with(signal).unwrap_or_else(|| process.kill()) {
+                signaled_count += 1;
+            }
+        }
+
+        Ok(GroupKillResult {
+            result: KillResult {
+                success: signaled_count > 0,
+                message: format!(
+                    "Terminated {} processes in the tree of {} ({})",
+                    signaled_count, pid, process_name
+                ),
+                required_elevation: signaled_count == 0,
+            },
+            signaled_count,
+        })
+    }
+
+    /// Direct children of `pid`, excluding threads
+    pub fn children_of(&self, pid: u32) -> Vec<u32> {
+        let parent = Pid::from_u32(pid);
+        self.system
+            .processes()
+            .iter()
+            .filter(|(child, process)| {
+                process.thread_kind().is_none()
+                    && process.parent() == Some(parent)
+                    && child.as_u32() != pid
+            })
+            .map(|(child, _)| child.as_u32())
+            .collect()
+    }
+
+    /// All descendants of `pid`, deepest first
+    ///
+    /// A process that started before its recorded parent is skipped along with its
+    /// subtree: its parent exited and the PID went to a newer process, as Windows
+    /// allows, so it isn't part of this tree.
+    pub fn descendants_of(&self, pid: u32) -> Vec<u32> {
+        let mut found: Vec<u32> = Vec::new();
+        let mut frontier = vec![Pid::from_u32(pid)];
+
+        while let Some(parent) = frontier.pop() {
+            let parent_start = self.system.process(parent).map_or(0, |p| p.start_time());
+            for (child, process) in self.system.processes() {
+                if process.thread_kind().is_none()
+                    && process.parent() == Some(parent)
+                    && child.as_u32() != pid
+                    && started_after_parent(process.start_time(), parent_start)
+                    && !found.contains(&child.as_u32())
+                {
+                    found.push(child.as_u32());
+                    frontier.push(*child);
+                }
+            }
+        }
+
+        found.reverse();
+        found
+    }
+
+    /// A process and all of its descendants in kill order, children before their parents
+    ///
+    /// Uses the table and listeners from `prepare_batch`. The tree comes from observed
+    /// parent links, so this works the same on every platform. Returns the refusal to
+    /// report when the root is refused or Process Surgeon itself is part of the tree;
+    /// otherwise each target still gets its own safety check when it is terminated.
+    pub fn tree_targets(
+        &self,
+        pid: u32,
+        force: bool,
+        listeners: &[PortInfo],
+    ) -> std::result::Result<Vec<u32>, KillResult> {
+        let ports = if force { Vec::new() } else { ports_of(listeners, pid) };
+        let process_name = self.check_target(pid, &ports)?;
+
+        let mut targets = self.descendants_of(pid);
+        if targets.iter().any(|&target| is_self_process(target)) {
+            return Err(KillResult {
+                success: false,
+                message: format!(
+                    "Cannot terminate the tree of {} ({}): it contains Process Surgeon",
+                    pid, process_name
+                ),
+                required_elevation: false,
+            });
+        }
+        targets.push(pid);
+        Ok(targets)
+    }
+
+    /// Simulate a termination without sending any signal
+    ///
+    /// Runs the same safety and existence checks as `terminate` and reports
+    /// what would happen. `success` is always false and the message is
+    /// prefixed with "[DRY RUN]".
+    pub fn simulate(&mut self, pid: u32, force: bool) -> KillResult {
+        match self.preflight(pid, force) {
+            Ok(process_name) => {
+                let signal = if force { "SIGKILL" } else { "SIGTERM" };
+                KillResult {
+                    success: false,
+                    message: format!(
+                        "[DRY RUN] Would send {} to {} (PID {})",
+                        signal, process_name, pid
+                    ),
+                    required_elevation: !self.is_owned_by_current_user(pid),
+                }
+            }
+            Err(refusal) => KillResult {
+                success: false,
+                message: format!("[DRY RUN] {}", refusal.message),
+                required_elevation: refusal.required_elevation,
+            },
+        }
+    }
+
+    /// Run the safety and existence checks shared by real and simulated kills
+    ///
+    /// Returns the process name if it may be signalled, or the refusal to report.
+    /// Holders of protected ports are only refused unless `force` is set.
+    fn preflight(&mut self, pid: u32, force: bool) -> std::result::Result<String, KillResult> {
         self.refresh();
+        let ports = if force {
+            Vec::new()
+        } else {
+            listening_ports_of(pid).map_err(|e| KillResult {
+                success: false,
+                message: e.to_string(),
+                required_elevation: false,
+            })?
+        };
+        self.check_target(pid, &ports)
+    }
 
+    /// The checks of `preflight` against the current table, given the PID's listening ports
+    fn check_target(&self, pid: u32, ports: &[u16]) -> std::result::Result<String, KillResult> {
         // Get process info for safety check
         let sysinfo_pid = Pid::from_u32(pid);
         let process = self.system.process(sysinfo_pid);
@@ -42,108 +562,174 @@ impl ProcessTerminator {
             .unwrap_or_else(|| "Unknown".to_string());
 
         // Perform safety check
-        let safety_result = check_process_safety(pid, &process_name);
+        let safety_result = check_process_safety_with_ports(pid, &process_name, ports);
         
         match safety_result {
             SafetyCheckResult::Safe => {
                 // Process is safe to terminate
             }
             SafetyCheckResult::ProtectedProcess(name) => {
-                return Ok(KillResult {
+                return Err(KillResult {
                     success: false,
                     message: format!("Cannot terminate protected system process: {}", name),
                     required_elevation: false,
                 });
             }
             SafetyCheckResult::ProtectedPid(p) => {
-                return Ok(KillResult {
+                return Err(KillResult {
                     success: false,
                     message: format!("Cannot terminate protected PID: {}", p),
                     required_elevation: false,
                 });
             }
-            SafetyCheckResult::SelfTermination => {
-                return Ok(KillResult {
+            SafetyCheckResult::ProtectedPort(port) => {
+                return Err(KillResult {
                     success: false,
-                    message: "Cannot terminate self".to_string(),
+                    message: format!(
+                        "Process {} holds protected port {}; force the kill to proceed",
+                        process_name, port
+                    ),
                     required_elevation: false,
                 });
             }
-        }
-
-        // Check if process exists
-        let process = match self.system.process(sysinfo_pid) {
-            Some(p) => p,
-            None => {
-                return Ok(KillResult {
+            SafetyCheckResult::SelfTermination => {
+                return Err(KillResult {
                     success: false,
-                    message: format!("Process {} not found", pid),
+                    message: "Cannot terminate self".to_string(),
                     required_elevation: false,
                 });
             }
-        };
+        }
 
-        // Attempt termination
-        let signal = if force { Signal::Kill } else { Signal::Term };
-        
-        if process.kill_with(signal).unwrap_or(false) {
-            Ok(KillResult {
-                success: true,
-                message: format!(
-                    "Process {} ({}) terminated successfully",
-                    pid, process_name
-                ),
-                required_elevation: false,
-            })
-        } else {
-            // Kill failed - might need elevation
-            Ok(KillResult {
+        // Check if process exists
+        if self.system.process(sysinfo_pid).is_none() {
+            return Err(KillResult {
                 success: false,
-                message: format!(
-                    "Failed to terminate process {} ({}). May require elevated privileges.",
-                    pid, process_name
-                ),
-                required_elevation: true,
-            })
+                message: format!("Process {} not found", pid),
+                required_elevation: false,
+            });
         }
+
+        Ok(process_name)
+    }
+
+    /// Capture the executable, arguments and working directory of a running process
+    pub fn launch_spec(&mut self, pid: u32) -> Option<LaunchSpec> {
+        let sysinfo_pid = Pid::from_u32(pid);
+
+        // The default refresh skips command lines and working directories
+        self.system.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&[sysinfo_pid]),
+            ProcessRefreshKind::new()
+                .with_exe(UpdateKind::Always)
+                .with_cmd(UpdateKind::Always)
+                .with_cwd(UpdateKind::Always),
+        );
+
+        let process = self.system.process(sysinfo_pid)?;
+
+        Some(LaunchSpec {
+            name: process.name().to_string_lossy().to_string(),
+            exe: process.exe().map(|p| p.to_path_buf()),
+            args: process.cmd().iter().skip(1).cloned().collect(),
+            cwd: process.cwd().map(|p| p.to_path_buf()),
+        })
     }
 
     /// Graceful termination with timeout
     /// Tries SIGTERM first, then SIGKILL after timeout
-    pub async fn terminate_graceful(&mut self, pid: u32, timeout_secs: u64) -> Result<KillResult> {
-        // First try graceful termination
-        let result = self.terminate(pid, false)?;
-        
-        if result.success {
-            return Ok(result);
-        }
+    pub async fn terminate_graceful(
+        terminator: &RwLock<Self>,
+        pid: u32,
+        timeout_secs: u64,
+    ) -> Result<KillResult> {
+        let ladder = vec![
+            (TerminationSignal::Term, timeout_secs),
+            (TerminationSignal::Kill, KILL_WAIT_SECS),
+        ];
+        Ok(Self::terminate_escalating(terminator, pid, ladder).await?.result)
+    }
 
-        if result.required_elevation {
-            return Ok(result);
-        }
+    /// Send each signal of `ladder` in turn, waiting up to its timeout (seconds) for exit
+    ///
+    /// Safety checks run once up front. An empty ladder uses `DEFAULT_ESCALATION_LADDER`.
+    /// Signals the platform can't deliver (e.g. SIGINT on Windows) are skipped. Step
+    /// timeouts are capped at `MAX_ESCALATION_STEP_SECS`, and the terminator is only
+    /// locked while sending each signal, not while waiting for the process to exit.
+    pub async fn terminate_escalating(
+        terminator: &RwLock<Self>,
+        pid: u32,
+        ladder: Vec<(TerminationSignal, u64)>,
+    ) -> Result<EscalationResult> {
+        let ladder = if ladder.is_empty() {
+            DEFAULT_ESCALATION_LADDER.to_vec()
+        } else {
+            ladder
+        };
 
-        // Wait for process to exit
-        let start = std::time::Instant::now();
-        let timeout = std::time::Duration::from_secs(timeout_secs);
+        let preflight = terminator.write().await.preflight(pid, false);
+        let process_name = match preflight {
+            Ok(name) => name,
+            Err(refusal) => {
+                return Ok(EscalationResult {
+                    result: refusal,
+                    step: None,
+                    signal: None,
+                })
+            }
+        };
 
-        while start.elapsed() < timeout {
-            self.refresh();
-            let sysinfo_pid = Pid::from_u32(pid);
-            
-            if self.system.process(sysinfo_pid).is_none() {
-                return Ok(KillResult {
-                    success: true,
-                    message: format!("Process {} terminated gracefully", pid),
-                    required_elevation: false,
-                });
+        for (step, (signal, timeout_secs)) in ladder.into_iter().enumerate() {
+            let sent = terminator
+                .read()
+                .await
+                .system
+                .process(Pid::from_u32(pid))
+                .map(|process| process.kill_with(to_sysinfo_signal(signal)));
+
+            match sent {
+                // Already gone, e.g. exited during the previous wait
+                None => return Ok(escalation_success(pid, &process_name, step, signal)),
+                Some(None) => {
+                    log::debug!("{:?} is not supported on this platform, skipping", signal);
+                    continue;
+                }
+                Some(Some(false)) => {
+                    return Ok(EscalationResult {
+                        result: KillResult {
+                            success: false,
+                            message: format!(
+                                "Failed to send {:?} to process {} ({}). May require elevated privileges.",
+                                signal, pid, process_name
+                            ),
+                            required_elevation: true,
+                        },
+                        step: Some(step),
+                        signal: Some(signal),
+                    })
+                }
+                Some(Some(true)) => {}
             }
-            
-            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+            let timeout_secs = timeout_secs.min(MAX_ESCALATION_STEP_SECS);
+            if wait_for_pid_exit(pid, Duration::from_secs(timeout_secs)).await {
+                return Ok(escalation_success(pid, &process_name, step, signal));
+            }
+            log::warn!("Process {} still running after {:?}, escalating", pid, signal);
         }
 
-        // Timeout - force kill
-        log::warn!("Process {} did not exit gracefully, forcing termination", pid);
-        self.terminate(pid, true)
+        Ok(EscalationResult {
+            result: KillResult {
+                success: false,
+                message: format!(
+                    "Process {} ({}) did not exit after every escalation step",
+                    pid, process_name
+                ),
+                required_elevation: false,
+            },
+            step: None,
+            signal: None,
+        })
     }
 
     /// Check if current user owns the process
@@ -171,111 +757,234 @@ impl ProcessTerminator {
     }
 }
 
+/// Wait for a process to exit, returning false if it is still running after `timeout`
+///
+/// Polls just this PID with its own process table, so no terminator lock is needed
+/// while waiting.
+pub async fn wait_for_pid_exit(pid: u32, timeout: Duration) -> bool {
+    let start = Instant::now();
+
+    loop {
+        // Zombies have already exited; they're only waiting to be reaped
+        if live_pids(&[pid]).is_empty() {
+            return true;
+        }
+
+        if start.elapsed() >= timeout {
+            return false;
+        }
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+}
+
+/// Local ports a PID is listening on
+///
+/// Fails when the scan does, so callers refuse the kill rather than skip the
+/// protected-port check.
+fn listening_ports_of(pid: u32) -> Result<Vec<u16>> {
+    let listeners = scan_listening_ports().map_err(protected_port_check_failed)?;
+    Ok(ports_of(&listeners, pid))
+}
+
+/// Error for a listener scan that failed while checking for protected ports
+fn protected_port_check_failed(error: anyhow::Error) -> anyhow::Error {
+    log::warn!("Port scan for safety check failed: {}", error);
+    anyhow!("Refusing to terminate: could not check for protected ports ({})", error)
+}
+
+/// Whether a process started at `child_start` can be a child of one started at
+/// `parent_start`, in seconds since the epoch; 0 means the time is unknown
+fn started_after_parent(child_start: u64, parent_start: u64) -> bool {
+    child_start == 0 || parent_start == 0 || child_start >= parent_start
+}
+
+/// Local ports among `listeners` that a PID holds
+fn ports_of(listeners: &[PortInfo], pid: u32) -> Vec<u16> {
+    listeners
+        .iter()
+        .filter(|p| p.pids.contains(&pid))
+        .map(|p| p.local_port)
+        .collect()
+}
+
+/// Seconds to wait for exit after SIGKILL
+const KILL_WAIT_SECS: u64 = 2;
+
+/// Longest a single escalation step waits for exit; longer requested timeouts are capped
+pub const MAX_ESCALATION_STEP_SECS: u64 = 60;
+
+/// How long `reload` watches a process after SIGHUP before reporting it survived
+const RELOAD_SETTLE: Duration = Duration::from_millis(500);
+
+/// Ladder used when `terminate_escalating` is given no steps: SIGINT, SIGTERM, SIGKILL
+pub const DEFAULT_ESCALATION_LADDER: [(TerminationSignal, u64); 3] = [
+    (TerminationSignal::Int, 3),
+    (TerminationSignal::Term, 5),
+    (TerminationSignal::Kill, KILL_WAIT_SECS),
+];
+
+/// Termination signals this platform can deliver, mildest first
+pub fn supported_termination_signals() -> Vec<TerminationSignal> {
+    [
+        TerminationSignal::Int,
+        TerminationSignal::Term,
+        TerminationSignal::Quit,
+        TerminationSignal::Kill,
+    ]
+    .into_iter()
+    .filter(|signal| SUPPORTED_SIGNALS.contains(&to_sysinfo_signal(*signal)))
+    .collect()
+}
+
+fn to_sysinfo_signal(signal: TerminationSignal) -> Signal {
+    match signal {
+        TerminationSignal::Int => Signal::Interrupt,
+        TerminationSignal::Term => Signal::Term,
+        TerminationSignal::Quit => Signal::Quit,
+        TerminationSignal::Kill => Signal::Kill,
+    }
+}
+
+fn escalation_success(
+    pid: u32,
+    process_name: &str,
+    step: usize,
+    signal: TerminationSignal,
+) -> EscalationResult {
+    EscalationResult {
+        result: KillResult {
+            success: true,
+            message: format!("Process {} ({}) exited after {:?}", pid, process_name, signal),
+            required_elevation: false,
+        },
+        step: Some(step),
+        signal: Some(signal),
+    }
+}
+
 impl Default for ProcessTerminator {
     fn default() -> Self {
         Self::new()
     }
 }
 
-/// Platform-specific elevated termination
+/// Run an elevated helper to completion, protecting its PID while it is alive
+fn run_elevated_helper(command: &mut std::process::Command) -> std::io::Result<std::process::Output> {
+    use std::process::Stdio;
+
+    let child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let _guard = ElevatedHelperGuard::register(child.id());
+    child.wait_with_output()
+}
+
+/// How `request_elevated_termination` asks for privileges on this platform
 #[cfg(target_os = "macos")]
+pub const ELEVATION_METHOD: &str = "osascript";
+
+#[cfg(target_os = "linux")]
+pub const ELEVATION_METHOD: &str = "pkexec";
+
+#[cfg(target_os = "windows")]
+pub const ELEVATION_METHOD: &str = "runas";
+
+/// Platform-specific elevated termination
 pub fn request_elevated_termination(pid: u32, force: bool) -> Result<KillResult> {
-    use std::process::Command;
-    
+    let mut results = request_elevated_termination_many(&[pid], force)?;
+    Ok(results.remove(0))
+}
+
+/// Elevated termination of several PIDs behind a single prompt, one result per PID
+///
+/// An open elevated session handles the PIDs without prompting. Otherwise they all go
+/// to one helper run, which can't tell which PID failed, so a failure fails them all.
+pub fn request_elevated_termination_many(pids: &[u32], force: bool) -> Result<Vec<KillResult>> {
+    let mut results: Vec<KillResult> = pids
+        .iter()
+        .map_while(|&pid| kill_via_elevated_session(pid, force))
+        .collect();
+
+    let remaining = &pids[results.len()..];
+    if remaining.is_empty() {
+        return Ok(results);
+    }
+
+    let output = run_elevated_helper(&mut elevated_kill_command(remaining, force))?;
+    let error = String::from_utf8_lossy(&output.stderr);
+    results.extend(remaining.iter().map(|&pid| {
+        if output.status.success() {
+            KillResult {
+                success: true,
+                message: format!("Process {} terminated with elevated privileges", pid),
+                required_elevation: true,
+            }
+        } else {
+            KillResult {
+                success: false,
+                message: format!("Elevated termination failed: {}", error),
+                required_elevation: true,
+            }
+        }
+    }));
+    Ok(results)
+}
+
+#[cfg(target_os = "macos")]
+fn elevated_kill_command(pids: &[u32], force: bool) -> std::process::Command {
     // On macOS, we'll use the sidecar pattern with osascript for now
     // In production, this would use SMAppService
     let signal = if force { "KILL" } else { "TERM" };
-    
+    let pids: Vec<String> = pids.iter().map(|pid| pid.to_string()).collect();
+
     let script = format!(
         "do shell script \"kill -{} {}\" with administrator privileges",
-        signal, pid
+        signal,
+        pids.join(" ")
     );
-    
-    let output = Command::new("osascript")
-        .arg("-e")
-        .arg(&script)
-        .output()?;
-    
-    if output.status.success() {
-        Ok(KillResult {
-            success: true,
-            message: format!("Process {} terminated with elevated privileges", pid),
-            required_elevation: true,
-        })
-    } else {
-        let error = String::from_utf8_lossy(&output.stderr);
-        Ok(KillResult {
-            success: false,
-            message: format!("Elevated termination failed: {}", error),
-            required_elevation: true,
-        })
-    }
+
+    let mut command = std::process::Command::new("osascript");
+    command.arg("-e").arg(script);
+    command
 }
 
 #[cfg(target_os = "linux")]
-pub fn request_elevated_termination(pid: u32, force: bool) -> Result<KillResult> {
-    use std::process::Command;
-    
+fn elevated_kill_command(pids: &[u32], force: bool) -> std::process::Command {
     let signal = if force { "-9" } else { "-15" };
-    
+
     // Try pkexec first (Polkit)
-    let output = Command::new("pkexec")
+    let mut command = std::process::Command::new("pkexec");
+    command
         .arg("kill")
         .arg(signal)
-        .arg(pid.to_string())
-        .output()?;
-    
-    if output.status.success() {
-        Ok(KillResult {
-            success: true,
-            message: format!("Process {} terminated with elevated privileges", pid),
-            required_elevation: true,
-        })
-    } else {
-        let error = String::from_utf8_lossy(&output.stderr);
-        Ok(KillResult {
-            success: false,
-            message: format!("Elevated termination failed: {}", error),
-            required_elevation: true,
-        })
-    }
+        .args(pids.iter().map(|pid| pid.to_string()));
+    command
 }
 
 #[cfg(target_os = "windows")]
-pub fn request_elevated_termination(pid: u32, _force: bool) -> Result<KillResult> {
-    use std::process::Command;
-    
+fn elevated_kill_command(pids: &[u32], _force: bool) -> std::process::Command {
     // On Windows, we'd use ShellExecute with "runas" verb
     // For now, use taskkill with elevated prompt
-    let output = Command::new("powershell")
-        .args([
-            "-Command",
-            &format!(
-                "Start-Process -FilePath 'taskkill' -ArgumentList '/F /PID {}' -Verb RunAs -Wait",
-                pid
-            ),
-        ])
-        .output()?;
-    
-    if output.status.success() {
-        Ok(KillResult {
-            success: true,
-            message: format!("Process {} terminated with elevated privileges", pid),
-            required_elevation: true,
-        })
-    } else {
-        let error = String::from_utf8_lossy(&output.stderr);
-        Ok(KillResult {
-            success: false,
-            message: format!("Elevated termination failed: {}", error),
-            required_elevation: true,
-        })
-    }
+    let targets: Vec<String> = pids.iter().map(|pid| format!("/PID {}", pid)).collect();
+
+    let mut command = std::process::Command::new("powershell");
+    command.args([
+        "-Command",
+        &format!(
+            "Start-Process -FilePath 'taskkill' -ArgumentList '/F {}' -Verb RunAs -Wait",
+            targets.join(" ")
+        ),
+    ]);
+    command
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use sysinfo::ProcessStatus;
 
     #[test]
     fn test_terminator_creation() {
@@ -283,4 +992,273 @@ mod tests {
         // Just verify it creates successfully
         assert!(true);
     }
+
+    #[test]
+    fn test_supported_termination_signals() {
+        let signals = supported_termination_signals();
+        assert!(signals.contains(&TerminationSignal::Kill));
+        #[cfg(unix)]
+        assert_eq!(signals.len(), 4);
+    }
+
+    #[test]
+    fn test_simulate_refuses_self() {
+        let mut terminator = ProcessTerminator::new();
+        let result = terminator.simulate(std::process::id(), false);
+        assert!(!result.success);
+        assert!(result.message.starts_with("[DRY RUN]"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_simulate_does_not_signal() {
+        let mut child = std::process::Command::new("sleep")
+            .arg("5")
+            .spawn()
+            .expect("failed to spawn sleep");
+
+        let mut terminator = ProcessTerminator::new();
+        let result = terminator.simulate(child.id(), false);
+
+        assert!(!result.success);
+        assert!(result.message.starts_with("[DRY RUN] Would send SIGTERM"));
+        assert!(child.try_wait().unwrap().is_none());
+
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_terminate_expecting_detects_pid_reuse() {
+        let mut child = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .expect("failed to spawn sleep");
+
+        // The caller saw another process under this PID before it was recycled
+        let mut terminator = ProcessTerminator::new();
+        let error = terminator
+            .terminate_expecting(child.id(), false, Some("postgres"))
+            .unwrap_err();
+        let reused = error.downcast_ref::<PidReused>().unwrap();
+        assert_eq!(reused.expected, "postgres");
+        assert_eq!(reused.actual, "sleep");
+        assert!(child.try_wait().unwrap().is_none());
+
+        let result = terminator
+            .terminate_expecting(child.id(), false, Some("sleep"))
+            .unwrap();
+        assert!(result.success);
+        let _ = child.wait();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_terminate_group_refuses_own_group() {
+        use std::os::unix::process::CommandExt;
+
+        // Child inherits our process group, which must never be signalled
+        let mut child = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .expect("failed to spawn sleep");
+
+        let mut terminator = ProcessTerminator::new();
+        let result = terminator.terminate_group(child.id(), false).unwrap();
+        assert!(!result.result.success);
+        assert_eq!(result.signaled_count, 0);
+        let _ = child.kill();
+        let _ = child.wait();
+
+        // A child in its own group can be signalled as a unit
+        let mut leader = std::process::Command::new("sleep")
+            .arg("30")
+            .process_group(0)
+            .spawn()
+            .expect("failed to spawn sleep");
+
+        let result = terminator.terminate_group(leader.id(), true).unwrap();
+        assert!(result.result.success);
+        assert_eq!(result.signaled_count, 1);
+        let _ = leader.wait();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_suspend_and_resume() {
+        let mut child = std::process::Command::new("sleep").arg("30").spawn().unwrap();
+        let pid = child.id();
+        let mut terminator = ProcessTerminator::new();
+
+        assert!(terminator.suspend(pid).unwrap().success);
+        std::thread::sleep(Duration::from_millis(100));
+        terminator.refresh();
+        assert_eq!(
+            terminator.system.process(Pid::from_u32(pid)).map(|p| p.status()),
+            Some(ProcessStatus::Stop)
+        );
+
+        assert!(terminator.resume(pid).unwrap().success);
+
+        child.kill().unwrap();
+        child.wait().unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_reload() {
+        let mut survivor = std::process::Command::new("sh")
+            .args(["-c", "trap '' HUP; sleep 30"])
+            .spawn()
+            .expect("failed to spawn sh");
+        let mut casualty = std::process::Command::new("sleep").arg("30").spawn().unwrap();
+        std::thread::sleep(Duration::from_millis(100));
+        let terminator = RwLock::new(ProcessTerminator::new());
+
+        let reloaded = ProcessTerminator::reload(&terminator, survivor.id()).await.unwrap();
+        assert!(reloaded.result.success);
+        assert!(reloaded.still_running);
+
+        let reloaded = ProcessTerminator::reload(&terminator, casualty.id()).await.unwrap();
+        assert!(!reloaded.result.success);
+        assert!(!reloaded.still_running);
+
+        survivor.kill().unwrap();
+        survivor.wait().unwrap();
+        casualty.wait().unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_descendants_of() {
+        let mut shell = std::process::Command::new("sh")
+            .args(["-c", "sleep 30 & wait"])
+            .spawn()
+            .expect("failed to spawn sh");
+        std::thread::sleep(Duration::from_millis(200));
+
+        let mut terminator = ProcessTerminator::new();
+        terminator.refresh();
+        let descendants = terminator.descendants_of(shell.id());
+        assert_eq!(descendants.len(), 1);
+
+        for pid in descendants {
+            unsafe { libc::kill(pid as libc::pid_t, libc::SIGKILL) };
+        }
+        let _ = shell.kill();
+        let _ = shell.wait();
+    }
+
+    #[test]
+    fn test_started_after_parent() {
+        assert!(started_after_parent(1_700_000_100, 1_700_000_000));
+        assert!(started_after_parent(1_700_000_000, 1_700_000_000));
+        // The parent's PID was reused by a process started after the child
+        assert!(!started_after_parent(1_700_000_000, 1_700_000_100));
+        assert!(started_after_parent(0, 1_700_000_100));
+        assert!(started_after_parent(1_700_000_000, 0));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_children_of_skips_grandchildren() {
+        let mut shell = std::process::Command::new("sh")
+            .args(["-c", "sh -c 'sleep 30 & wait' & wait"])
+            .spawn()
+            .expect("failed to spawn sh");
+        std::thread::sleep(Duration::from_millis(200));
+
+        let mut terminator = ProcessTerminator::new();
+        terminator.refresh();
+        assert_eq!(terminator.children_of(shell.id()).len(), 1);
+        let descendants = terminator.descendants_of(shell.id());
+        assert_eq!(descendants.len(), 2);
+
+        for pid in descendants {
+            unsafe { libc::kill(pid as libc::pid_t, libc::SIGKILL) };
+        }
+        let _ = shell.kill();
+        let _ = shell.wait();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_tree_targets_kill_leaves_first() {
+        let mut shell = std::process::Command::new("sh")
+            .args(["-c", "sleep 30 & sleep 30 & wait"])
+            .spawn()
+            .expect("failed to spawn sh");
+        std::thread::sleep(Duration::from_millis(200));
+
+        let mut terminator = ProcessTerminator::new();
+        let listeners = terminator.prepare_batch(true).unwrap();
+        let targets = terminator.tree_targets(shell.id(), true, &listeners).unwrap();
+        assert_eq!(targets.len(), 3);
+        assert_eq!(targets[2], shell.id());
+
+        let results: Vec<KillResult> = targets
+            .into_iter()
+            .map(|pid| terminator.terminate_in_batch(pid, true, None, &listeners).unwrap())
+            .collect();
+        assert!(results.iter().all(|result| result.success));
+        assert!(results[2].message.contains(&shell.id().to_string()));
+        let _ = shell.wait();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_launch_spec_captures_args() {
+        let mut child = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .expect("failed to spawn sleep");
+
+        let mut terminator = ProcessTerminator::new();
+        let spec = terminator.launch_spec(child.id()).expect("process should exist");
+
+        assert!(spec.exe.is_some());
+        assert_eq!(spec.args, vec![OsString::from("30")]);
+
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_wait_for_pid_exit_treats_zombie_as_exited() {
+        let mut child = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .expect("failed to spawn sleep");
+        let pid = child.id();
+
+        assert!(!wait_for_pid_exit(pid, Duration::from_millis(200)).await);
+
+        let _ = child.kill();
+        assert!(wait_for_pid_exit(pid, Duration::from_secs(2)).await);
+        let _ = child.wait();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_terminate_escalating_moves_past_ignored_signal() {
+        // The shell ignores SIGINT, so the ladder has to escalate to SIGTERM
+        let mut child = std::process::Command::new("sh")
+            .args(["-c", "trap '' INT; sleep 30"])
+            .spawn()
+            .expect("failed to spawn sh");
+        std::thread::sleep(Duration::from_millis(100));
+
+        let terminator = RwLock::new(ProcessTerminator::new());
+        let ladder = vec![(TerminationSignal::Int, 1), (TerminationSignal::Term, 2)];
+        let outcome = ProcessTerminator::terminate_escalating(&terminator, child.id(), ladder)
+            .await
+            .unwrap();
+
+        assert!(outcome.result.success);
+        assert_eq!(outcome.step, Some(1));
+        assert_eq!(outcome.signal, Some(TerminationSignal::Term));
+        let _ = child.wait();
+    }
 }
@@ -184,7 +184,9 @@ export const useStore = create<StoreState>((set, get) => ({
     const process = get().processes.find(p => p.container?.id === containerId);
     
     try {
-      const result = await invoke<KillResult>('container_action', { containerId, action });
+      const result = await invoke<KillResult>('container_action', {
+        request: { containerId, action },
+      });
       
       // Add to history
       get().addHistoryEntry({
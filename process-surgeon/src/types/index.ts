@@ -20,6 +20,83 @@ export type ContainerRuntime = 'docker' | 'podman' | 'containerd' | 'unknown';
 
 export type ContainerAction = 'stop' | 'kill' | 'remove' | 'restart';
 
+export type ConflictKind = 'name' | 'port';
+
+export interface ConflictingContainer {
+  runtime: ContainerRuntime;
+  endpoint: string;
+  id: string;
+  name: string;
+}
+
+export interface RuntimeConflict {
+  kind: ConflictKind;
+  value: string;
+  containers: ConflictingContainer[];
+}
+
+export interface ContainerActionRequest {
+  containerId: string;
+  action: ContainerAction;
+  timeoutSecs?: number;
+}
+
+export interface RecreateResult {
+  id: string;
+  name: string;
+  started: boolean;
+  warnings: string[];
+}
+
+export type PortStatus = 'free' | 'in_use' | 'permission_denied';
+
+export interface PortAvailability {
+  port: number;
+  protocol: Protocol;
+  status: PortStatus;
+}
+
+export interface PortPressure {
+  rangeStart: number;
+  rangeEnd: number;
+  established: number;
+  timeWait: number;
+  inUse: number;
+  utilizationPercent: number;
+  warning: boolean;
+}
+
+export type Reachability = 'connected' | 'refused' | 'timeout';
+
+export interface ReachabilityCheck {
+  port: number;
+  address: string;
+  status: Reachability;
+}
+
+export interface ListenerInterface {
+  protocol: Protocol;
+  interface: string | null;
+  address: string;
+  port: number;
+}
+
+export interface PortInfo {
+  protocol: Protocol;
+  localAddress: string;
+  localPort: number;
+  remoteAddress: string | null;
+  remotePort: number | null;
+  state: SocketState;
+  pids: number[];
+  isLoopback: boolean;
+  isWildcard: boolean;
+  isIpv6: boolean;
+  rxBytes: number | null;
+  txBytes: number | null;
+  netns: string | null;
+}
+
 export interface PortEntry {
   protocol: Protocol;
   localAddress: string;
@@ -27,6 +104,32 @@ export interface PortEntry {
   remoteAddress: string | null;
   remotePort: number | null;
   state: SocketState;
+  isLoopback: boolean;
+  isWildcard: boolean;
+  isIpv6: boolean;
+  remoteHostname: string | null;
+  serviceName: string | null;
+  rxBytes: number | null;
+  txBytes: number | null;
+  remoteAsn: AsnInfo | null;
+  connectionCount: number;
+  sharedPort: boolean;
+  inheritedSocket: boolean;
+  portKey: string;
+  netns: string | null;
+}
+
+export interface AsnInfo {
+  asn: number;
+  org: string;
+  country: string | null;
+}
+
+export interface ContainerStats {
+  containerId: string;
+  cpuPercent: number;
+  memoryUsage: number;
+  memoryLimit: number;
 }
 
 export interface ContainerPort {
@@ -36,6 +139,22 @@ export interface ContainerPort {
   hostIp: string | null;
 }
 
+export interface DockerEndpointAttempt {
+  endpoint: string;
+  error: string | null;
+}
+
+export interface DockerDiagnostics {
+  available: boolean;
+  endpoint: string | null;
+  remote: boolean;
+  attempts: DockerEndpointAttempt[];
+  runtime: ContainerRuntime | null;
+  serverVersion: string | null;
+  apiVersion: string | null;
+  versionError: string | null;
+}
+
 export interface ContainerInfo {
   id: string;
   name: string;
@@ -44,6 +163,56 @@ export interface ContainerInfo {
   state: string;
   runtime: ContainerRuntime;
   ports: ContainerPort[];
+  composeProject: string | null;
+  composeService: string | null;
+  health: ContainerHealth | null;
+}
+
+export interface HostPortConflict {
+  port: number;
+  protocol: Protocol;
+  containers: ContainerInfo[];
+  processes: ProcessNode[];
+}
+
+export type ContainerHealth = 'starting' | 'healthy' | 'unhealthy';
+
+export type Exposure =
+  | { kind: 'external' }
+  | { kind: 'loopbackOnly' }
+  | { kind: 'specificInterfaces'; addresses: string[] };
+
+export interface ProcessInfo {
+  pid: number;
+  name: string;
+  exePath: string | null;
+  commandLine: string | null;
+  args: string[];
+  user: string;
+  memoryUsage: number;
+  cpuUsage: number;
+  diskReadBytes: number;
+  diskWrittenBytes: number;
+  startTime: string | null;
+  parentPid: number | null;
+}
+
+export interface ProcessDetail {
+  pid: number;
+  name: string;
+  exePath: string | null;
+  commandLine: string | null;
+  args: string[];
+  user: string;
+  memoryUsage: number;
+  cpuUsage: number;
+  diskReadBytes: number;
+  diskWrittenBytes: number;
+  startTime: string | null;
+  parentPid: number | null;
+  isProtected: boolean;
+  protectionReason: string | null;
+  isDockerProxy: boolean;
 }
 
 export interface ProcessNode {
@@ -52,22 +221,127 @@ export interface ProcessNode {
   name: string;
   exePath: string | null;
   commandLine: string | null;
+  args: string[];
   user: string;
   memoryUsage: number;
   cpuUsage: number;
+  diskReadBytes: number;
+  diskWrittenBytes: number;
   startTime: string | null;
+  uptimeSecs: number | null;
+  age: string | null;
   ports: PortEntry[];
   isDockerProxy: boolean;
+  containerized: boolean;
+  isTunnel: boolean;
+  tunnelTarget: string | null;
   container: ContainerInfo | null;
   isProtected: boolean;
+  ignored: boolean;
+  isOrphaned: boolean;
+  isSuspended: boolean;
+  note: string | null;
+}
+
+export interface WatchedProcess {
+  pid: number;
+  alive: boolean;
+  process: ProcessNode | null;
+}
+
+export interface ProcessGroupNode extends ProcessNode {
+  workerPids: number[];
+}
+
+export interface GroupedAppState {
+  groups: ProcessGroupNode[];
+  totalConnections: number;
+  listeningPorts: number;
+  dockerAvailable: boolean;
+  lastUpdated: string;
+  warnings: string[];
+}
+
+export type SortKey = 'pid' | 'name' | 'memory' | 'cpu' | 'port';
+
+export type ContainerSortKey = 'name' | 'state' | 'portCount';
+
+export type ProxyFormat = 'nginx' | 'caddy';
+
+export interface ProcessPage {
+  processes: ProcessNode[];
+  total: number;
+  offset: number;
+  limit: number;
+  totalConnections: number;
+  listeningPorts: number;
+  dockerAvailable: boolean;
+  lastUpdated: string;
+  warnings: string[];
 }
 
 export interface AppState {
   processes: ProcessNode[];
   totalConnections: number;
   listeningPorts: number;
+  tcpCount: number;
+  udpCount: number;
+  establishedCount: number;
+  ipv6Count: number;
   dockerAvailable: boolean;
   lastUpdated: string;
+  warnings: string[];
+  unixSockets: UnixSocket[];
+  limitedPrivileges: boolean;
+  truncated: boolean;
+  totalProcesses: number;
+  stateHistogram: Partial<Record<SocketState, number>>;
+}
+
+export type ScanUpdate =
+  | { kind: 'notModified'; generation: number }
+  | { kind: 'updated'; generation: number; state: AppState };
+
+export type UnixSocketState = 'listening' | 'connected' | 'unconnected' | 'unknown';
+
+export interface UnixSocket {
+  path: string;
+  pids: number[];
+  state: UnixSocketState;
+}
+
+export interface PortChange {
+  pid: number;
+  name: string;
+  added: PortEntry[];
+  removed: PortEntry[];
+}
+
+export interface ScanDiff {
+  added: ProcessNode[];
+  removed: ProcessNode[];
+  portChanges: PortChange[];
+}
+
+export interface SnapshotListener {
+  pid: number;
+  processName: string;
+  protocol: Protocol;
+  localAddress: string;
+  localPort: number;
+}
+
+export interface SnapshotComparison {
+  snapshot: string;
+  takenAt: string;
+  added: SnapshotListener[];
+  removed: SnapshotListener[];
+}
+
+export interface Dashboard {
+  appState: AppState;
+  containers: ContainerInfo[];
+  dockerAvailable: boolean;
 }
 
 export interface KillResult {
@@ -76,8 +350,148 @@ export interface KillResult {
   requiredElevation: boolean;
 }
 
+export type FreePortMethod = 'alreadyFree' | 'container' | 'process';
+
+export interface FreePortResult {
+  port: number;
+  method: FreePortMethod;
+  success: boolean;
+  containers: ContainerInfo[];
+  pids: number[];
+  results: KillResult[];
+}
+
+export interface WatchAction {
+  pid: number;
+  name: string;
+  reason: string;
+  result: KillResult;
+  at: string;
+}
+
+export type ErrorCode =
+  | 'SCAN_ERROR'
+  | 'SCAN_CANCELLED'
+  | 'KILL_ERROR'
+  | 'DOCKER_UNAVAILABLE'
+  | 'DOCKER_ERROR'
+  | 'ACCESS_DENIED'
+  | 'NOT_FOUND'
+  | 'SAFETY_VIOLATION'
+  | 'INVALID_RANGE'
+  | 'RESTART_UNAVAILABLE'
+  | 'RESTART_TIMEOUT'
+  | 'SPAWN_ERROR'
+  | 'GEOIP_ERROR'
+  | 'CONFIG_ERROR'
+  | 'NOT_SUPPORTED'
+  | 'OPEN_ERROR'
+  | 'INVALID_INPUT'
+  | 'PRIORITY_ERROR'
+  | 'SAFE_MODE_BLOCKED'
+  | 'AFFINITY_ERROR'
+  | 'AMBIGUOUS_TARGET'
+  | 'DOCKER_VERSION_MISMATCH'
+  | 'PID_REUSED'
+  | 'OOM_SCORE_ERROR'
+  | 'DEBUGGER_NOT_FOUND'
+  | 'BACKTRACE_ERROR';
+
+export type RiskLevel = 'low' | 'medium' | 'high';
+
+export interface KillRisk {
+  pid: number;
+  level: RiskLevel;
+  reasons: string[];
+}
+
+export type ProtectionKind = 'name' | 'pid' | 'port' | 'selfProcess';
+
+export interface ProtectionExplanation {
+  pid: number;
+  protected: boolean;
+  kind: ProtectionKind | null;
+  rule: string | null;
+  reason: string | null;
+}
+
+export interface ResourceThresholds {
+  cpuPercent: number | null;
+  memoryBytes: number | null;
+  consecutiveSamples: number | null;
+}
+
+export type ThresholdKind = 'cpu' | 'memory';
+
+export interface ResourceAlert {
+  process: ProcessNode;
+  threshold: ThresholdKind;
+  samples: number;
+}
+
+export interface PortStateChange {
+  port: number;
+  previousPids: number[];
+  pids: number[];
+  at: string;
+}
+
+export interface ContainerEvent {
+  id: string;
+  name: string | null;
+  action: string;
+  state: string;
+  at: string;
+}
+
+export type TerminationSignal = 'int' | 'term' | 'quit' | 'kill';
+
+export interface Capabilities {
+  supportsSignals: TerminationSignal[];
+  supportsSuspend: boolean;
+  supportsRenice: boolean;
+  supportsKillpg: boolean;
+  elevationMethod: string | null;
+  dockerAvailable: boolean;
+}
+
+export interface EscalationResult extends KillResult {
+  step: number | null;
+  signal: TerminationSignal | null;
+}
+
+export interface GroupKillResult extends KillResult {
+  signaledCount: number;
+}
+
+export interface ReloadResult extends KillResult {
+  stillRunning: boolean;
+}
+
+export type BacktraceTool = 'gdb' | 'lldb' | 'py-spy';
+
+export interface ProcessBacktrace {
+  pid: number;
+  tool: BacktraceTool;
+  output: string;
+}
+
+export interface OomScoreChange {
+  pid: number;
+  previous: number;
+  current: number;
+}
+
+export interface DrainResult extends KillResult {
+  listeningPorts: number[];
+  openConnections: number;
+  waitedMs: number;
+  drainSupported: boolean;
+  cancelled: boolean;
+}
+
 export interface AppError {
-  code: string;
+  code: ErrorCode;
   message: string;
   details: string | null;
 }
@@ -94,3 +508,12 @@ export interface SortOptions {
   field: 'pid' | 'name' | 'port' | 'memory' | 'cpu' | 'user';
   direction: 'asc' | 'desc';
 }
+
+export type LogLevel = 'error' | 'warn' | 'info' | 'debug' | 'trace';
+
+export interface LogEntry {
+  timestamp: string;
+  level: LogLevel;
+  target: string;
+  message: string;
+}